@@ -0,0 +1,125 @@
+//! Pulse length limits learned at runtime from the observed pulses.
+//!
+//! Some receivers stretch or shrink the pulses so much that the fixed limits misclassify them,
+//! e.g. a (1,0) pair as (1,1). With adaptive limits, the decoder keeps a moving average of the
+//! lengths of the 100, 200, and 300 ms pulses and puts the limits between these clusters.
+
+use crate::fixed::Q16;
+use crate::{ACTIVE_0_LIMIT, ACTIVE_AB_LIMIT, ACTIVE_A_LIMIT};
+
+/// Number of pulses each cluster needs before its average is used.
+pub const CLUSTER_MIN_SAMPLES: u16 = 8;
+
+/// Upper limits of the active pulse lengths in microseconds.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ActiveLimits {
+    /// Maximum length of a 100 ms pulse, i.e. bit A is 0.
+    pub limit_0: u32,
+    /// Maximum length of a 200 ms pulse, i.e. bits (1,0).
+    pub limit_a: u32,
+    /// Maximum length of a 300 ms pulse, i.e. bits (1,1).
+    pub limit_ab: u32,
+}
+
+impl ActiveLimits {
+    /// The fixed limits.
+    pub const FIXED: Self = Self {
+        limit_0: ACTIVE_0_LIMIT,
+        limit_a: ACTIVE_A_LIMIT,
+        limit_ab: ACTIVE_AB_LIMIT,
+    };
+}
+
+impl Default for ActiveLimits {
+    fn default() -> Self {
+        Self::FIXED
+    }
+}
+
+/// Moving averages of the 100, 200, and 300 ms pulse lengths.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) struct PulseClusters {
+    means: [Q16; 3], // in milliseconds
+    counts: [u16; 3],
+}
+
+impl PulseClusters {
+    /// Add a classified pulse to its cluster.
+    ///
+    /// # Arguments
+    /// * `nominal` - nominal length of the pulse in microseconds, other than 100, 200, or 300 ms
+    ///   is ignored
+    /// * `active` - measured length of the pulse in microseconds
+    pub(crate) fn add(&mut self, nominal: i32, active: u32) {
+        let idx = match nominal {
+            100_000 => 0,
+            200_000 => 1,
+            300_000 => 2,
+            _ => return,
+        };
+        let sample = Q16::from_ratio(active as i64, 1_000);
+        if self.counts[idx] == 0 {
+            self.means[idx] = sample;
+        } else {
+            self.means[idx] = self.means[idx].ema(sample, 4);
+        }
+        self.counts[idx] = self.counts[idx].saturating_add(1);
+    }
+
+    /// Return the limits halfway between the clusters, or the configured limits if not every
+    /// cluster has enough samples or the clusters are not in order.
+    ///
+    /// # Arguments
+    /// * `configured` - the limits to fall back to
+    /// * `minute_limit` - upper limit of the length of the begin-of-minute marker
+    pub(crate) fn limits(&self, configured: ActiveLimits, minute_limit: u32) -> ActiveLimits {
+        if self.counts.iter().any(|&c| c < CLUSTER_MIN_SAMPLES) {
+            return configured;
+        }
+        let [m0, m10, m11] = self.means.map(|m| m.mul_int(1_000).max(0) as u32);
+        if !(m0 < m10 && m10 < m11) {
+            return configured;
+        }
+        let limit_a = (m10 + m11) / 2;
+        let limit_ab = m11 + (m11 - limit_a);
+        if limit_ab >= minute_limit {
+            return configured;
+        }
+        ActiveLimits {
+            limit_0: (m0 + m10) / 2,
+            limit_a,
+            limit_ab,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MINUTE_LIMIT;
+
+    #[test]
+    fn test_limits() {
+        let mut clusters = PulseClusters::default();
+        for _ in 0..CLUSTER_MIN_SAMPLES {
+            clusters.add(100_000, 140_000);
+            clusters.add(200_000, 240_000);
+        }
+        assert_eq!(
+            clusters.limits(ActiveLimits::FIXED, MINUTE_LIMIT),
+            ActiveLimits::FIXED
+        ); // no 300 ms pulses yet
+        for _ in 0..CLUSTER_MIN_SAMPLES {
+            clusters.add(300_000, 340_000);
+            clusters.add(500_000, 540_000); // ignored
+        }
+        assert_eq!(
+            clusters.limits(ActiveLimits::FIXED, MINUTE_LIMIT),
+            ActiveLimits {
+                limit_0: 190_000,
+                limit_a: 290_000,
+                limit_ab: 390_000
+            }
+        );
+    }
+}
@@ -0,0 +1,185 @@
+//! Alarm table evaluated at each minute boundary of the broadcast (local) time, as used by
+//! radio-controlled alarm clocks.
+
+use radio_datetime_utils::RadioDateTimeUtils;
+
+/// Number of minutes in a day.
+const MINUTES_PER_DAY: u16 = 24 * 60;
+/// Maximum forward step in minutes for which passed alarms are caught up, i.e. one minute plus
+/// the hour skipped when summer time starts.
+const MAX_CATCH_UP: u16 = 61;
+
+/// An alarm which fired.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AlarmEvent {
+    /// Identifier passed to `AlarmTable::schedule_at()`.
+    pub callback_id: u8,
+    /// Hour the alarm was scheduled at.
+    pub hour: u8,
+    /// Minute the alarm was scheduled at.
+    pub minute: u8,
+}
+
+/// Table of up to `N` daily alarms.
+///
+/// Alarms in the hour skipped when summer time starts fire when that hour is skipped, alarms in
+/// the hour repeated when summer time ends fire only once.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AlarmTable<const N: usize> {
+    alarms: [Option<AlarmEvent>; N],
+    last: Option<u16>, // latest evaluated minute of the day
+}
+
+impl<const N: usize> Default for AlarmTable<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> AlarmTable<N> {
+    pub fn new() -> Self {
+        Self {
+            alarms: [None; N],
+            last: None,
+        }
+    }
+
+    /// Schedule a daily alarm, returns false if the time is invalid or the table is full.
+    ///
+    /// # Arguments
+    /// * `hour` - hour of the alarm, 0-23
+    /// * `minute` - minute of the alarm, 0-59
+    /// * `callback_id` - identifier reported when the alarm fires
+    pub fn schedule_at(&mut self, hour: u8, minute: u8, callback_id: u8) -> bool {
+        if hour > 23 || minute > 59 {
+            return false;
+        }
+        match self.alarms.iter_mut().find(|a| a.is_none()) {
+            Some(slot) => {
+                *slot = Some(AlarmEvent {
+                    callback_id,
+                    hour,
+                    minute,
+                });
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Remove all alarms with the given identifier.
+    ///
+    /// # Arguments
+    /// * `callback_id` - identifier of the alarms to remove
+    pub fn cancel(&mut self, callback_id: u8) {
+        for alarm in self.alarms.iter_mut() {
+            if alarm.is_some_and(|a| a.callback_id == callback_id) {
+                *alarm = None;
+            }
+        }
+    }
+
+    /// Evaluate the alarms at a minute boundary and return the ones which fired.
+    ///
+    /// This should be called after each decoded or propagated minute, i.e. after
+    /// `decode_minute()` or `add_minute()`.
+    ///
+    /// # Arguments
+    /// * `radio_datetime` - the current date/time
+    pub fn evaluate(
+        &mut self,
+        radio_datetime: &RadioDateTimeUtils,
+    ) -> impl Iterator<Item = AlarmEvent> {
+        let mut fired = [None; N];
+        if let (Some(hour), Some(minute)) = (radio_datetime.get_hour(), radio_datetime.get_minute())
+        {
+            let now = hour as u16 * 60 + minute as u16;
+            // window of minutes after `first` up to and including `now`
+            let (first, length) = match self.last {
+                Some(last) => {
+                    let forward = (now + MINUTES_PER_DAY - last) % MINUTES_PER_DAY;
+                    let backward = (last + MINUTES_PER_DAY - now) % MINUTES_PER_DAY;
+                    if forward <= MAX_CATCH_UP {
+                        (last, forward)
+                    } else if backward <= 60 {
+                        (now, 0) // repeated hour, already handled
+                    } else {
+                        (now + MINUTES_PER_DAY - 1, 1) // resynchronized, only this minute
+                    }
+                }
+                None => (now + MINUTES_PER_DAY - 1, 1),
+            };
+            if length > 0 {
+                self.last = Some(now);
+            }
+            for (idx, alarm) in self.alarms.iter().enumerate() {
+                if let Some(alarm) = alarm {
+                    let at = alarm.hour as u16 * 60 + alarm.minute as u16;
+                    let offset = (at + MINUTES_PER_DAY - first % MINUTES_PER_DAY) % MINUTES_PER_DAY;
+                    if offset >= 1 && offset <= length {
+                        fired[idx] = Some(*alarm);
+                    }
+                }
+            }
+        }
+        fired.into_iter().flatten()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn datetime(hour: u8, minute: u8) -> RadioDateTimeUtils {
+        let mut rdt = RadioDateTimeUtils::new(0);
+        rdt.set_hour(Some(hour), true, false);
+        rdt.set_minute(Some(minute), true, false);
+        rdt
+    }
+
+    #[test]
+    fn test_schedule_at() {
+        let mut alarms = AlarmTable::<2>::new();
+        assert_eq!(alarms.schedule_at(24, 0, 1), false);
+        assert_eq!(alarms.schedule_at(7, 30, 1), true);
+        assert_eq!(alarms.schedule_at(7, 45, 2), true);
+        assert_eq!(alarms.schedule_at(8, 0, 3), false); // full
+        alarms.cancel(2);
+        assert_eq!(alarms.schedule_at(8, 0, 3), true);
+        assert_eq!(alarms.evaluate(&RadioDateTimeUtils::new(0)).count(), 0);
+        assert_eq!(
+            alarms.evaluate(&datetime(7, 30)).collect::<Vec<_>>(),
+            [AlarmEvent {
+                callback_id: 1,
+                hour: 7,
+                minute: 30
+            }]
+        );
+        assert_eq!(alarms.evaluate(&datetime(7, 30)).count(), 0); // same minute again
+        assert_eq!(alarms.evaluate(&datetime(7, 31)).count(), 0);
+    }
+    #[test]
+    fn test_evaluate_summer_time_starts() {
+        let mut alarms = AlarmTable::<1>::new();
+        alarms.schedule_at(1, 30, 1);
+        assert_eq!(alarms.evaluate(&datetime(0, 59)).count(), 0);
+        assert_eq!(alarms.evaluate(&datetime(2, 0)).count(), 1); // 01:xx skipped
+    }
+    #[test]
+    fn test_evaluate_summer_time_ends() {
+        let mut alarms = AlarmTable::<1>::new();
+        alarms.schedule_at(1, 30, 1);
+        assert_eq!(alarms.evaluate(&datetime(1, 30)).count(), 1);
+        assert_eq!(alarms.evaluate(&datetime(1, 59)).count(), 0);
+        assert_eq!(alarms.evaluate(&datetime(1, 0)).count(), 0); // 01:xx repeated
+        assert_eq!(alarms.evaluate(&datetime(1, 30)).count(), 0);
+        assert_eq!(alarms.evaluate(&datetime(2, 0)).count(), 0);
+    }
+    #[test]
+    fn test_evaluate_midnight() {
+        let mut alarms = AlarmTable::<1>::new();
+        alarms.schedule_at(0, 0, 1);
+        assert_eq!(alarms.evaluate(&datetime(23, 59)).count(), 0);
+        assert_eq!(alarms.evaluate(&datetime(0, 0)).count(), 1);
+    }
+}
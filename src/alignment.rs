@@ -0,0 +1,138 @@
+//! Decoding of captured minutes with an unknown start, for post-processing of captures.
+
+use crate::frame_layout::{FIELDS_A, FIELDS_B};
+use crate::MSFUtils;
+
+/// Length of the captured sequences which can be aligned, leap seconds are not supported.
+pub const CAPTURE_LENGTH: usize = 60;
+
+/// Count the bits matching the fixed values of the frame layout, or None if the begin-of-minute
+/// or end-of-minute marker is not present.
+fn fixed_bit_score(
+    bits_a: &[Option<bool>],
+    bits_b: &[Option<bool>],
+    rotation: usize,
+) -> Option<u8> {
+    let mut score = 0;
+    for second in 0..CAPTURE_LENGTH {
+        let idx = (second + rotation) % CAPTURE_LENGTH;
+        let bit_a = bits_a[idx];
+        let bit_b = bits_b[idx];
+        let field_a = FIELDS_A.iter().find(|f| f.contains(second as u8))?;
+        let field_b = FIELDS_B.iter().find(|f| f.contains(second as u8))?;
+        let fixed_a = field_a.fixed_bit(second as u8);
+        let fixed_b = field_b.fixed_bit(second as u8);
+        let is_marker = second == 0 || second >= 52;
+        if fixed_a.is_some() {
+            if bit_a == fixed_a {
+                score += 1;
+            } else if is_marker {
+                return None;
+            }
+        }
+        if fixed_b.is_some() && bit_b == fixed_b {
+            score += 1;
+        }
+    }
+    Some(score)
+}
+
+/// Find the start of the minute in a captured sequence of 60 A and B bits.
+///
+/// Returns the index of the begin-of-minute marker, or None if no or more than one alignment
+/// is consistent with the markers and the fixed bits.
+///
+/// # Arguments
+/// * `bits_a` - the captured A bits
+/// * `bits_b` - the captured B bits
+pub fn find_alignment(bits_a: &[Option<bool>], bits_b: &[Option<bool>]) -> Option<usize> {
+    if bits_a.len() != CAPTURE_LENGTH || bits_b.len() != CAPTURE_LENGTH {
+        return None;
+    }
+    let mut best = None;
+    let mut best_score = 0;
+    let mut tie = false;
+    for rotation in 0..CAPTURE_LENGTH {
+        if let Some(score) = fixed_bit_score(bits_a, bits_b, rotation) {
+            if best.is_none() || score > best_score {
+                best = Some(rotation);
+                best_score = score;
+                tie = false;
+            } else if score == best_score {
+                tie = true;
+            }
+        }
+    }
+    if tie {
+        None
+    } else {
+        best
+    }
+}
+
+/// Align a captured sequence of 60 A and B bits to the minute and decode it.
+///
+/// Returns the index of the begin-of-minute marker in the capture together with a decoder
+/// holding the decoded date/time, parities, and DUT1. The minute is decoded using relaxed checks.
+///
+/// # Arguments
+/// * `bits_a` - the captured A bits
+/// * `bits_b` - the captured B bits
+pub fn align_and_decode(
+    bits_a: &[Option<bool>],
+    bits_b: &[Option<bool>],
+) -> Option<(usize, MSFUtils)> {
+    let rotation = find_alignment(bits_a, bits_b)?;
+    let mut msf = MSFUtils::default();
+    for second in 0..CAPTURE_LENGTH {
+        let idx = (second + rotation) % CAPTURE_LENGTH;
+        msf.bit_buffer_a[second] = bits_a[idx];
+        msf.bit_buffer_b[second] = bits_b[idx];
+    }
+    msf.second = CAPTURE_LENGTH as u8 - 1;
+    let _ = msf.decode_minute(false);
+    Some((rotation, msf))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{BIT_BUFFER_A, BIT_BUFFER_B};
+
+    fn rotated(bits: &[bool; 60], rotation: usize) -> [Option<bool>; 60] {
+        let mut res = [None; 60];
+        for (idx, bit) in bits.iter().enumerate() {
+            res[(idx + rotation) % 60] = Some(*bit);
+        }
+        res
+    }
+
+    #[test]
+    fn test_align_and_decode_aligned() {
+        let (rotation, msf) =
+            align_and_decode(&rotated(&BIT_BUFFER_A, 0), &rotated(&BIT_BUFFER_B, 0)).unwrap();
+        assert_eq!(rotation, 0);
+        assert_eq!(msf.get_radio_datetime().get_minute(), Some(58));
+        assert_eq!(msf.get_radio_datetime().get_hour(), Some(14));
+        #[cfg(not(feature = "no-dut1"))]
+        assert_eq!(msf.get_dut1(), Some(-2));
+    }
+    #[test]
+    fn test_align_and_decode_rotated() {
+        let (rotation, msf) =
+            align_and_decode(&rotated(&BIT_BUFFER_A, 23), &rotated(&BIT_BUFFER_B, 23)).unwrap();
+        assert_eq!(rotation, 23);
+        assert_eq!(msf.get_radio_datetime().get_year(), Some(22));
+        assert_eq!(msf.snapshot().parities.hour_minute, Some(true));
+    }
+    #[test]
+    fn test_align_and_decode_no_marker() {
+        let mut bits_a = rotated(&BIT_BUFFER_A, 5);
+        bits_a[(55 + 5) % 60] = None; // in the end-of-minute marker
+        assert_eq!(find_alignment(&bits_a, &rotated(&BIT_BUFFER_B, 5)), None);
+    }
+    #[test]
+    fn test_align_and_decode_wrong_length() {
+        assert_eq!(find_alignment(&[None; 59], &[None; 59]), None);
+    }
+}
@@ -0,0 +1,615 @@
+//! Second version of the API, returning snapshots and results instead of requiring the caller
+//! to query the individual getters.
+//!
+//! The methods of the first version which are superseded by this module are kept as deprecated
+//! wrappers: `decode_time()` by `decode_minute()`, and `get_first_minute()` and
+//! `get_parity_1()` to `get_parity_4()` by `snapshot()`.
+
+use crate::core_types::DecodedMinute;
+use crate::strictness::Strictness;
+use crate::MSFUtils;
+use core::fmt;
+use radio_datetime_utils::{RadioDateTimeUtils, DST_SUMMER};
+
+/// Results of the four parity checks, Some(true) means OK.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Parities {
+    /// Year parity.
+    pub year: Option<bool>,
+    /// Month/day parity.
+    pub month_day: Option<bool>,
+    /// Weekday parity.
+    pub weekday: Option<bool>,
+    /// Hour/minute parity.
+    pub hour_minute: Option<bool>,
+}
+
+impl Parities {
+    /// Return if all parities are OK.
+    pub fn all_ok(&self) -> bool {
+        self.year == Some(true)
+            && self.month_day == Some(true)
+            && self.weekday == Some(true)
+            && self.hour_minute == Some(true)
+    }
+}
+
+/// Fields of the last decoded minute which failed their parity and were taken from the
+/// prediction instead, see `MSFUtils::set_predictive_fallback()`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PredictedFields {
+    /// The year was predicted.
+    pub year: bool,
+    /// The month and day were predicted.
+    pub month_day: bool,
+    /// The weekday was predicted.
+    pub weekday: bool,
+    /// The hour and minute were predicted.
+    pub hour_minute: bool,
+}
+
+impl PredictedFields {
+    /// Return if any field was predicted.
+    pub fn any(&self) -> bool {
+        self.year || self.month_day || self.weekday || self.hour_minute
+    }
+}
+
+/// State of the decoder at the moment it was taken.
+#[derive(Clone, Copy)]
+pub struct Snapshot {
+    /// This is the first minute that is decoded.
+    pub first_minute: bool,
+    /// A new minute (0111_1110 marker) has arrived.
+    pub new_minute: bool,
+    /// The 500 ms long begin-of-minute marker has arrived.
+    pub past_new_minute: bool,
+    /// A new second has arrived.
+    pub new_second: bool,
+    /// The second counter.
+    pub second: u8,
+    /// Value of the current A bit.
+    pub bit_a: Option<bool>,
+    /// Value of the current B bit.
+    pub bit_b: Option<bool>,
+    /// Length of the current minute in seconds.
+    pub minute_length: u8,
+    /// Parity results of the last decoded minute.
+    pub parities: Parities,
+    /// DUT1 (UT1 - UTC) in deci-seconds of the last decoded minute.
+    pub dut1: Option<i8>,
+    /// CRC-16 over the raw A and B bits of the last decoded minute.
+    pub minute_crc: Option<u16>,
+    /// The minute was decoded from the A bits alone, without parity checks.
+    pub a_only: bool,
+    /// The date/time.
+    pub radio_datetime: RadioDateTimeUtils,
+}
+
+impl Snapshot {
+    /// Return the date/time and DUT1 as the type shared with the sibling radio crates.
+    pub fn decoded_minute(&self) -> DecodedMinute {
+        DecodedMinute::new(&self.radio_datetime, self.dut1)
+    }
+}
+
+/// Write a field as two digits, or `??` if it is unknown.
+fn write_field(f: &mut fmt::Formatter<'_>, value: Option<u8>) -> fmt::Result {
+    match value {
+        Some(v) => write!(f, "{v:02}"),
+        None => f.write_str("??"),
+    }
+}
+
+/// Formats the date/time, DUT1, and parities like
+/// `22-10-23 14:58 BST DUT1=-0.2s P:OK,OK,OK,OK`, unknown values are shown as `?`.
+///
+/// The year is shown within the century as broadcast, see `reference_check::full_year()` for
+/// resolving the century.
+impl fmt::Display for Snapshot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rdt = &self.radio_datetime;
+        write_field(f, rdt.get_year())?;
+        f.write_str("-")?;
+        write_field(f, rdt.get_month())?;
+        f.write_str("-")?;
+        write_field(f, rdt.get_day())?;
+        f.write_str(" ")?;
+        write_field(f, rdt.get_hour())?;
+        f.write_str(":")?;
+        write_field(f, rdt.get_minute())?;
+        f.write_str(match rdt.get_dst() {
+            Some(dst) if dst & DST_SUMMER != 0 => " BST",
+            Some(_) => " GMT",
+            None => " ???",
+        })?;
+        match self.dut1 {
+            Some(dut1) => {
+                let sign = if dut1 < 0 { '-' } else { '+' };
+                write!(f, " DUT1={sign}0.{}s", dut1.unsigned_abs())?
+            }
+            None => f.write_str(" DUT1=?")?,
+        }
+        let parities = [
+            self.parities.year,
+            self.parities.month_day,
+            self.parities.weekday,
+            self.parities.hour_minute,
+        ];
+        for (idx, parity) in parities.iter().enumerate() {
+            f.write_str(if idx == 0 { " P:" } else { "," })?;
+            f.write_str(match parity {
+                Some(true) => "OK",
+                Some(false) => "BAD",
+                None => "?",
+            })?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for Snapshot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Snapshot")
+            .field("first_minute", &self.first_minute)
+            .field("new_minute", &self.new_minute)
+            .field("past_new_minute", &self.past_new_minute)
+            .field("new_second", &self.new_second)
+            .field("second", &self.second)
+            .field("bit_a", &self.bit_a)
+            .field("bit_b", &self.bit_b)
+            .field("minute_length", &self.minute_length)
+            .field("parities", &self.parities)
+            .field("dut1", &self.dut1)
+            .field("minute_crc", &self.minute_crc)
+            .field("a_only", &self.a_only)
+            .field("radio_datetime", &self.decoded_minute())
+            .finish()
+    }
+}
+
+/// What a consumer attaching late needs to show the time right away, see
+/// `MSFUtils::current_view()`.
+#[derive(Clone, Copy)]
+pub struct CurrentView {
+    /// The state right after the last minute which passed the checks, None if there was none.
+    pub last_decoded: Option<Snapshot>,
+    /// Number of minutes since that minute, None if there was none.
+    pub minutes_since_decode: Option<u16>,
+    /// The current state, with the date/time propagated since that minute.
+    pub current: Snapshot,
+}
+
+/// Flags which were set since they were last taken, see `MSFUtils::take_flags()`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MinuteSecondFlags {
+    /// A new second has arrived.
+    pub new_second: bool,
+    /// A new minute (0111_1110 marker) has arrived.
+    pub new_minute: bool,
+    /// The 500 ms long begin-of-minute marker has arrived.
+    pub past_new_minute: bool,
+}
+
+/// Reasons why `decode_minute()` did not yield a valid date/time.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DecodeError {
+    /// The second counter does not match the length of the minute.
+    Incomplete,
+    /// The minute was decoded, but did not pass the checks.
+    Invalid,
+}
+
+/// Progress of a single-shot sync, see `MSFUtils::get_sync_state()`.
+#[cfg(feature = "single-shot")]
+#[derive(Clone, Copy)]
+pub enum SyncState {
+    /// Single-shot sync is disabled, or not enough consistent minutes were decoded yet.
+    Searching,
+    /// Enough consecutive consistent minutes were decoded, with the snapshot taken after the
+    /// last of them.
+    SyncAchieved(Snapshot),
+    /// The decoder ignores all edges, see `MSFUtils::disarm()`.
+    Disarmed,
+}
+
+impl MSFUtils {
+    /// Take a snapshot of the current state of the decoder.
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            first_minute: self.first_minute,
+            new_minute: self.new_minute,
+            past_new_minute: self.past_new_minute,
+            new_second: self.new_second,
+            second: self.second,
+            bit_a: self.get_current_bit_a(),
+            bit_b: self.get_current_bit_b(),
+            minute_length: self.get_minute_length(),
+            parities: Parities {
+                year: self.parity_1,
+                month_day: self.parity_2,
+                weekday: self.parity_3,
+                hour_minute: self.parity_4,
+            },
+            dut1: self.get_dut1(),
+            minute_crc: self.minute_crc,
+            a_only: self.a_only,
+            radio_datetime: self.radio_datetime,
+        }
+    }
+
+    /// Return the last decoded minute together with the current state in one call, so a consumer
+    /// which attaches late (e.g. a display task started after boot) does not have to wait up to
+    /// a minute for the next decoded minute.
+    pub fn current_view(&self) -> CurrentView {
+        CurrentView {
+            last_decoded: self.last_decoded,
+            minutes_since_decode: self.minutes_since_decode,
+            current: self.snapshot(),
+        }
+    }
+
+    /// Return the flags which were set since the last call and clear them, in one call.
+    ///
+    /// Unlike `get_new_second()` and friends, which only reflect the last edge, the flags are
+    /// latched until taken. A main loop which takes them no longer misses events, or sees an
+    /// inconsistent combination, when edges are handled (e.g. in an interrupt handler) between
+    /// two separate getter calls. The flags used by `increase_second()` are not affected.
+    pub fn take_flags(&mut self) -> MinuteSecondFlags {
+        core::mem::take(&mut self.latched_flags)
+    }
+
+    /// Get the number of consecutive consistent minutes needed for a single-shot sync, 0 if
+    /// single-shot sync is disabled.
+    #[cfg(feature = "single-shot")]
+    pub fn get_sync_minutes(&self) -> u8 {
+        self.decoder_config.sync_minutes
+    }
+
+    /// Set the number of consecutive consistent minutes (decoded without any field jumping)
+    /// needed for a single-shot sync, 0 to disable it.
+    ///
+    /// This is meant for devices which only sync once in a while: after `get_sync_state()`
+    /// reports `SyncState::SyncAchieved`, the application calls `disarm()` and can switch off
+    /// the receiver. Setting the value restarts the search.
+    ///
+    /// # Arguments
+    /// * `minutes` - number of minutes, e.g. 2
+    #[cfg(feature = "single-shot")]
+    pub fn set_sync_minutes(&mut self, minutes: u8) {
+        self.decoder_config.sync_minutes = minutes;
+        self.sync_snapshot = None;
+    }
+
+    /// Get the progress of the single-shot sync.
+    #[cfg(feature = "single-shot")]
+    pub fn get_sync_state(&self) -> SyncState {
+        match (self.disarmed, self.sync_snapshot) {
+            (true, _) => SyncState::Disarmed,
+            (false, Some(snapshot)) => SyncState::SyncAchieved(snapshot),
+            (false, None) => SyncState::Searching,
+        }
+    }
+
+    /// Stop processing edges, `handle_new_edge()` then returns right away.
+    ///
+    /// The date/time is still advanced by `decode_minute()` if the application keeps calling
+    /// it once per minute.
+    #[cfg(feature = "single-shot")]
+    pub fn disarm(&mut self) {
+        self.disarmed = true;
+    }
+
+    /// Resume processing edges after `disarm()` and restart the single-shot sync.
+    ///
+    /// Edge tracking restarts as if the next edge was the very first one, as the receiver was
+    /// probably switched off in the meantime.
+    #[cfg(feature = "single-shot")]
+    pub fn rearm(&mut self) {
+        self.disarmed = false;
+        self.restart_edges();
+        self.before_first_edge = true;
+        self.last_edge = None;
+        self.sync_streak = 0;
+        self.sync_snapshot = None;
+    }
+
+    /// Decode the time broadcast during the last minute and clear `first_minute` when appropriate.
+    ///
+    /// Returns the snapshot after decoding if the minute passed the checks, otherwise the reason
+    /// why not. The date/time is also advanced by one minute on failure, once the first minute is
+    /// decoded.
+    ///
+    /// This method must be called _before_ `increase_second()`
+    ///
+    /// # Arguments
+    /// * `strict_checks` - checks all parities, DUT1 validity, and EOM marker presence when setting
+    ///   date/time and clearing self.first_minute
+    pub fn decode_minute(&mut self, strict_checks: bool) -> Result<Snapshot, DecodeError> {
+        let complete = self.second + 1 == self.get_minute_length();
+        self.decode(strict_checks);
+        if !complete {
+            Err(DecodeError::Incomplete)
+        } else if self.minutes_since_decode == Some(0) {
+            Ok(self.snapshot())
+        } else {
+            Err(DecodeError::Invalid)
+        }
+    }
+
+    /// Like `decode_minute()`, but with the checks chosen automatically.
+    ///
+    /// Decoding starts with relaxed checks to acquire the date/time quickly, switches to strict
+    /// checks after a number of consecutive consistent minutes (decoded without any field
+    /// jumping), and back to relaxed checks after a number of consecutive failed minutes. See
+    /// `set_escalation()` and `get_strictness()`.
+    ///
+    /// This method must be called _before_ `increase_second()`
+    pub fn decode_minute_auto(&mut self) -> Result<Snapshot, DecodeError> {
+        let res = self.decode_minute(self.strictness == Strictness::Strict);
+        let rdt = &self.radio_datetime;
+        let consistent = res.is_ok()
+            && !(rdt.get_jump_year()
+                || rdt.get_jump_month()
+                || rdt.get_jump_day()
+                || rdt.get_jump_weekday()
+                || rdt.get_jump_hour()
+                || rdt.get_jump_minute());
+        self.strictness = self.decoder_config.escalation.update(
+            self.strictness,
+            &mut self.strictness_streak,
+            consistent,
+        );
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::msf_helpers;
+    use crate::strictness::Escalation;
+    use crate::test_utils::{filled_msf, minute_edges};
+
+    #[test]
+    fn test_decode_minute_ok() {
+        let mut msf = filled_msf();
+        let snapshot = msf.decode_minute(true).ok().unwrap();
+        assert_eq!(snapshot.first_minute, false);
+        assert_eq!(snapshot.decoded_minute().minute, Some(58));
+        assert_eq!(snapshot.second, 59);
+        assert_eq!(snapshot.minute_length, 60);
+        assert_eq!(snapshot.parities.all_ok(), true);
+        #[cfg(not(feature = "no-dut1"))]
+        assert_eq!(snapshot.dut1, Some(-2));
+        assert_eq!(
+            snapshot.minute_crc,
+            Some(msf_helpers::get_minute_crc(
+                &msf.bit_buffer_a[..60],
+                &msf.bit_buffer_b[..60]
+            ))
+        );
+        assert_eq!(snapshot.radio_datetime.get_minute(), Some(58));
+    }
+    #[test]
+    fn test_decode_minute_incomplete() {
+        let mut msf = filled_msf();
+        msf.second = 42;
+        assert_eq!(
+            msf.decode_minute(false).err(),
+            Some(DecodeError::Incomplete)
+        );
+    }
+    #[test]
+    fn test_decode_minute_invalid() {
+        let mut msf = filled_msf();
+        msf.bit_buffer_a[48] = Some(!msf.bit_buffer_a[48].unwrap());
+        assert_eq!(msf.decode_minute(true).err(), Some(DecodeError::Invalid));
+        assert_eq!(msf.snapshot().parities.hour_minute, Some(false));
+        assert_eq!(msf.snapshot().first_minute, true);
+    }
+    #[test]
+    fn test_decode_minute_a_only() {
+        let mut msf = filled_msf();
+        msf.set_a_only(true);
+        for second in 1..58 {
+            if second != 53 {
+                msf.bit_buffer_b[second] = None; // only the DST bits were received
+            }
+        }
+        let snapshot = msf.decode_minute(true).ok().unwrap();
+        assert_eq!(snapshot.a_only, true);
+        assert_eq!(snapshot.parities, Parities::default());
+        assert_eq!(snapshot.dut1, None);
+        assert_eq!(snapshot.radio_datetime.get_year(), Some(22));
+        assert_eq!(snapshot.radio_datetime.get_minute(), Some(58));
+    }
+    #[test]
+    #[cfg(feature = "voting")]
+    fn test_decode_minute_voting() {
+        let mut msf = filled_msf();
+        msf.set_voting_depth(3);
+        for _ in 0..2 {
+            assert_eq!(msf.decode_minute(true).is_ok(), true);
+            // receive the same minute again, as if one minute has passed
+            msf.radio_datetime.set_minute(Some(57), true, false);
+        }
+        msf.bit_buffer_a[20] = Some(true); // wrong year bit
+        let snapshot = msf.decode_minute(true).ok().unwrap();
+        assert_eq!(msf.get_voted_bits(), 1);
+        assert_eq!(snapshot.parities.year, Some(true));
+        assert_eq!(snapshot.radio_datetime.get_year(), Some(22));
+        // the received bits are kept
+        assert_eq!(msf.get_bit_buffer_a()[20], Some(true));
+        assert_eq!(
+            msf.get_minute_crc(),
+            Some(msf_helpers::get_minute_crc(
+                &msf.get_bit_buffer_a()[..60],
+                &msf.get_bit_buffer_b()[..60]
+            ))
+        );
+    }
+    #[test]
+    #[cfg(feature = "statistics")]
+    fn test_decode_minute_confidence() {
+        let mut msf = filled_msf();
+        let _ = msf.decode_minute(true);
+        let scores = msf.get_confidence_scores();
+        assert_eq!(scores.minute, 191); // no prediction yet
+        assert_eq!(scores.dst, 191);
+        #[cfg(not(feature = "no-dut1"))]
+        assert_eq!(scores.dut1, 191);
+        msf.radio_datetime.set_minute(Some(57), true, false);
+        msf.bit_buffer_a[20] = Some(true); // wrong year bit
+        let _ = msf.decode_minute(false);
+        let scores = msf.get_confidence_scores();
+        assert_eq!(scores.year, 0);
+        assert_eq!(scores.minute, 255);
+        assert_eq!(scores.dst, 255);
+        msf.second = 42;
+        let _ = msf.decode_minute(false);
+        assert_eq!(msf.get_confidence_scores().minute, 0); // incomplete
+    }
+    #[test]
+    fn test_decode_minute_auto() {
+        let mut msf = filled_msf();
+        msf.set_escalation(Escalation {
+            escalate_after: 2,
+            deescalate_after: 1,
+        });
+        assert_eq!(msf.get_escalation().escalate_after, 2);
+        for level in [Strictness::Relaxed, Strictness::Strict] {
+            assert_eq!(msf.decode_minute_auto().is_ok(), true);
+            assert_eq!(msf.get_strictness(), level);
+            // receive the same minute again, as if one minute has passed
+            msf.radio_datetime.set_minute(Some(57), true, false);
+        }
+        msf.bit_buffer_a[48] = Some(!msf.bit_buffer_a[48].unwrap());
+        assert_eq!(msf.decode_minute_auto().err(), Some(DecodeError::Invalid));
+        assert_eq!(msf.get_strictness(), Strictness::Relaxed);
+    }
+    #[test]
+    #[cfg(feature = "single-shot")]
+    fn test_sync_state() {
+        let mut msf = filled_msf();
+        assert_eq!(matches!(msf.get_sync_state(), SyncState::Searching), true);
+        msf.set_sync_minutes(2);
+        assert_eq!(msf.get_sync_minutes(), 2);
+        assert_eq!(msf.decode_minute(true).is_ok(), true);
+        assert_eq!(matches!(msf.get_sync_state(), SyncState::Searching), true);
+        // receive the same minute again, as if one minute has passed
+        msf.radio_datetime.set_minute(Some(57), true, false);
+        assert_eq!(msf.decode_minute(true).is_ok(), true);
+        match msf.get_sync_state() {
+            SyncState::SyncAchieved(snapshot) => {
+                assert_eq!(snapshot.radio_datetime.get_minute(), Some(58))
+            }
+            _ => panic!("no sync"),
+        }
+        msf.disarm();
+        assert_eq!(matches!(msf.get_sync_state(), SyncState::Disarmed), true);
+        let fingerprint = msf.state_fingerprint();
+        for (is_low_edge, t) in minute_edges(1_000_000) {
+            msf.handle_new_edge(is_low_edge, t);
+        }
+        assert_eq!(msf.state_fingerprint(), fingerprint); // edges are ignored
+        msf.rearm();
+        assert_eq!(matches!(msf.get_sync_state(), SyncState::Searching), true);
+        msf.handle_new_edge(false, 1_000_000);
+        msf.handle_new_edge(true, 1_100_000);
+        msf.handle_new_edge(false, 2_000_000);
+        assert_eq!(msf.get_new_second(), true);
+    }
+    #[test]
+    #[cfg(feature = "single-shot")]
+    fn test_rearm() {
+        let mut msf = MSFUtils::default();
+        for (is_low_edge, t) in minute_edges(1_000_000)[..20].iter().copied() {
+            msf.handle_new_edge(is_low_edge, t);
+            if msf.get_new_second() {
+                msf.increase_second();
+            }
+        }
+        assert_eq!(msf.second_start.is_some(), true);
+        assert_eq!(msf.last_edge.is_some(), true);
+        msf.disarm();
+        msf.rearm();
+        assert_eq!(msf.before_first_edge, true);
+        assert_eq!(msf.last_edge, None);
+        assert_eq!(msf.old_t_diff, 0);
+        assert_eq!(msf.second_edge, None);
+        assert_eq!(msf.second_start, None);
+        #[cfg(feature = "flywheel")]
+        assert_eq!(msf.flywheel_lock, 0);
+        assert_eq!(msf.get_new_second(), false);
+    }
+    #[test]
+    fn test_snapshot_display() {
+        let mut msf = filled_msf();
+        assert_eq!(
+            std::format!("{}", msf.snapshot()),
+            "??-??-?? ??:?? ??? DUT1=? P:?,?,?,?"
+        );
+        msf.bit_buffer_a[48] = Some(!msf.bit_buffer_a[48].unwrap());
+        msf.decode_minute(false).ok();
+        #[cfg(not(feature = "no-dut1"))]
+        assert_eq!(
+            std::format!("{}", msf.snapshot()),
+            "22-10-23 ??:?? BST DUT1=-0.2s P:OK,OK,OK,BAD"
+        );
+        assert_eq!(
+            std::format!("{:?}", msf.snapshot()).starts_with("Snapshot { first_minute: "),
+            true
+        );
+    }
+    #[test]
+    fn test_current_view() {
+        let mut msf = filled_msf();
+        assert_eq!(msf.current_view().last_decoded.is_none(), true);
+        assert_eq!(msf.decode_minute(true).is_ok(), true);
+        msf.bit_buffer_a[48] = Some(!msf.bit_buffer_a[48].unwrap());
+        assert_eq!(msf.decode_minute(true).err(), Some(DecodeError::Invalid));
+        let view = msf.current_view();
+        assert_eq!(view.minutes_since_decode, Some(1));
+        let last_decoded = view.last_decoded.unwrap();
+        assert_eq!(last_decoded.parities.all_ok(), true);
+        assert_eq!(last_decoded.radio_datetime.get_minute(), Some(58));
+        assert_eq!(view.current.radio_datetime.get_minute(), Some(59)); // propagated
+    }
+    #[test]
+    fn test_take_flags() {
+        let mut msf = MSFUtils::default();
+        for (is_low_edge, t) in minute_edges(1_000_000) {
+            msf.handle_new_edge(is_low_edge, t);
+            if msf.get_new_second() {
+                msf.increase_second();
+            }
+        }
+        msf.handle_new_edge(true, 61_500_000); // begin-of-minute marker
+        assert_eq!(msf.get_new_second(), false);
+        assert_eq!(msf.get_new_minute(), false);
+        assert_eq!(
+            msf.take_flags(),
+            MinuteSecondFlags {
+                new_second: true,
+                new_minute: true,
+                past_new_minute: true
+            }
+        );
+        assert_eq!(msf.take_flags(), MinuteSecondFlags::default());
+    }
+    #[test]
+    fn test_snapshot_flags() {
+        let mut msf = MSFUtils::default();
+        msf.force_past_new_minute();
+        let snapshot = msf.snapshot();
+        assert_eq!(snapshot.past_new_minute, true);
+        assert_eq!(snapshot.new_minute, false);
+        assert_eq!(snapshot.bit_a, Some(true));
+        assert_eq!(snapshot.bit_b, Some(true));
+        assert_eq!(snapshot.parities, Parities::default());
+        assert_eq!(snapshot.minute_crc, None);
+    }
+}
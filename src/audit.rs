@@ -0,0 +1,104 @@
+//! Counters summarizing the behavior of the decoder, for audits of long-running installations.
+
+/// Behavior of the decoder during one day of the held date/time.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DailySummary {
+    /// Year of the day, 0-99.
+    pub year: u8,
+    /// Month of the day.
+    pub month: u8,
+    /// Day of the month.
+    pub day: u8,
+    /// Number of minutes seen by `decode_minute()`.
+    pub minutes: u16,
+    /// Number of minutes which passed the checks.
+    pub decoded_minutes: u16,
+    /// Number of times the second counter was realigned by a begin-of-minute marker.
+    pub resyncs: u16,
+    /// Number of times a field of the held date/time jumped.
+    pub steps: u16,
+}
+
+/// Totals and the summary of the running day.
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct Audit {
+    pub(crate) uptime_minutes: u32,
+    pub(crate) total_resyncs: u32,
+    pub(crate) total_steps: u32,
+    pub(crate) today: DailySummary,
+    pub(crate) summary: Option<DailySummary>,
+}
+
+impl Audit {
+    pub(crate) fn add_resync(&mut self) {
+        self.total_resyncs = self.total_resyncs.saturating_add(1);
+        self.today.resyncs = self.today.resyncs.saturating_add(1);
+    }
+
+    /// Account for a minute and roll over the summary if the day changed.
+    ///
+    /// # Arguments
+    /// * `decoded` - the minute passed the checks
+    /// * `step` - a field of the held date/time jumped
+    /// * `old_date` - the (year, month, day) held before the minute, if known
+    /// * `new_day` - the day held after the minute
+    pub(crate) fn add_minute(
+        &mut self,
+        decoded: bool,
+        step: bool,
+        old_date: Option<(u8, u8, u8)>,
+        new_day: Option<u8>,
+    ) {
+        self.uptime_minutes = self.uptime_minutes.saturating_add(1);
+        if step {
+            self.total_steps = self.total_steps.saturating_add(1);
+            self.today.steps = self.today.steps.saturating_add(1);
+        }
+        self.today.minutes = self.today.minutes.saturating_add(1);
+        if decoded {
+            self.today.decoded_minutes = self.today.decoded_minutes.saturating_add(1);
+        }
+        if let Some((year, month, day)) = old_date {
+            if new_day.is_some() && new_day != Some(day) {
+                self.summary = Some(DailySummary {
+                    year,
+                    month,
+                    day,
+                    ..self.today
+                });
+                self.today = DailySummary::default();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_minute() {
+        let mut audit = Audit::default();
+        audit.add_minute(true, false, None, Some(23));
+        audit.add_resync();
+        audit.add_minute(false, true, Some((22, 10, 23)), Some(23));
+        assert_eq!(audit.summary, None);
+        audit.add_minute(true, false, Some((22, 10, 23)), Some(24));
+        assert_eq!(
+            audit.summary,
+            Some(DailySummary {
+                year: 22,
+                month: 10,
+                day: 23,
+                minutes: 3,
+                decoded_minutes: 2,
+                resyncs: 1,
+                steps: 1,
+            })
+        );
+        assert_eq!(audit.uptime_minutes, 3);
+        assert_eq!(audit.total_resyncs, 1);
+        assert_eq!(audit.total_steps, 1);
+        assert_eq!(audit.today, DailySummary::default());
+    }
+}
@@ -0,0 +1,74 @@
+//! Bit error rate of the received date/time bits, measured against the bits predicted from the
+//! previous minute.
+//!
+//! Once a minute is known, the date/time of the next one follows from `add_minute()`, so any
+//! received date/time bit which differs from the encoded prediction is counted as a bit error.
+//! This is an objective measure of the reception quality as long as the prediction is right.
+
+/// Running count of the compared and of the mismatching bits.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BitErrorRate {
+    bits: u32,
+    errors: u32,
+}
+
+impl BitErrorRate {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the counters to 0.
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Compare a received bit with its predicted value.
+    ///
+    /// # Arguments
+    /// * `received` - the received bit
+    /// * `predicted` - the predicted bit
+    pub fn add_bit(&mut self, received: bool, predicted: bool) {
+        self.bits = self.bits.saturating_add(1);
+        if received != predicted {
+            self.errors = self.errors.saturating_add(1);
+        }
+    }
+
+    /// Get the number of compared bits.
+    pub fn get_bits(&self) -> u32 {
+        self.bits
+    }
+
+    /// Get the number of compared bits which did not match their prediction.
+    pub fn get_errors(&self) -> u32 {
+        self.errors
+    }
+
+    /// Get the bit error rate in parts per million, None if no bits were compared yet.
+    pub fn get_ppm(&self) -> Option<u32> {
+        if self.bits == 0 {
+            return None;
+        }
+        Some((self.errors as u64 * 1_000_000 / self.bits as u64) as u32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bit_error_rate() {
+        let mut ber = BitErrorRate::new();
+        assert_eq!(ber.get_ppm(), None);
+        ber.add_bit(true, true);
+        ber.add_bit(false, false);
+        ber.add_bit(false, true);
+        ber.add_bit(true, true);
+        assert_eq!(ber.get_bits(), 4);
+        assert_eq!(ber.get_errors(), 1);
+        assert_eq!(ber.get_ppm(), Some(250_000));
+        ber.reset();
+        assert_eq!(ber.get_bits(), 0);
+    }
+}
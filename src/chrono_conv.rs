@@ -0,0 +1,58 @@
+//! Conversions of decoded minutes to the date/time types of the `chrono` crate.
+//!
+//! MSF broadcasts the year within the century only, the full year is chosen using the weekday,
+//! see `reference_check::full_year()`.
+//!
+//! Enabled with the `chrono` feature.
+
+use crate::api2::Snapshot;
+use crate::reference_check::full_year;
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, Utc};
+use radio_datetime_utils::DST_SUMMER;
+
+impl Snapshot {
+    /// Return the broadcast UK civil time, None if a field of it is unknown.
+    pub fn to_chrono_naive(&self) -> Option<NaiveDateTime> {
+        let rdt = &self.radio_datetime;
+        let (month, day) = (rdt.get_month()?, rdt.get_day()?);
+        let year = full_year(rdt.get_year()?, month, day, rdt.get_weekday())?;
+        NaiveDate::from_ymd_opt(year as i32, month as u32, day as u32)?.and_hms_opt(
+            rdt.get_hour()? as u32,
+            rdt.get_minute()? as u32,
+            0,
+        )
+    }
+
+    /// Return the broadcast time as UK civil time with its offset from UTC, which is one hour
+    /// during summer time (BST), None if a field of it is unknown.
+    pub fn to_chrono_local(&self) -> Option<DateTime<FixedOffset>> {
+        let summer = self.radio_datetime.get_dst()? & DST_SUMMER != 0;
+        let offset = FixedOffset::east_opt(if summer { 3600 } else { 0 })?;
+        self.to_chrono_naive()?.and_local_timezone(offset).single()
+    }
+
+    /// Return the broadcast time in UTC, None if a field of it is unknown.
+    pub fn to_chrono_utc(&self) -> Option<DateTime<Utc>> {
+        Some(self.to_chrono_local()?.to_utc())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::filled_msf;
+    use chrono::{Datelike, Timelike};
+
+    #[test]
+    fn test_to_chrono() {
+        let mut msf = filled_msf();
+        assert_eq!(msf.snapshot().to_chrono_naive(), None);
+        let snapshot = msf.decode_minute(false).ok().unwrap();
+        let naive = snapshot.to_chrono_naive().unwrap();
+        assert_eq!((naive.year(), naive.month(), naive.day()), (2022, 10, 23));
+        assert_eq!((naive.hour(), naive.minute()), (14, 58));
+        let local = snapshot.to_chrono_local().unwrap();
+        assert_eq!(local.offset().local_minus_utc(), 3600);
+        assert_eq!(local.naive_local(), naive);
+        assert_eq!(snapshot.to_chrono_utc().unwrap().timestamp(), 1_666_533_480);
+    }
+}
@@ -0,0 +1,136 @@
+//! The broadcast UK civil time together with the corresponding UTC, for clocks which keep UTC.
+//!
+//! MSF broadcasts GMT, which equals UTC, or BST, which is one hour ahead of it. The conversion
+//! uses the summer time bit of the minute itself, so the minutes around a change are converted
+//! correctly: 00:59 GMT is followed by 02:00 BST, which both map to consecutive UTC minutes, and
+//! 01:59 BST is followed by 01:00 GMT.
+
+use crate::api2::Snapshot;
+use crate::core_types::DecodedMinute;
+use crate::MSFUtils;
+use radio_datetime_utils::{RadioDateTimeUtils, DST_SUMMER};
+
+/// Return the number of days in the given month, the year is in the range 0-99.
+pub(crate) fn days_in_month(year: u8, month: u8) -> u8 {
+    match month {
+        2 if year.is_multiple_of(4) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// A decoded minute in UK civil time and in UTC.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CivilTime {
+    /// The broadcast time, GMT or BST.
+    pub local: DecodedMinute,
+    /// The same minute in UTC, its `dst` field is 0.
+    pub utc: DecodedMinute,
+    /// Summer time (BST) is active, so `local` is one hour ahead of `utc`.
+    pub summer_time: bool,
+}
+
+impl CivilTime {
+    /// Convert a decoded date/time, None if a field of it is unknown.
+    ///
+    /// # Arguments
+    /// * `radio_datetime` - the date/time in UK civil time
+    /// * `dut1` - DUT1 in deci-seconds, copied to both times
+    pub fn new(radio_datetime: &RadioDateTimeUtils, dut1: Option<i8>) -> Option<Self> {
+        let local = DecodedMinute::new(radio_datetime, dut1);
+        let summer_time = local.dst? & DST_SUMMER != 0;
+        let (mut year, mut month, mut day) = (local.year?, local.month?, local.day?);
+        let (mut weekday, mut hour) = (local.weekday?, local.hour?);
+        local.minute?;
+        if summer_time {
+            if hour > 0 {
+                hour -= 1;
+            } else {
+                hour = 23;
+                weekday = (weekday + 6) % 7;
+                if day > 1 {
+                    day -= 1;
+                } else {
+                    if month > 1 {
+                        month -= 1;
+                    } else {
+                        month = 12;
+                        year = (year + 99) % 100;
+                    }
+                    day = days_in_month(year, month);
+                }
+            }
+        }
+        Some(Self {
+            local,
+            utc: DecodedMinute {
+                year: Some(year),
+                month: Some(month),
+                day: Some(day),
+                weekday: Some(weekday),
+                hour: Some(hour),
+                dst: Some(0),
+                ..local
+            },
+            summer_time,
+        })
+    }
+}
+
+impl Snapshot {
+    /// Return the decoded minute in UK civil time and in UTC, None if a field of it is unknown.
+    pub fn civil_time(&self) -> Option<CivilTime> {
+        CivilTime::new(&self.radio_datetime, self.dut1)
+    }
+}
+
+impl MSFUtils {
+    /// Return the current date/time in UK civil time and in UTC, None if a field of it is
+    /// unknown.
+    pub fn civil_time(&self) -> Option<CivilTime> {
+        CivilTime::new(&self.radio_datetime, self.get_dut1())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::filled_msf;
+
+    #[test]
+    fn test_civil_time() {
+        let mut msf = filled_msf();
+        assert_eq!(msf.civil_time(), None);
+        let _ = msf.decode_minute(false);
+        let civil = msf.civil_time().unwrap();
+        assert_eq!(civil.summer_time, true);
+        assert_eq!(civil.local.hour, Some(14));
+        assert_eq!(civil.utc.hour, Some(13));
+        assert_eq!(civil.utc.minute, Some(58));
+        assert_eq!(civil.utc.day, Some(23));
+        // 00:30 BST on the first of the month is 23:30 UTC on the last day of the previous one
+        let mut rdt = msf.get_radio_datetime();
+        rdt.set_month(Some(3), true, false);
+        rdt.set_day(Some(1), true, false);
+        rdt.set_hour(Some(0), true, false);
+        rdt.set_minute(Some(30), true, false);
+        let civil = CivilTime::new(&rdt, None).unwrap();
+        assert_eq!(civil.utc.month, Some(2));
+        assert_eq!(civil.utc.day, Some(28));
+        assert_eq!(civil.utc.weekday, Some(5));
+        assert_eq!(civil.utc.hour, Some(23));
+        assert_eq!(civil.utc.minute, Some(30));
+        // GMT is UTC
+        rdt.set_dst(Some(false), Some(false), false);
+        let civil = CivilTime::new(&rdt, None).unwrap();
+        assert_eq!(civil.summer_time, false);
+        assert_eq!(
+            civil.utc,
+            DecodedMinute {
+                dst: Some(0),
+                ..civil.local
+            }
+        );
+    }
+}
@@ -0,0 +1,164 @@
+//! Compact state of the decoder in a fixed-size byte array, so a microcontroller can deep-sleep
+//! between reception windows and resume without acquiring the signal from scratch.
+//!
+//! Unlike `state::DecoderState`, this needs no `serde` and leaves out the bits of the current
+//! minute. It holds the date/time, DUT1, the sync status (0 without the `single-shot` feature),
+//! and the phase of the minute, i.e. the time stamp at which the current minute started. The
+//! phase is only meaningful if the time base passed to `handle_new_edge()` keeps running during
+//! the sleep, e.g. when it is derived from a low-power timer.
+
+use crate::MSFUtils;
+use radio_datetime_utils::{RadioDateTimeUtils, DST_SUMMER};
+
+/// Size of the compact state in bytes.
+pub const COMPACT_STATE_SIZE: usize = 16;
+
+/// Version of the layout, stored in the first byte.
+const VERSION: u8 = 1;
+
+// bits of the flags byte
+const FIRST_MINUTE: u8 = 1;
+const SUMMER_KNOWN: u8 = 2;
+const SUMMER: u8 = 4;
+const PHASE_KNOWN: u8 = 8;
+
+/// Marker of an unknown field.
+const UNKNOWN: u8 = 0xff;
+
+impl MSFUtils {
+    /// Return the compact state of the decoder, to be restored later using
+    /// `restore_compact_state()`.
+    ///
+    /// This method must be called _after_ `increase_second()`
+    pub fn save_compact_state(&self) -> [u8; COMPACT_STATE_SIZE] {
+        let rdt = &self.radio_datetime;
+        let summer = rdt.get_dst().map(|dst| dst & DST_SUMMER != 0);
+        let minute_start = self
+            .second_start
+            .map(|t| t.wrapping_sub(self.second as u32 * 1_000_000));
+        let mut state = [0; COMPACT_STATE_SIZE];
+        state[0] = VERSION;
+        state[1] = [
+            (self.first_minute, FIRST_MINUTE),
+            (summer.is_some(), SUMMER_KNOWN),
+            (summer == Some(true), SUMMER),
+            (minute_start.is_some(), PHASE_KNOWN),
+        ]
+        .iter()
+        .filter(|(set, _)| *set)
+        .fold(0, |flags, (_, flag)| flags | flag);
+        for (idx, field) in [
+            rdt.get_year(),
+            rdt.get_month(),
+            rdt.get_day(),
+            rdt.get_weekday(),
+            rdt.get_hour(),
+            rdt.get_minute(),
+        ]
+        .iter()
+        .enumerate()
+        {
+            state[2 + idx] = field.unwrap_or(UNKNOWN);
+        }
+        state[8] = self.get_dut1().map_or(0x80, |dut1| dut1 as u8);
+        #[cfg(feature = "single-shot")]
+        {
+            state[9] = self.sync_streak;
+        }
+        state[10..12].copy_from_slice(&self.minutes_since_decode.unwrap_or(0xffff).to_le_bytes());
+        state[12..16].copy_from_slice(&minute_start.unwrap_or(0).to_le_bytes());
+        state
+    }
+
+    /// Restore a state returned by `save_compact_state()`, keeping the configuration of this
+    /// decoder.
+    ///
+    /// The decoder resumes at the start of a minute without any received bits, with the
+    /// date/time advanced by the minutes slept. Edge tracking restarts with the next edge.
+    ///
+    /// Returns if the state was restored, states of another layout version are ignored.
+    ///
+    /// # Arguments
+    /// * `state` - the state to restore
+    /// * `elapsed_minutes` - the number of minutes between the minute in which the state was
+    ///   saved and the one in which it is restored
+    pub fn restore_compact_state(
+        &mut self,
+        state: &[u8; COMPACT_STATE_SIZE],
+        elapsed_minutes: u16,
+    ) -> bool {
+        if state[0] != VERSION {
+            return false;
+        }
+        let field = |idx: usize| (state[idx] != UNKNOWN).then_some(state[idx]);
+        let mut rdt = RadioDateTimeUtils::new(0);
+        rdt.set_year(field(2), true, false);
+        rdt.set_month(field(3), true, false);
+        rdt.set_day(field(4), true, false);
+        rdt.set_weekday(field(5), true, false);
+        rdt.set_hour(field(6), true, false);
+        rdt.set_minute(field(7), true, false);
+        let summer = (state[1] & SUMMER_KNOWN != 0).then_some(state[1] & SUMMER != 0);
+        rdt.set_dst(summer, Some(false), false);
+        self.first_minute = state[1] & FIRST_MINUTE != 0;
+        if !self.first_minute {
+            for _ in 0..elapsed_minutes {
+                if !rdt.add_minute() {
+                    break;
+                }
+            }
+        }
+        self.radio_datetime = rdt;
+        #[cfg(not(feature = "no-dut1"))]
+        {
+            self.dut1 = (state[8] != 0x80).then_some(state[8] as i8);
+        }
+        #[cfg(feature = "single-shot")]
+        {
+            self.sync_streak = state[9];
+        }
+        let minutes_since_decode = u16::from_le_bytes([state[10], state[11]]);
+        self.minutes_since_decode = (minutes_since_decode != 0xffff)
+            .then(|| minutes_since_decode.saturating_add(elapsed_minutes));
+        let minute_start = u32::from_le_bytes([state[12], state[13], state[14], state[15]]);
+        self.second_start = (state[1] & PHASE_KNOWN != 0)
+            .then(|| minute_start.wrapping_add(elapsed_minutes as u32 * 60_000_000));
+        self.second = 0;
+        self.bit_buffer_a = [None; radio_datetime_utils::BIT_BUFFER_SIZE];
+        self.bit_buffer_b = [None; radio_datetime_utils::BIT_BUFFER_SIZE];
+        self.before_first_edge = true;
+        self.new_second = false;
+        self.new_minute = false;
+        self.past_new_minute = false;
+        self.check_invariants();
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::filled_msf;
+
+    #[test]
+    fn test_save_restore_compact_state() {
+        let mut msf = filled_msf();
+        assert_eq!(msf.decode_minute(false).is_ok(), true);
+        msf.increase_second();
+        msf.second_start = Some(60_000_000);
+        let state = msf.save_compact_state();
+        let mut restored = MSFUtils::default();
+        assert_eq!(restored.restore_compact_state(&state, 3), true);
+        assert_eq!(restored.snapshot().first_minute, false);
+        let rdt = restored.get_radio_datetime();
+        assert_eq!((rdt.get_hour(), rdt.get_minute()), (Some(15), Some(1)));
+        assert_eq!(rdt.get_day(), Some(23));
+        assert_eq!(rdt.get_dst().map(|dst| dst & DST_SUMMER != 0), Some(true));
+        assert_eq!(restored.get_dut1(), msf.get_dut1());
+        assert_eq!(restored.minutes_since_decode, Some(3));
+        assert_eq!(restored.predicted_minute_marker_at(), Some(300_000_000));
+        let mut bad = state;
+        bad[0] = 0;
+        assert_eq!(restored.restore_compact_state(&bad, 0), false);
+    }
+}
@@ -0,0 +1,56 @@
+//! Confidence scores of the fields of the last decoded minute, e.g. to display fields which are
+//! probably correct differently from certain ones.
+
+/// Confidence of each field of the last decoded minute, from 0 (unknown or failed) to 255.
+///
+/// A field which was not received completely or failed its check (parity, or complete reception
+/// for fields without one) scores 0. Otherwise the score is the sum of:
+/// * 128 if the field passed its check, 64 if it could not be checked (A bits only)
+/// * 127 if the value agrees with the one predicted from the previous minute, 63 if there was
+///   no prediction, 0 if it disagrees
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ConfidenceScores {
+    pub year: u8,
+    pub month: u8,
+    pub day: u8,
+    pub weekday: u8,
+    pub hour: u8,
+    pub minute: u8,
+    pub dst: u8,
+    pub dut1: u8,
+}
+
+/// Compute the confidence score of a field.
+///
+/// # Arguments
+/// * `received` - the value of the field is known
+/// * `check` - result of the parity or other check, None if it could not be checked
+/// * `agrees` - the value agrees with the prediction, None if there was no prediction
+pub(crate) fn score(received: bool, check: Option<bool>, agrees: Option<bool>) -> u8 {
+    if !received || check == Some(false) {
+        return 0;
+    }
+    let checked = if check == Some(true) { 128 } else { 64 };
+    let predicted = match agrees {
+        Some(true) => 127,
+        Some(false) => 0,
+        None => 63,
+    };
+    checked + predicted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_score() {
+        assert_eq!(score(false, Some(true), Some(true)), 0);
+        assert_eq!(score(true, Some(false), Some(true)), 0);
+        assert_eq!(score(true, None, None), 127);
+        assert_eq!(score(true, None, Some(false)), 64);
+        assert_eq!(score(true, Some(true), Some(false)), 128);
+        assert_eq!(score(true, Some(true), None), 191);
+        assert_eq!(score(true, Some(true), Some(true)), 255);
+    }
+}
@@ -0,0 +1,165 @@
+//! Replay of an edge log through decoders with two different configurations, to compare the
+//! configurations using recorded data.
+//!
+//! Enabled with the `std` feature.
+
+use crate::decoder_config::DecoderConfig;
+use crate::{ConfigError, MSFUtils};
+use radio_datetime_utils::radio_datetime_helpers;
+use std::vec::Vec;
+
+/// Maximum difference in microseconds between the decode times of the same minute.
+const MATCH_WINDOW: u32 = 1_000_000;
+
+/// A successfully decoded minute.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DecodedMinute {
+    /// Time stamp of the edge after which the minute was decoded.
+    pub t: u32,
+    pub year: Option<u8>,
+    pub month: Option<u8>,
+    pub day: Option<u8>,
+    pub hour: Option<u8>,
+    pub minute: Option<u8>,
+    pub dut1: Option<i8>,
+}
+
+impl DecodedMinute {
+    /// Return if both minutes hold the same date/time and DUT1.
+    fn same_contents(&self, other: &Self) -> bool {
+        Self {
+            t: other.t,
+            ..*self
+        } == *other
+    }
+}
+
+/// Outcome of replaying the same edge log using two configurations.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ComparisonReport {
+    /// Minutes decoded using the first configuration.
+    pub decoded_a: Vec<DecodedMinute>,
+    /// Minutes decoded using the second configuration.
+    pub decoded_b: Vec<DecodedMinute>,
+    /// Minutes only decoded using the second configuration.
+    pub gained: Vec<DecodedMinute>,
+    /// Minutes only decoded using the first configuration.
+    pub lost: Vec<DecodedMinute>,
+    /// Minutes decoded using both configurations, but with different results.
+    pub differing: Vec<(DecodedMinute, DecodedMinute)>,
+}
+
+/// Feed the edges to the decoder using `MSFUtils::decode_edges()` and return the successfully
+/// decoded minutes.
+///
+/// # Arguments
+/// * `msf` - the decoder
+/// * `edges` - the edges as `(is_low_edge, t)` pairs, see `MSFUtils::handle_new_edge()`
+pub fn replay(msf: &mut MSFUtils, edges: &[(bool, u32)]) -> Vec<DecodedMinute> {
+    let mut decoded = Vec::new();
+    let mut stream = msf.decode_edges(edges.iter().copied());
+    while let Some(res) = stream.next() {
+        if let (Ok(snapshot), Some(t)) = (res, stream.get_last_t()) {
+            let rdt = snapshot.radio_datetime;
+            decoded.push(DecodedMinute {
+                t,
+                year: rdt.get_year(),
+                month: rdt.get_month(),
+                day: rdt.get_day(),
+                hour: rdt.get_hour(),
+                minute: rdt.get_minute(),
+                dut1: snapshot.dut1,
+            });
+        }
+    }
+    decoded
+}
+
+/// Replay the edges using both configurations and report the differences, or the setting of
+/// either configuration which was rejected.
+///
+/// # Arguments
+/// * `edges` - the edges as `(is_low_edge, t)` pairs, see `MSFUtils::handle_new_edge()`
+/// * `config_a` - the first (reference) configuration
+/// * `config_b` - the second configuration
+pub fn compare(
+    edges: &[(bool, u32)],
+    config_a: DecoderConfig,
+    config_b: DecoderConfig,
+) -> Result<ComparisonReport, ConfigError> {
+    let decode = |config| {
+        let mut msf = MSFUtils::builder().decoder_config(config).build()?;
+        Ok(replay(&mut msf, edges))
+    };
+    let decoded_a = decode(config_a)?;
+    let decoded_b = decode(config_b)?;
+    let find = |minutes: &[DecodedMinute], t: u32| {
+        minutes.iter().copied().find(|m| {
+            radio_datetime_helpers::time_diff(m.t, t).min(radio_datetime_helpers::time_diff(t, m.t))
+                < MATCH_WINDOW
+        })
+    };
+    let mut report = ComparisonReport::default();
+    for a in &decoded_a {
+        match find(&decoded_b, a.t) {
+            Some(b) if !a.same_contents(&b) => report.differing.push((*a, b)),
+            Some(_) => {}
+            None => report.lost.push(*a),
+        }
+    }
+    for b in &decoded_b {
+        if find(&decoded_a, b.t).is_none() {
+            report.gained.push(*b);
+        }
+    }
+    report.decoded_a = decoded_a;
+    report.decoded_b = decoded_b;
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::minute_edges;
+
+    #[test]
+    fn test_replay() {
+        let mut msf = MSFUtils::default();
+        let decoded = replay(&mut msf, &minute_edges(1_000_000));
+        assert_eq!(decoded.len(), 1);
+        assert_eq!(decoded[0].t, 61_000_000);
+        assert_eq!(decoded[0].hour, Some(14));
+        assert_eq!(decoded[0].minute, Some(58));
+        #[cfg(not(feature = "no-dut1"))]
+        assert_eq!(decoded[0].dut1, Some(-2));
+    }
+    #[test]
+    fn test_compare() {
+        let edges = minute_edges(1_000_000);
+        let report = compare(&edges, DecoderConfig::DEFAULT, DecoderConfig::DEFAULT).unwrap();
+        assert_eq!(report.decoded_a, report.decoded_b);
+        assert_eq!(report.gained.len(), 0);
+        assert_eq!(report.lost.len(), 0);
+        assert_eq!(report.differing.len(), 0);
+        // 200 ms pulses then count as bit A being 0
+        let config_b = DecoderConfig {
+            active_0_limit: 210_000,
+            active_a_limit: 220_000,
+            ..DecoderConfig::DEFAULT
+        };
+        let report = compare(&edges, DecoderConfig::DEFAULT, config_b).unwrap();
+        assert_eq!(report.decoded_b.len(), 0);
+        assert_eq!(report.lost, report.decoded_a);
+        assert_eq!(report.gained.len(), 0);
+        let report = compare(&edges, config_b, DecoderConfig::DEFAULT).unwrap();
+        assert_eq!(report.gained, report.decoded_b);
+        let config_b = DecoderConfig {
+            minute_limit: 300_000,
+            ..DecoderConfig::DEFAULT
+        };
+        assert_eq!(
+            compare(&edges, DecoderConfig::DEFAULT, config_b).err(),
+            Some(ConfigError::DecoderConfig)
+        );
+    }
+}
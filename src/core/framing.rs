@@ -0,0 +1,177 @@
+//! Framing of the edges into pulses and seconds, shared by the decoders.
+//!
+//! Spikes are filtered out here, the remaining edges either end a pulse or start one, the latter
+//! possibly starting a new second. What the pulse means is left to the decoder.
+
+use radio_datetime_utils::radio_datetime_helpers;
+
+/// Limits used to frame the edges, in microseconds.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FramingLimits {
+    /// Edges following the previous one within this time are spikes.
+    pub spike: u32,
+    /// Upper limit of the passive part of a second.
+    pub passive_runaway: u32,
+    /// Upper limit of the length of the begin-of-minute marker.
+    pub minute: u32,
+    /// Lower limit of the time between edges to assume the timer was paused.
+    pub discontinuity: u32,
+}
+
+/// What an edge means for the framing.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FramedEdge {
+    /// The very first edge, only starts the edge tracking.
+    First,
+    /// The timer is assumed to have been paused, edge tracking was restarted.
+    Discontinuity,
+    /// A spike of the given length, ignored.
+    Spike { length: u32 },
+    /// End of a pulse of length `active`, after a passive part of length `passive` (0 if
+    /// unknown).
+    PulseEnd { active: u32, passive: u32 },
+    /// Start of a pulse after a passive part of length `passive`, possibly starting a new second.
+    PulseStart { passive: u32, new_second: bool },
+    /// Start of a pulse after a passive part of length `passive` which is too long.
+    PassiveRunaway { passive: u32 },
+}
+
+impl FramedEdge {
+    /// Return the time since the previous edge for the edges which were measured against it.
+    pub fn length(&self) -> Option<u32> {
+        match *self {
+            Self::First | Self::Discontinuity => None,
+            Self::Spike { length } => Some(length),
+            Self::PulseEnd { active, .. } => Some(active),
+            Self::PulseStart { passive, .. } | Self::PassiveRunaway { passive } => Some(passive),
+        }
+    }
+}
+
+/// Edge framing for a decoder which keeps the edge tracking state.
+pub trait EdgeFraming {
+    /// Return the edge tracking state: if no edge was received yet, the time stamp of the last
+    /// edge which was not a spike, and the time between the last two such edges (0 if unknown).
+    fn edge_state(&mut self) -> (&mut bool, &mut u32, &mut u32);
+
+    /// Frame a new edge and update the edge tracking state.
+    ///
+    /// # Arguments
+    /// * `is_low_edge` - indicates that the edge ends a pulse
+    /// * `t` - time stamp of the received edge, in microseconds
+    /// * `limits` - the limits to frame the edge with
+    fn frame_edge(&mut self, is_low_edge: bool, t: u32, limits: &FramingLimits) -> FramedEdge {
+        let (before_first_edge, t0, old_t_diff) = self.edge_state();
+        if *before_first_edge {
+            *before_first_edge = false;
+            *t0 = t;
+            return FramedEdge::First;
+        }
+        let t_diff = radio_datetime_helpers::time_diff(*t0, t);
+        // time stamps out of order wrap around to a large difference too
+        if t_diff >= limits.discontinuity {
+            *t0 = t;
+            *old_t_diff = 0; // the next pulse is the first one again
+            return FramedEdge::Discontinuity;
+        }
+        if t_diff < limits.spike {
+            // Shift t0 to deal with a train of spikes adding up to more than the spike limit.
+            *t0 = t0.wrapping_add(t_diff);
+            return FramedEdge::Spike { length: t_diff };
+        }
+        *t0 = t;
+        let passive = *old_t_diff;
+        *old_t_diff = t_diff;
+        if is_low_edge {
+            FramedEdge::PulseEnd {
+                active: t_diff,
+                passive,
+            }
+        } else if t_diff < limits.passive_runaway {
+            FramedEdge::PulseStart {
+                passive: t_diff,
+                new_second: t_diff > 1_000_000 - limits.minute,
+            }
+        } else {
+            FramedEdge::PassiveRunaway { passive: t_diff }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LIMITS: FramingLimits = FramingLimits {
+        spike: 30_000,
+        passive_runaway: 1_500_000,
+        minute: 550_000,
+        discontinuity: 60_000_000,
+    };
+
+    #[derive(Default)]
+    struct Framer {
+        before_first_edge: bool,
+        t0: u32,
+        old_t_diff: u32,
+    }
+
+    impl EdgeFraming for Framer {
+        fn edge_state(&mut self) -> (&mut bool, &mut u32, &mut u32) {
+            (
+                &mut self.before_first_edge,
+                &mut self.t0,
+                &mut self.old_t_diff,
+            )
+        }
+    }
+
+    #[test]
+    fn test_frame_edge() {
+        let mut framer = Framer {
+            before_first_edge: true,
+            ..Framer::default()
+        };
+        assert_eq!(framer.frame_edge(true, 0, &LIMITS), FramedEdge::First);
+        assert_eq!(
+            framer.frame_edge(false, 900_000, &LIMITS),
+            FramedEdge::PulseStart {
+                passive: 900_000,
+                new_second: true
+            }
+        );
+        assert_eq!(
+            framer.frame_edge(true, 910_000, &LIMITS),
+            FramedEdge::Spike { length: 10_000 }
+        );
+        assert_eq!(framer.t0, 910_000); // shifted by the spike
+        assert_eq!(
+            framer.frame_edge(true, 1_000_000, &LIMITS),
+            FramedEdge::PulseEnd {
+                active: 90_000,
+                passive: 900_000
+            }
+        );
+        assert_eq!(
+            framer.frame_edge(false, 1_100_000, &LIMITS),
+            FramedEdge::PulseStart {
+                passive: 100_000,
+                new_second: false
+            }
+        );
+        assert_eq!(
+            framer.frame_edge(false, 3_000_000, &LIMITS),
+            FramedEdge::PassiveRunaway { passive: 1_900_000 }
+        );
+        assert_eq!(
+            framer.frame_edge(true, 1_000, &LIMITS),
+            FramedEdge::Discontinuity
+        );
+        assert_eq!(framer.old_t_diff, 0);
+        assert_eq!(FramedEdge::Discontinuity.length(), None);
+        assert_eq!(
+            FramedEdge::PassiveRunaway { passive: 1_900_000 }.length(),
+            Some(1_900_000)
+        );
+    }
+}
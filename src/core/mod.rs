@@ -0,0 +1,10 @@
+//! The decoder core: classification of the edges into pulses, and decoding of the received
+//! bits. `MSFUtils` drives these for each edge and minute.
+//!
+//! Always built, the other parts of the crate are selected using features.
+
+pub(crate) mod framing;
+pub mod msf_helpers;
+pub(crate) mod pulse;
+
+pub use pulse::EdgeClass;
@@ -0,0 +1,129 @@
+/// Decode the unary value of the given slice.
+/// A 0 bit cannot be followed by a 1 bit.
+///
+/// # Arguments
+/// * `bit_buffer` - buffer containing to calculate the value from
+/// * `start` - start bit position
+/// * `stop` - stop bit position
+pub fn get_unary_value(bit_buffer: &[Option<bool>], start: usize, stop: usize) -> Option<i8> {
+    let mut sum = 0;
+    let mut old_bit = None;
+    for bit in &bit_buffer[start..=stop] {
+        (*bit)?;
+        let s_bit = bit.unwrap();
+        if s_bit && old_bit == Some(false) {
+            return None;
+        }
+        sum += s_bit as i8;
+        old_bit = *bit;
+    }
+    Some(sum)
+}
+
+/// Calculate a CRC-16/CCITT over the given bytes.
+///
+/// # Arguments
+/// * `bytes` - the data to calculate the CRC over
+pub fn get_crc16(bytes: impl IntoIterator<Item = u8>) -> u16 {
+    let mut crc: u16 = 0xffff;
+    for byte in bytes {
+        crc ^= (byte as u16) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x1021
+            } else {
+                crc << 1
+            };
+        }
+    }
+    crc
+}
+
+/// Calculate a CRC-16/CCITT over the given A and B bits.
+///
+/// Each second is fed as one byte, with bit 0 set for a 1 A bit, bit 1 for a 1 B bit, bit 2 for
+/// an unknown A bit, and bit 3 for an unknown B bit.
+///
+/// # Arguments
+/// * `bits_a` - the A bits of the minute
+/// * `bits_b` - the B bits of the minute, must be as long as `bits_a`
+pub fn get_minute_crc(bits_a: &[Option<bool>], bits_b: &[Option<bool>]) -> u16 {
+    get_crc16(bits_a.iter().zip(bits_b).map(|(bit_a, bit_b)| {
+        (match bit_a {
+            Some(a) => *a as u8,
+            None => 0b0100,
+        }) | match bit_b {
+            Some(b) => (*b as u8) << 1,
+            None => 0b1000,
+        }
+    }))
+}
+
+/// Initial value for `get_fnv1a()`.
+pub const FNV1A_BASIS: u32 = 0x811c_9dc5;
+
+/// Continue a 32-bit FNV-1a hash over the given bytes.
+///
+/// # Arguments
+/// * `hash` - the hash so far, `FNV1A_BASIS` to start a new one
+/// * `bytes` - the data to add to the hash
+pub fn get_fnv1a(hash: u32, bytes: impl IntoIterator<Item = u8>) -> u32 {
+    bytes.into_iter().fold(hash, |hash, byte| {
+        (hash ^ byte as u32).wrapping_mul(0x0100_0193)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_unary_value_all_0() {
+        const UNARY_BUFFER: [Option<bool>; 4] =
+            [Some(false), Some(false), Some(false), Some(false)];
+        assert_eq!(get_unary_value(&UNARY_BUFFER, 0, 3), Some(0));
+    }
+    #[test]
+    fn test_get_unary_value_all_1() {
+        const UNARY_BUFFER: [Option<bool>; 4] = [Some(true), Some(true), Some(true), Some(true)];
+        assert_eq!(get_unary_value(&UNARY_BUFFER, 0, 3), Some(4));
+    }
+    #[test]
+    fn test_get_unary_value_middle() {
+        const UNARY_BUFFER: [Option<bool>; 4] = [Some(true), Some(true), Some(false), Some(false)];
+        assert_eq!(get_unary_value(&UNARY_BUFFER, 0, 3), Some(2));
+    }
+    #[test]
+    fn test_get_unary_value_1_after_0() {
+        const UNARY_BUFFER: [Option<bool>; 4] = [Some(false), Some(false), Some(true), Some(false)];
+        assert_eq!(get_unary_value(&UNARY_BUFFER, 0, 3), None);
+    }
+    #[test]
+    fn test_get_unary_value_invalid_none() {
+        const UNARY_BUFFER: [Option<bool>; 4] = [Some(true), Some(true), None, Some(false)];
+        assert_eq!(get_unary_value(&UNARY_BUFFER, 0, 3), None);
+    }
+    #[test]
+    fn test_get_crc16() {
+        assert_eq!(get_crc16(*b"123456789"), 0x29b1); // CRC-16/CCITT-FALSE check value
+    }
+    #[test]
+    fn test_get_fnv1a() {
+        assert_eq!(get_fnv1a(FNV1A_BASIS, *b""), FNV1A_BASIS);
+        assert_eq!(get_fnv1a(FNV1A_BASIS, *b"a"), 0xe40c_292c);
+        assert_eq!(
+            get_fnv1a(get_fnv1a(FNV1A_BASIS, *b"fo"), *b"o"),
+            get_fnv1a(FNV1A_BASIS, *b"foo")
+        );
+    }
+    #[test]
+    fn test_get_minute_crc() {
+        const BITS_A: [Option<bool>; 4] = [Some(true), Some(false), None, Some(true)];
+        const BITS_B: [Option<bool>; 4] = [Some(true), None, Some(false), Some(false)];
+        assert_eq!(get_minute_crc(&[], &[]), 0xffff);
+        let crc = get_minute_crc(&BITS_A, &BITS_B);
+        assert_ne!(crc, get_minute_crc(&BITS_A[..3], &BITS_B[..3]));
+        assert_ne!(crc, get_minute_crc(&BITS_B, &BITS_A));
+        assert_eq!(crc, get_minute_crc(&BITS_A, &BITS_B));
+    }
+}
@@ -0,0 +1,163 @@
+//! Classification of the edges and of the carrier-off pulses, shared by the decoders.
+
+use crate::adaptive_limits::ActiveLimits;
+use crate::ook::{self, PulseRule};
+use crate::MINUTE_LIMIT;
+
+/// What the decoder made of an edge.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EdgeClass {
+    /// The very first edge, only starts the edge tracking.
+    First,
+    /// Ignored, because it followed an edge of the same kind within the refractory window.
+    Refractory,
+    /// The timer of the caller is assumed to have been paused, edge tracking was restarted.
+    Discontinuity,
+    /// Ignored as a spike.
+    Spike,
+    /// End of a pulse carrying the given bit pair.
+    Bit { a: bool, b: bool },
+    /// End of the begin-of-minute marker.
+    MinuteMarker,
+    /// End of a pulse which is too long, or of which the passive part before it is unknown.
+    ActiveRunaway,
+    /// End of a short pulse which did not change the bits.
+    Unchanged,
+    /// Start of a pulse which starts a new second.
+    NewSecond,
+    /// Start of a pulse within a second, e.g. the second pulse of a (0,1) bit pair.
+    Passive,
+    /// Start of a pulse after a too long passive part.
+    PassiveRunaway,
+}
+
+/// Meaning of a carrier-off pulse.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Pulse {
+    /// A bit pair, the (0,1) pair consists of two short pulses.
+    Bit { a: bool, b: bool },
+    /// The 500 ms begin-of-minute marker.
+    MinuteMarker,
+    /// The pulse is too long, or the passive part before it is unknown.
+    Runaway,
+    /// A short pulse which does not change the bits.
+    Unchanged,
+}
+
+/// Build the classification table of the MSF pulses for the given limits.
+///
+/// # Arguments
+/// * `limits` - upper limits of the active pulse lengths
+/// * `minute_limit` - upper limit of the length of the begin-of-minute marker
+pub(crate) const fn pulse_table(limits: &ActiveLimits, minute_limit: u32) -> [PulseRule<Pulse>; 6] {
+    [
+        PulseRule {
+            active_below: limits.limit_0,
+            passive: 1..=limits.limit_0 - 1,
+            symbol: Pulse::Bit { a: false, b: true },
+        },
+        PulseRule {
+            active_below: limits.limit_0,
+            passive: 1_000_000 - minute_limit + 1..=u32::MAX,
+            symbol: Pulse::Bit { a: false, b: false },
+        },
+        PulseRule {
+            active_below: limits.limit_0,
+            passive: 0..=u32::MAX,
+            symbol: Pulse::Unchanged,
+        },
+        PulseRule {
+            active_below: limits.limit_a,
+            passive: 1_000_000 - limits.limit_ab + 1..=u32::MAX,
+            symbol: Pulse::Bit { a: true, b: false },
+        },
+        PulseRule {
+            active_below: limits.limit_ab,
+            passive: 1_000_000 - limits.limit_ab + 1..=u32::MAX,
+            symbol: Pulse::Bit { a: true, b: true },
+        },
+        PulseRule {
+            active_below: minute_limit,
+            passive: 1_000_000 - limits.limit_ab + 1..=u32::MAX,
+            symbol: Pulse::MinuteMarker,
+        },
+    ]
+}
+
+/// Classification table of the MSF pulses with the fixed limits.
+pub(crate) const MSF_PULSE_TABLE: [PulseRule<Pulse>; 6] =
+    pulse_table(&ActiveLimits::FIXED, MINUTE_LIMIT);
+
+impl Pulse {
+    /// Classify a pulse using `MSF_PULSE_TABLE`.
+    ///
+    /// # Arguments
+    /// * `active` - length of the pulse in microseconds
+    /// * `passive` - length of the passive part before the pulse in microseconds, 0 if unknown
+    pub(crate) fn classify(active: u32, passive: u32) -> Self {
+        ook::classify(&MSF_PULSE_TABLE, active, passive, Self::Runaway)
+    }
+
+    /// Classify a pulse using a table built for the given limits.
+    ///
+    /// # Arguments
+    /// * `active` - length of the pulse in microseconds
+    /// * `passive` - length of the passive part before the pulse in microseconds, 0 if unknown
+    /// * `limits` - upper limits of the active pulse lengths
+    /// * `minute_limit` - upper limit of the length of the begin-of-minute marker
+    pub(crate) fn classify_with(
+        active: u32,
+        passive: u32,
+        limits: &ActiveLimits,
+        minute_limit: u32,
+    ) -> Self {
+        ook::classify(
+            &pulse_table(limits, minute_limit),
+            active,
+            passive,
+            Self::Runaway,
+        )
+    }
+
+    /// Return the nominal length of the pulse in microseconds, if it is a valid one.
+    pub(crate) fn nominal(&self) -> Option<i32> {
+        match self {
+            Self::Bit { a: false, .. } => Some(100_000),
+            Self::Bit { a: true, b: false } => Some(200_000),
+            Self::Bit { a: true, b: true } => Some(300_000),
+            Self::MinuteMarker => Some(500_000),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify() {
+        assert_eq!(
+            Pulse::classify(100_000, 900_000),
+            Pulse::Bit { a: false, b: false }
+        );
+        assert_eq!(
+            Pulse::classify(100_000, 100_000),
+            Pulse::Bit { a: false, b: true }
+        );
+        assert_eq!(Pulse::classify(100_000, 300_000), Pulse::Unchanged);
+        assert_eq!(
+            Pulse::classify(200_000, 800_000),
+            Pulse::Bit { a: true, b: false }
+        );
+        assert_eq!(
+            Pulse::classify(300_000, 700_000),
+            Pulse::Bit { a: true, b: true }
+        );
+        assert_eq!(Pulse::classify(500_000, 700_000), Pulse::MinuteMarker);
+        assert_eq!(Pulse::classify(500_000, 0), Pulse::Runaway);
+        assert_eq!(Pulse::classify(700_000, 700_000), Pulse::Runaway);
+        assert_eq!(Pulse::MinuteMarker.nominal(), Some(500_000));
+        assert_eq!(Pulse::Unchanged.nominal(), None);
+    }
+}
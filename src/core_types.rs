@@ -0,0 +1,100 @@
+//! Primitive types shared with the sibling radio crates (e.g. dcf77_utils), so applications
+//! which combine several stations need no conversion glue between them.
+//!
+//! The types in this module are versioned separately from the rest of the crate:
+//! `CORE_TYPES_VERSION` is only increased, and this module only changes incompatibly, with a new
+//! major version of the crate. Fields which a station does not broadcast are None.
+
+use radio_datetime_utils::RadioDateTimeUtils;
+
+/// Version of the types in this module, equal in all crates which share them.
+pub const CORE_TYPES_VERSION: u16 = 1;
+
+/// The bits received during one second, `b` is None for stations with a single bit per second.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BitPair {
+    pub a: Option<bool>,
+    pub b: Option<bool>,
+}
+
+/// Length of the current minute, see `MSFUtils::minute_length_estimate()`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MinuteLength {
+    /// Length of the minute in seconds, 59, 60, or 61.
+    pub length: u8,
+    /// The length is confirmed by the end-of-minute marker or the configuration, otherwise it
+    /// may still change in a later second.
+    pub certain: bool,
+}
+
+/// Reception quality of the current minute so far, see `MSFUtils::get_signal_quality()`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SignalQuality {
+    /// Number of seconds with completely received bits.
+    pub valid_seconds: u8,
+    /// Number of seconds which have passed.
+    pub seconds: u8,
+}
+
+impl SignalQuality {
+    /// Return the share of valid seconds in percent, 0 if no second has passed yet.
+    pub fn percent(&self) -> u8 {
+        if self.seconds == 0 {
+            0
+        } else {
+            (self.valid_seconds as u16 * 100 / self.seconds as u16) as u8
+        }
+    }
+}
+
+/// Date/time of a decoded minute, independent of the station.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DecodedMinute {
+    /// Year within the century.
+    pub year: Option<u8>,
+    pub month: Option<u8>,
+    pub day: Option<u8>,
+    /// Day of the week, 0 is Sunday.
+    pub weekday: Option<u8>,
+    pub hour: Option<u8>,
+    pub minute: Option<u8>,
+    /// DST flags as in `radio_datetime_utils`.
+    pub dst: Option<u8>,
+    /// DUT1 (UT1 - UTC) in deci-seconds.
+    pub dut1: Option<i8>,
+}
+
+impl DecodedMinute {
+    /// Collect the fields of a decoded date/time.
+    ///
+    /// # Arguments
+    /// * `radio_datetime` - the date/time
+    /// * `dut1` - DUT1 in deci-seconds, None if the station does not broadcast it
+    pub fn new(radio_datetime: &RadioDateTimeUtils, dut1: Option<i8>) -> Self {
+        Self {
+            year: radio_datetime.get_year(),
+            month: radio_datetime.get_month(),
+            day: radio_datetime.get_day(),
+            weekday: radio_datetime.get_weekday(),
+            hour: radio_datetime.get_hour(),
+            minute: radio_datetime.get_minute(),
+            dst: radio_datetime.get_dst(),
+            dut1,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signal_quality_percent() {
+        assert_eq!(SignalQuality::default().percent(), 0);
+        let quality = SignalQuality {
+            valid_seconds: 45,
+            seconds: 60,
+        };
+        assert_eq!(quality.percent(), 75);
+    }
+}
@@ -0,0 +1,142 @@
+//! Configuration of the decoder: the timing limits, to compensate for the pulse shaping of a
+//! particular receiver without changing the crate, and the strictness, gating, holdover, and
+//! statistics settings. `DecoderConfig::preset()` bundles these for typical applications.
+
+use crate::adaptive_limits::ActiveLimits;
+use crate::merge_policy::MergePolicy;
+use crate::strictness::Escalation;
+use crate::{
+    ACTIVE_0_LIMIT, ACTIVE_AB_LIMIT, ACTIVE_A_LIMIT, MINUTE_LIMIT, PASSIVE_RUNAWAY,
+    TIMER_DISCONTINUITY,
+};
+
+/// Configuration of the decoder, see `MSFUtils::set_decoder_config()`.
+///
+/// The limits are in microseconds. Each setting can also be changed on its own using the
+/// setter of `MSFUtils` with the same name.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DecoderConfig {
+    /// Maximum length of a 100 ms pulse, i.e. bit A is 0.
+    pub active_0_limit: u32,
+    /// Maximum length of a 200 ms pulse, i.e. bits (1,0).
+    pub active_a_limit: u32,
+    /// Maximum length of a 300 ms pulse, i.e. bits (1,1).
+    pub active_ab_limit: u32,
+    /// Maximum length of the 500 ms begin-of-minute marker, longer pulses are runaways.
+    pub minute_limit: u32,
+    /// Time without a pulse after which the signal is considered lost.
+    pub passive_runaway: u32,
+    /// Strictness: which fields of a minute decoded using relaxed checks are merged.
+    pub merge_policy: MergePolicy,
+    /// Strictness: when `decode_minute_auto()` switches between relaxed and strict checks.
+    pub escalation: Escalation,
+    /// Gating: window within which a second edge of the same direction is ignored, 0 is off.
+    pub refractory_window: u32,
+    /// Gating: consecutive consistent minutes needed for a single-shot sync, 0 is off, only used
+    /// with the `single-shot` feature.
+    pub sync_minutes: u8,
+    /// Holdover: keep the second counter aligned through missed seconds, only used with the
+    /// `flywheel` feature.
+    pub flywheel: bool,
+    /// Statistics: correct the pulse lengths for the estimated pulse bias.
+    pub pulse_bias_compensation: bool,
+    /// Statistics: learn the limits of the pulse lengths from the observed pulses.
+    pub adaptive_limits: bool,
+}
+
+impl DecoderConfig {
+    /// The default configuration.
+    pub const DEFAULT: Self = Self {
+        active_0_limit: ACTIVE_0_LIMIT,
+        active_a_limit: ACTIVE_A_LIMIT,
+        active_ab_limit: ACTIVE_AB_LIMIT,
+        minute_limit: MINUTE_LIMIT,
+        passive_runaway: PASSIVE_RUNAWAY,
+        merge_policy: MergePolicy::Parity,
+        escalation: Escalation::DEFAULT,
+        refractory_window: 0,
+        sync_minutes: 0,
+        flywheel: false,
+        pulse_bias_compensation: false,
+        adaptive_limits: false,
+    };
+
+    /// Return if the configuration is usable: each active limit lies above the nominal length of
+    /// its pulse and below the next limit, the minute limit lies below one second, the passive
+    /// runaway limit lies in [1_000_000..TIMER_DISCONTINUITY), the escalation limits are at
+    /// least 1, and the refractory window lies below the default `active_0_limit`.
+    pub fn is_valid(&self) -> bool {
+        100_000 < self.active_0_limit
+            && self.active_0_limit < self.active_a_limit
+            && 200_000 < self.active_a_limit
+            && self.active_a_limit < self.active_ab_limit
+            && 300_000 < self.active_ab_limit
+            && self.active_ab_limit < self.minute_limit
+            && 500_000 < self.minute_limit
+            && self.minute_limit < 1_000_000
+            && (1_000_000..TIMER_DISCONTINUITY).contains(&self.passive_runaway)
+            && self.escalation.is_valid()
+            && self.refractory_window < ACTIVE_0_LIMIT
+    }
+
+    /// Return the upper limits of the active pulse lengths.
+    pub fn active_limits(&self) -> ActiveLimits {
+        ActiveLimits {
+            limit_0: self.active_0_limit,
+            limit_a: self.active_a_limit,
+            limit_ab: self.active_ab_limit,
+        }
+    }
+
+    /// Return if the pulse length limits are the default ones.
+    pub(crate) fn has_default_limits(&self) -> bool {
+        self.active_limits() == ActiveLimits::FIXED && self.minute_limit == MINUTE_LIMIT
+    }
+}
+
+impl Default for DecoderConfig {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_valid() {
+        assert_eq!(DecoderConfig::DEFAULT.is_valid(), true);
+        assert_eq!(DecoderConfig::DEFAULT.active_limits(), ActiveLimits::FIXED);
+        assert_eq!(DecoderConfig::DEFAULT.has_default_limits(), true);
+        let config = DecoderConfig {
+            active_a_limit: 400_000, // above active_ab_limit
+            ..DecoderConfig::DEFAULT
+        };
+        assert_eq!(config.is_valid(), false);
+        assert_eq!(config.has_default_limits(), false);
+        let config = DecoderConfig {
+            minute_limit: 1_000_000,
+            ..DecoderConfig::DEFAULT
+        };
+        assert_eq!(config.is_valid(), false);
+        let config = DecoderConfig {
+            passive_runaway: TIMER_DISCONTINUITY,
+            ..DecoderConfig::DEFAULT
+        };
+        assert_eq!(config.is_valid(), false);
+        let config = DecoderConfig {
+            escalation: Escalation {
+                escalate_after: 0,
+                deescalate_after: 10,
+            },
+            ..DecoderConfig::DEFAULT
+        };
+        assert_eq!(config.is_valid(), false);
+        let config = DecoderConfig {
+            refractory_window: ACTIVE_0_LIMIT,
+            ..DecoderConfig::DEFAULT
+        };
+        assert_eq!(config.is_valid(), false);
+    }
+}
@@ -0,0 +1,76 @@
+//! Diagnostics about anomalies seen by the decoder, tagged with a severity.
+//!
+//! Diagnostics are passed to the hook set using `MSFUtils::set_diagnostic_hook()`. With the `log`
+//! feature, they are also emitted through the `log` crate. With the `defmt` feature, the types
+//! implement `defmt::Format` so the hook can pass them on to defmt.
+
+use crate::marker_policy::MinuteMarker;
+
+/// Severity of a diagnostic.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Severity {
+    /// Expected during normal reception, e.g. after a pause.
+    Info,
+    /// Reception error, the affected bit or minute is lost.
+    Warning,
+}
+
+/// An anomaly seen by the decoder.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Diagnostic {
+    /// The active part of a second lasted too long.
+    ActiveRunaway { second: u8 },
+    /// No edge arrived for too long, the transmitter might be off.
+    PassiveRunaway { second: u8 },
+    /// The timer of the caller was paused, see `MSFUtils::get_timer_discontinuity()`.
+    TimerDiscontinuity,
+    /// The begin-of-minute marker arrived while the second counter was not at the start of
+    /// the minute.
+    Misalignment { second: u8 },
+    /// The given minute marker arrived while the second counter was not at the end or the
+    /// start of the minute, see `MSFUtils::set_marker_policy()`.
+    MarkerConflict { second: u8, marker: MinuteMarker },
+    /// The parity check of the given group (1 = year, 2 = month/day, 3 = weekday,
+    /// 4 = hour/minute) failed.
+    ParityMismatch { group: u8 },
+    /// The internal state of the decoder is inconsistent, see
+    /// `MSFUtils::set_invariant_checks()`.
+    InconsistentState,
+}
+
+impl Diagnostic {
+    /// Return the severity of this diagnostic.
+    pub fn severity(&self) -> Severity {
+        match self {
+            Self::TimerDiscontinuity => Severity::Info,
+            _ => Severity::Warning,
+        }
+    }
+}
+
+#[cfg(feature = "log")]
+impl From<Severity> for log::Level {
+    fn from(severity: Severity) -> Self {
+        match severity {
+            Severity::Info => log::Level::Info,
+            Severity::Warning => log::Level::Warn,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_severity() {
+        assert_eq!(Diagnostic::TimerDiscontinuity.severity(), Severity::Info);
+        assert_eq!(
+            Diagnostic::ParityMismatch { group: 4 }.severity(),
+            Severity::Warning
+        );
+        assert_eq!(Severity::Info < Severity::Warning, true);
+    }
+}
@@ -0,0 +1,6 @@
+//! Phase and drift estimation for disciplining a local clock.
+//!
+//! Enabled with the `discipline` feature.
+
+pub mod phase_samples;
+pub mod stability;
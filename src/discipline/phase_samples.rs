@@ -0,0 +1,133 @@
+//! Raw deviations of the second boundaries, for external stability analysis.
+
+use radio_datetime_utils::radio_datetime_helpers;
+
+/// Number of samples kept until they are drained, older samples are overwritten.
+pub const PHASE_SAMPLES_SIZE: usize = 64;
+
+/// Ring buffer of the deviations of the lengths of consecutive seconds from one second, as
+/// measured by the timer passed to `handle_new_edge()`.
+///
+/// Each sample is the time between two consecutive second boundaries (the start of the active
+/// pulse) minus 1_000_000 microseconds, without any smoothing. Divided by one million, these are
+/// the fractional frequency deviations of the local timer against MSF over one second. Seconds
+/// of which the boundary was not received do not produce a sample.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PhaseSamples {
+    samples: [i32; PHASE_SAMPLES_SIZE],
+    start: usize,
+    len: usize,
+    dropped: u32,
+    last_boundary: Option<u32>,
+}
+
+impl Default for PhaseSamples {
+    fn default() -> Self {
+        Self {
+            samples: [0; PHASE_SAMPLES_SIZE],
+            start: 0,
+            len: 0,
+            dropped: 0,
+            last_boundary: None,
+        }
+    }
+}
+
+impl PhaseSamples {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a second boundary, which adds a sample if the previous boundary was received
+    /// about one second earlier.
+    ///
+    /// # Arguments
+    /// * `t` - time stamp of the boundary in microseconds
+    pub fn add_boundary(&mut self, t: u32) {
+        if let Some(last) = self.last_boundary {
+            let deviation = radio_datetime_helpers::time_diff(last, t) as i32 - 1_000_000;
+            if deviation.abs() < 500_000 {
+                self.push(deviation);
+            }
+        }
+        self.last_boundary = Some(t);
+    }
+
+    /// Forget the last boundary, e.g. after a timer discontinuity.
+    pub fn restart(&mut self) {
+        self.last_boundary = None;
+    }
+
+    fn push(&mut self, sample: i32) {
+        if self.len == PHASE_SAMPLES_SIZE {
+            self.start = (self.start + 1) % PHASE_SAMPLES_SIZE;
+            self.len -= 1;
+            self.dropped = self.dropped.saturating_add(1);
+        }
+        self.samples[(self.start + self.len) % PHASE_SAMPLES_SIZE] = sample;
+        self.len += 1;
+    }
+
+    /// Move the oldest samples into `buf` and return how many were moved.
+    ///
+    /// # Arguments
+    /// * `buf` - buffer to move the samples into
+    pub fn drain(&mut self, buf: &mut [i32]) -> usize {
+        let count = buf.len().min(self.len);
+        for (idx, sample) in buf[..count].iter_mut().enumerate() {
+            *sample = self.samples[(self.start + idx) % PHASE_SAMPLES_SIZE];
+        }
+        self.start = (self.start + count) % PHASE_SAMPLES_SIZE;
+        self.len -= count;
+        count
+    }
+
+    /// Get the number of samples waiting to be drained.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Return if no samples are waiting to be drained.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Get the number of samples which were overwritten before being drained.
+    pub fn get_dropped(&self) -> u32 {
+        self.dropped
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_boundary() {
+        let mut samples = PhaseSamples::new();
+        samples.add_boundary(1_000_000);
+        assert_eq!(samples.is_empty(), true);
+        samples.add_boundary(2_000_030);
+        samples.add_boundary(2_999_980);
+        samples.add_boundary(5_000_000); // boundaries missed in between
+        samples.restart();
+        samples.add_boundary(9_000_000);
+        let mut buf = [0; 4];
+        assert_eq!(samples.drain(&mut buf), 2);
+        assert_eq!(buf[..2], [30, -50]);
+        assert_eq!(samples.is_empty(), true);
+    }
+    #[test]
+    fn test_drain_overflow() {
+        let mut samples = PhaseSamples::new();
+        for s in 0..PHASE_SAMPLES_SIZE as u32 + 3 {
+            samples.add_boundary(s * 1_000_001);
+        }
+        assert_eq!(samples.len(), PHASE_SAMPLES_SIZE);
+        assert_eq!(samples.get_dropped(), 2);
+        let mut buf = [0; 3];
+        assert_eq!(samples.drain(&mut buf), 3);
+        assert_eq!(buf, [1; 3]);
+        assert_eq!(samples.len(), PHASE_SAMPLES_SIZE - 3);
+    }
+}
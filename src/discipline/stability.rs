@@ -0,0 +1,71 @@
+//! Overlapping Allan deviation of the local timer against MSF, computed from `PhaseSamples`.
+
+use crate::discipline::phase_samples::{PhaseSamples, PHASE_SAMPLES_SIZE};
+
+/// Averaging times in seconds at which `PhaseSamples::allan_deviations()` evaluates the
+/// Allan deviation.
+pub const STABILITY_TAUS: [usize; 5] = [1, 2, 4, 8, 16];
+
+/// Compute the overlapping Allan deviation in parts per 10^12 at an averaging time of `m`
+/// seconds.
+///
+/// Returns None if `m` is 0 or there are not enough samples, at least `2 * m` are needed.
+///
+/// # Arguments
+/// * `samples` - consecutive deviations of the second lengths in microseconds, see
+///   `PhaseSamples`
+/// * `m` - averaging time in seconds
+pub fn overlapping_adev(samples: &[i32], m: usize) -> Option<u64> {
+    let n = samples.len() + 1; // number of phase points
+    if m == 0 || n < 2 * m + 1 {
+        return None;
+    }
+    // integrate the frequency deviations into phase on the fly
+    let phase = |idx: usize| samples[..idx].iter().fold(0i64, |acc, s| acc + *s as i64);
+    let mut sum: u128 = 0;
+    for idx in 0..n - 2 * m {
+        let d = phase(idx + 2 * m) - 2 * phase(idx + m) + phase(idx);
+        sum += (d as i128 * d as i128) as u128;
+    }
+    // microseconds per second are parts per 10^6, scale the variance to parts per 10^12
+    let variance = sum * 1_000_000_000_000 / (2 * (m * m) as u128 * (n - 2 * m) as u128);
+    Some(variance.isqrt() as u64)
+}
+
+impl PhaseSamples {
+    /// Compute the overlapping Allan deviation in parts per 10^12 at each of the
+    /// `STABILITY_TAUS` over the samples waiting to be drained, see `overlapping_adev()`.
+    pub fn allan_deviations(&self) -> [Option<u64>; STABILITY_TAUS.len()] {
+        let mut samples = [0; PHASE_SAMPLES_SIZE];
+        let mut pending = *self;
+        let len = pending.drain(&mut samples);
+        STABILITY_TAUS.map(|m| overlapping_adev(&samples[..len], m))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_overlapping_adev() {
+        assert_eq!(overlapping_adev(&[0; 10], 0), None);
+        assert_eq!(overlapping_adev(&[0; 10], 6), None);
+        // a constant frequency offset does not count
+        assert_eq!(overlapping_adev(&[3; 10], 1), Some(0));
+        // alternating by 2 us: 2e-6 / sqrt(2)
+        assert_eq!(overlapping_adev(&[1, -1, 1, -1, 1], 1), Some(1_414_213));
+        assert_eq!(overlapping_adev(&[1, -1, 1, -1, 1], 2), Some(0));
+    }
+    #[test]
+    fn test_allan_deviations() {
+        let mut samples = PhaseSamples::new();
+        for s in 0..10 {
+            samples.add_boundary(s * 1_000_000 + (s % 2) * 2);
+        }
+        let adev = samples.allan_deviations();
+        assert_eq!(adev[0], Some(2_828_427));
+        assert_eq!(adev[4], None);
+        assert_eq!(samples.len(), 9); // not drained
+    }
+}
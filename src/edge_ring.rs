@@ -0,0 +1,112 @@
+//! Ring of the last raw edges with their classification, to find out what the decoder saw when
+//! a minute fails to decode.
+
+pub use crate::core::EdgeClass;
+
+/// Maximum number of edges kept in the ring.
+pub const EDGE_RING_SIZE: usize = 32;
+
+/// An edge as passed to `MSFUtils::handle_new_edge()` with its classification.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EdgeRecord {
+    pub is_low_edge: bool,
+    pub t: u32,
+    pub class: EdgeClass,
+}
+
+const EMPTY_RECORD: EdgeRecord = EdgeRecord {
+    is_low_edge: false,
+    t: 0,
+    class: EdgeClass::First,
+};
+
+/// Ring buffer holding the last edges, up to a configurable length.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EdgeRing {
+    records: [EdgeRecord; EDGE_RING_SIZE],
+    capacity: usize,
+    next: usize,
+    len: usize,
+}
+
+impl Default for EdgeRing {
+    fn default() -> Self {
+        Self {
+            records: [EMPTY_RECORD; EDGE_RING_SIZE],
+            capacity: 0,
+            next: 0,
+            len: 0,
+        }
+    }
+}
+
+impl EdgeRing {
+    /// Get the number of edges which are kept, 0 if the ring is disabled.
+    pub fn get_capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Set the number of edges to keep and clear the ring, values above `EDGE_RING_SIZE` are
+    /// ignored.
+    ///
+    /// # Arguments
+    /// * `capacity` - number of edges, 0 to disable the ring
+    pub(crate) fn set_capacity(&mut self, capacity: usize) {
+        if capacity <= EDGE_RING_SIZE {
+            self.capacity = capacity;
+            self.next = 0;
+            self.len = 0;
+        }
+    }
+
+    /// Add an edge, replacing the oldest one if the ring is full.
+    pub(crate) fn add(&mut self, is_low_edge: bool, t: u32, class: EdgeClass) {
+        if self.capacity == 0 {
+            return;
+        }
+        self.records[self.next] = EdgeRecord {
+            is_low_edge,
+            t,
+            class,
+        };
+        self.next = (self.next + 1) % self.capacity;
+        self.len = (self.len + 1).min(self.capacity);
+    }
+
+    /// Get the number of kept edges.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Return if no edges are kept.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Iterate over the kept edges, oldest first.
+    pub fn iter(&self) -> impl Iterator<Item = &EdgeRecord> {
+        let capacity = self.capacity.max(1);
+        let start = (self.next + capacity - self.len) % capacity;
+        (0..self.len).map(move |idx| &self.records[(start + idx) % capacity])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_edge_ring() {
+        let mut ring = EdgeRing::default();
+        ring.add(false, 0, EdgeClass::First);
+        assert_eq!(ring.is_empty(), true); // disabled
+        ring.set_capacity(EDGE_RING_SIZE + 1);
+        assert_eq!(ring.get_capacity(), 0);
+        ring.set_capacity(2);
+        for t in 0..3 {
+            ring.add(true, t, EdgeClass::Spike);
+        }
+        assert_eq!(ring.len(), 2);
+        assert_eq!(ring.iter().map(|r| r.t).collect::<Vec<_>>(), [1, 2]);
+    }
+}
@@ -0,0 +1,75 @@
+//! Broadcast time of an instant given as a time stamp, e.g. of a received edge, so clock setting
+//! code does not have to combine the decoded minute with raw time stamps itself.
+
+use crate::core_types::DecodedMinute;
+use crate::MSFUtils;
+
+/// Broadcast time of an instant, see `MSFUtils::time_at()`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EdgeTime {
+    /// The minute containing the instant, in UK civil time.
+    pub minute: DecodedMinute,
+    /// Second within the minute, 60 during a positive leap second.
+    pub second: u8,
+    /// Microseconds within the second.
+    pub micros: u32,
+}
+
+impl MSFUtils {
+    /// Return the broadcast time at the given time stamp, e.g. of an edge passed to
+    /// `handle_new_edge()`.
+    ///
+    /// Returns None if no minute was decoded successfully yet, if no second has started yet, or
+    /// if the time stamp is not within the current or the next minute.
+    ///
+    /// This method must be called _after_ `increase_second()`
+    ///
+    /// # Arguments
+    /// * `at_local_t` - time stamp in the time base of `handle_new_edge()`
+    pub fn time_at(&self, at_local_t: u32) -> Option<EdgeTime> {
+        if self.minutes_since_decode.is_none() || !self.radio_datetime.is_valid() {
+            return None;
+        }
+        let mut offset = self.second as i64 * 1_000_000 + self.elapsed_in_second(at_local_t)?;
+        if offset < 0 {
+            return None;
+        }
+        let mut rdt = self.radio_datetime;
+        let minute_length = self.get_minute_length() as i64 * 1_000_000;
+        if offset >= minute_length {
+            // the next minute is assumed to last 60 seconds
+            offset -= minute_length;
+            if offset >= 60_000_000 || !rdt.add_minute() {
+                return None;
+            }
+        }
+        Some(EdgeTime {
+            minute: DecodedMinute::new(&rdt, self.get_dut1()),
+            second: (offset / 1_000_000) as u8,
+            micros: (offset % 1_000_000) as u32,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::filled_msf;
+
+    #[test]
+    fn test_time_at() {
+        let mut msf = filled_msf();
+        msf.second_start = Some(4_000_000);
+        assert_eq!(msf.time_at(4_000_000), None); // not decoded yet
+        let _ = msf.decode_minute(false);
+        msf.increase_second();
+        msf.second_start = Some(5_000_000);
+        let time = msf.time_at(5_123_456).unwrap();
+        assert_eq!((time.minute.hour, time.minute.minute), (Some(14), Some(58)));
+        assert_eq!((time.second, time.micros), (0, 123_456));
+        let time = msf.time_at(69_000_001).unwrap();
+        assert_eq!((time.minute.hour, time.minute.minute), (Some(14), Some(59)));
+        assert_eq!((time.second, time.micros), (4, 1));
+        assert_eq!(msf.time_at(4_999_999), None); // in the previous minute
+        assert_eq!(msf.time_at(125_000_000), None);
+    }
+}
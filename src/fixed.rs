@@ -0,0 +1,112 @@
+//! Q16.16 fixed-point numbers for the numeric parts of the crate.
+//!
+//! The crate does not use floating point at all (which `test_no_floating_point` verifies), so
+//! it performs predictably on cores without an FPU like the Cortex-M0 and M3.
+
+/// Number of fractional bits.
+const FRAC_BITS: u32 = 16;
+
+/// Signed fixed-point number with 16 integer and 16 fractional bits.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) struct Q16(i32);
+
+impl Q16 {
+    pub(crate) const ZERO: Self = Self(0);
+
+    /// Convert the ratio `num / den` rounded to the nearest value, saturating on overflow.
+    ///
+    /// # Arguments
+    /// * `num` - numerator
+    /// * `den` - denominator, must not be 0
+    pub(crate) fn from_ratio(num: i64, den: i64) -> Self {
+        let (n, d) = ((num as i128) << FRAC_BITS, den as i128);
+        // round half away from zero
+        let scaled = if (n < 0) == (d < 0) {
+            (n + d / 2) / d
+        } else {
+            (n - d / 2) / d
+        };
+        Self(scaled.clamp(i32::MIN as i128, i32::MAX as i128) as i32)
+    }
+
+    /// Multiply by an integer and round the result to the nearest integer.
+    ///
+    /// # Arguments
+    /// * `factor` - the integer to multiply by
+    pub(crate) fn mul_int(self, factor: i64) -> i64 {
+        let product = self.0 as i128 * factor as i128;
+        (((product >> (FRAC_BITS - 1)) + 1) >> 1) as i64
+    }
+
+    /// Move the value towards `sample` by 1/2^shift of the difference, i.e. an exponential
+    /// moving average.
+    ///
+    /// # Arguments
+    /// * `sample` - the new sample
+    /// * `shift` - the weight of the sample is 1/2^shift
+    pub(crate) fn ema(self, sample: Self, shift: u32) -> Self {
+        let delta = (sample.0 as i64 - self.0 as i64) >> shift;
+        Self((self.0 as i64 + delta).clamp(i32::MIN as i64, i32::MAX as i64) as i32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+    use std::string::String;
+    use std::vec::Vec;
+
+    #[test]
+    fn test_from_ratio() {
+        assert_eq!(Q16::from_ratio(3, 2), Q16(0x1_8000));
+        assert_eq!(Q16::from_ratio(-1, 3), Q16(-0x5555));
+        assert_eq!(Q16::from_ratio(1, -3), Q16(-0x5555));
+        assert_eq!(Q16::from_ratio(1 << 20, 1), Q16(i32::MAX)); // saturated
+        assert_eq!(Q16::from_ratio(40_000, 1_000).mul_int(1_000), 40_000);
+        assert_eq!(Q16::from_ratio(-1, 3).mul_int(1_000), -333);
+    }
+    #[test]
+    fn test_ema() {
+        let mut avg = Q16::ZERO;
+        for _ in 0..200 {
+            avg = avg.ema(Q16::from_ratio(7, 1_000), 4);
+        }
+        // an integer moving average with the same weight would be stuck at 0
+        assert_eq!(avg.mul_int(1_000), 7);
+    }
+
+    /// Collect the names of all source files below `dir`.
+    fn source_files(dir: &Path, files: &mut Vec<std::path::PathBuf>) {
+        for entry in std::fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                source_files(&path, files);
+            } else if path.extension().is_some_and(|e| e == "rs") {
+                files.push(path);
+            }
+        }
+    }
+
+    #[test]
+    fn test_no_floating_point() {
+        // built at run time, so this file does not match itself
+        let types: [String; 2] = [["f", "32"].concat(), ["f", "64"].concat()];
+        let mut files = Vec::new();
+        source_files(
+            &Path::new(env!("CARGO_MANIFEST_DIR")).join("src"),
+            &mut files,
+        );
+        for file in files {
+            let source = std::fs::read_to_string(&file).unwrap();
+            for word in source.split(|c: char| !c.is_ascii_alphanumeric() && c != '_') {
+                assert_eq!(
+                    types.iter().any(|t| t == word),
+                    false,
+                    "floating point in {}",
+                    file.display()
+                );
+            }
+        }
+    }
+}
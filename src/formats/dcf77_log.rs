@@ -0,0 +1,152 @@
+//! Conversion of recorded DCF77 minutes into MSF-equivalent decoded minutes.
+//!
+//! This lets applications written against this crate keep working on logs recorded with a DCF77
+//! receiver (e.g. the bit buffers of dcf77_utils). The broadcast time is converted from CET/CEST
+//! to GMT/BST, both are one hour behind, so the DST state carries over unchanged.
+
+use crate::civil_time::days_in_month;
+use crate::formats::packed_minute::PackedMinute;
+use radio_datetime_utils::{radio_datetime_helpers, DST_ANNOUNCED, DST_SUMMER, LEAP_ANNOUNCED};
+
+impl PackedMinute {
+    /// Decode a recorded DCF77 minute and convert it to the equivalent MSF minute.
+    ///
+    /// Returns None if the minute is incomplete or does not pass the DCF77 parity and marker
+    /// checks. DUT1 and the raw-bit CRC are not broadcast by DCF77 and left unknown.
+    ///
+    /// # Arguments
+    /// * `bits` - the 59 bits of the minute (60 with a leap second), bit 0 is the start of the
+    ///   minute
+    pub fn from_dcf77(bits: &[Option<bool>]) -> Option<Self> {
+        if !(59..=60).contains(&bits.len()) || bits[0] != Some(false) || bits[20] != Some(true) {
+            return None;
+        }
+        let cest = bits[17]?;
+        if bits[18]? == cest {
+            return None;
+        }
+        // even parity, so the parity over the data bits and the parity bit must be clear
+        for (start, stop) in [(21, 28), (29, 35), (36, 58)] {
+            if radio_datetime_helpers::get_parity(bits, start, stop, Some(false))? {
+                return None;
+            }
+        }
+        let minute = radio_datetime_helpers::get_bcd_value(bits, 21, 27)?;
+        let mut hour = radio_datetime_helpers::get_bcd_value(bits, 29, 34)?;
+        let mut day = radio_datetime_helpers::get_bcd_value(bits, 36, 41)?;
+        let weekday = radio_datetime_helpers::get_bcd_value(bits, 42, 44)?;
+        let mut month = radio_datetime_helpers::get_bcd_value(bits, 45, 49)?;
+        let mut year = radio_datetime_helpers::get_bcd_value(bits, 50, 57)?;
+        if minute > 59
+            || hour > 23
+            || !(1..=7).contains(&weekday)
+            || !(1..=12).contains(&month)
+            || day == 0
+            || day > days_in_month(year, month)
+        {
+            return None;
+        }
+        // DCF77 counts the weekdays from Monday (1) to Sunday (7), MSF from Sunday (0)
+        let mut weekday = weekday % 7;
+        if hour > 0 {
+            hour -= 1;
+        } else {
+            hour = 23;
+            weekday = (weekday + 6) % 7;
+            if day > 1 {
+                day -= 1;
+            } else {
+                if month > 1 {
+                    month -= 1;
+                } else {
+                    month = 12;
+                    year = (year + 99) % 100;
+                }
+                day = days_in_month(year, month);
+            }
+        }
+        let mut dst = if cest { DST_SUMMER } else { 0 };
+        if bits[16]? {
+            dst |= DST_ANNOUNCED;
+        }
+        Some(Self {
+            year: Some(year),
+            month: Some(month),
+            day: Some(day),
+            weekday: Some(weekday),
+            hour: Some(hour),
+            minute: Some(minute),
+            dst: Some(dst),
+            leap_second: Some(if bits[19]? { LEAP_ANNOUNCED } else { 0 }),
+            dut1: None,
+            minute_crc: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Encode a DCF77 minute, `fields` are minute, hour, day, weekday, month, year.
+    fn dcf77_bits(fields: [u8; 6], cest: bool) -> [Option<bool>; 59] {
+        let mut bits = [Some(false); 59];
+        bits[17] = Some(cest);
+        bits[18] = Some(!cest);
+        bits[20] = Some(true);
+        for ((start, len), value) in [(21, 7), (29, 6), (36, 6), (42, 3), (45, 5), (50, 8)]
+            .iter()
+            .zip(fields)
+        {
+            let bcd = (value / 10) << 4 | (value % 10);
+            for i in 0..*len {
+                bits[start + i] = Some(bcd & (1 << i) != 0);
+            }
+        }
+        for (start, stop) in [(21, 28), (29, 35), (36, 58)] {
+            bits[stop] = Some(
+                bits[start..stop]
+                    .iter()
+                    .filter(|b| **b == Some(true))
+                    .count()
+                    % 2
+                    == 1,
+            );
+        }
+        bits
+    }
+
+    #[test]
+    fn test_from_dcf77() {
+        let bits = dcf77_bits([58, 15, 23, 7, 10, 22], true);
+        let minute = PackedMinute::from_dcf77(&bits).unwrap();
+        assert_eq!(minute.year, Some(22));
+        assert_eq!(minute.month, Some(10));
+        assert_eq!(minute.day, Some(23));
+        assert_eq!(minute.weekday, Some(0));
+        assert_eq!(minute.hour, Some(14));
+        assert_eq!(minute.minute, Some(58));
+        assert_eq!(minute.dst, Some(DST_SUMMER));
+        assert_eq!(minute.dut1, None);
+    }
+    #[test]
+    fn test_from_dcf77_previous_day() {
+        let bits = dcf77_bits([30, 0, 1, 5, 3, 24], false);
+        let minute = PackedMinute::from_dcf77(&bits).unwrap();
+        assert_eq!(minute.month, Some(2));
+        assert_eq!(minute.day, Some(29));
+        assert_eq!(minute.weekday, Some(4));
+        assert_eq!(minute.hour, Some(23));
+        let bits = dcf77_bits([0, 0, 1, 6, 1, 0], false);
+        let minute = PackedMinute::from_dcf77(&bits).unwrap();
+        assert_eq!(minute.year, Some(99));
+        assert_eq!(minute.day, Some(31));
+    }
+    #[test]
+    fn test_from_dcf77_invalid() {
+        let mut bits = dcf77_bits([58, 15, 23, 7, 10, 22], true);
+        bits[30] = Some(!bits[30].unwrap());
+        assert_eq!(PackedMinute::from_dcf77(&bits), None);
+        assert_eq!(PackedMinute::from_dcf77(&bits[..58]), None);
+    }
+}
@@ -0,0 +1,152 @@
+//! Per-second view on a received minute for visualization tools.
+
+use crate::frame_layout::{self, FrameField};
+use crate::MSFUtils;
+use radio_datetime_utils::BIT_BUFFER_SIZE;
+
+/// Meaning and received value of a single second.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SecondView {
+    /// Index of this second in the minute.
+    pub second: u8,
+    /// Field of the A channel, None for a positive leap second.
+    pub field_a: Option<&'static FrameField>,
+    /// Field of the B channel, None for a positive leap second.
+    pub field_b: Option<&'static FrameField>,
+    /// Fixed value of the A bit, if any.
+    pub expected_a: Option<bool>,
+    /// Fixed value of the B bit, if any.
+    pub expected_b: Option<bool>,
+    /// Received A bit.
+    pub bit_a: Option<bool>,
+    /// Received B bit.
+    pub bit_b: Option<bool>,
+    /// Both bits were received and match their fixed values, if any.
+    pub valid: bool,
+}
+
+/// Per-second view of a whole minute.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FrameView {
+    seconds: [SecondView; BIT_BUFFER_SIZE],
+    minute_length: u8,
+}
+
+impl FrameView {
+    /// Build the view from the given A and B bits.
+    ///
+    /// # Arguments
+    /// * `bit_buffer_a` - the A bits of the minute
+    /// * `bit_buffer_b` - the B bits of the minute
+    /// * `minute_length` - length of the minute in seconds, 59 to 61
+    pub fn new(
+        bit_buffer_a: &[Option<bool>],
+        bit_buffer_b: &[Option<bool>],
+        minute_length: u8,
+    ) -> Self {
+        let empty = SecondView {
+            second: 0,
+            field_a: None,
+            field_b: None,
+            expected_a: None,
+            expected_b: None,
+            bit_a: None,
+            bit_b: None,
+            valid: false,
+        };
+        let minute_length = minute_length.min(BIT_BUFFER_SIZE as u8);
+        let mut seconds = [empty; BIT_BUFFER_SIZE];
+        for (idx, view) in seconds.iter_mut().enumerate().take(minute_length as usize) {
+            let second = idx as u8;
+            let nominal = frame_layout::nominal_second(second, minute_length);
+            let field_a = frame_layout::field_a(second, minute_length);
+            let field_b = frame_layout::field_b(second, minute_length);
+            let expected_a = nominal.and_then(|n| field_a.and_then(|f| f.fixed_bit(n)));
+            let expected_b = nominal.and_then(|n| field_b.and_then(|f| f.fixed_bit(n)));
+            let bit_a = bit_buffer_a.get(idx).copied().flatten();
+            let bit_b = bit_buffer_b.get(idx).copied().flatten();
+            *view = SecondView {
+                second,
+                field_a,
+                field_b,
+                expected_a,
+                expected_b,
+                bit_a,
+                bit_b,
+                valid: bit_a.is_some()
+                    && bit_b.is_some()
+                    && (expected_a.is_none() || expected_a == bit_a)
+                    && (expected_b.is_none() || expected_b == bit_b),
+            };
+        }
+        Self {
+            seconds,
+            minute_length,
+        }
+    }
+
+    /// Build the view from the bits currently held by the decoder.
+    ///
+    /// # Arguments
+    /// * `msf` - the decoder to take the bits from
+    pub fn from_msf(msf: &MSFUtils) -> Self {
+        Self::new(
+            &msf.bit_buffer_a,
+            &msf.bit_buffer_b,
+            msf.get_minute_length(),
+        )
+    }
+
+    /// Get the length of the viewed minute in seconds.
+    pub fn get_minute_length(&self) -> u8 {
+        self.minute_length
+    }
+
+    /// Get the views of all seconds in the minute.
+    pub fn seconds(&self) -> &[SecondView] {
+        &self.seconds[..self.minute_length as usize]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frame_view_regular_minute() {
+        let mut bits_a = [Some(false); 60];
+        let bits_b = [Some(false); 60];
+        bits_a[0] = Some(true);
+        bits_a[53..=58].fill(Some(true));
+        let view = FrameView::new(&bits_a, &bits_b, 60);
+        assert_eq!(view.seconds().len(), 60);
+        let s0 = view.seconds()[0];
+        assert_eq!(s0.field_a.unwrap().name, "begin-of-minute marker");
+        assert_eq!(s0.expected_b, Some(true));
+        assert_eq!(s0.valid, false); // B bit should be 1
+        let s20 = view.seconds()[20];
+        assert_eq!(s20.field_a.unwrap().name, "year");
+        assert_eq!(s20.expected_a, None);
+        assert_eq!(s20.valid, true);
+        assert_eq!(view.seconds()[55].valid, true);
+    }
+    #[test]
+    fn test_frame_view_positive_leap_second() {
+        let bits = [None; 61];
+        let view = FrameView::new(&bits, &bits, 61);
+        assert_eq!(view.get_minute_length(), 61);
+        assert_eq!(view.seconds()[17].field_a, None);
+        assert_eq!(view.seconds()[18].field_a.unwrap().name, "year");
+        assert_eq!(view.seconds()[60].expected_a, Some(false));
+        assert_eq!(view.seconds()[60].valid, false); // nothing received
+    }
+    #[test]
+    fn test_frame_view_from_msf() {
+        let mut msf = MSFUtils::default();
+        msf.force_past_new_minute();
+        let view = FrameView::from_msf(&msf);
+        assert_eq!(view.seconds()[0].bit_a, Some(true));
+        assert_eq!(view.seconds()[0].valid, true);
+        assert_eq!(view.seconds()[1].bit_a, None);
+    }
+}
@@ -0,0 +1,129 @@
+//! Home Assistant MQTT discovery, so a receiver publishing with `MqttMapper` shows up in Home
+//! Assistant without manual configuration.
+//!
+//! The entities read the topics of `MqttMapper`:
+//! * `time` - the decoded date/time as a timestamp sensor
+//! * `quality` - the percentage of minutes decoded today
+//! * `dut1` - DUT1 in seconds
+//! * `summer_time` - a binary sensor which is on during BST
+//!
+//! Enabled with the `formats` and `std` features.
+
+use crate::formats::mqtt::{MqttMapper, MqttMessage};
+
+/// Topic prefix under which Home Assistant expects discovery messages by default.
+pub const DISCOVERY_PREFIX: &str = "homeassistant";
+
+/// An entity exposed to Home Assistant.
+struct Entity {
+    component: &'static str,
+    object_id: &'static str,
+    name: &'static str,
+    subtopic: &'static str,
+    template: &'static str,
+    /// Additional JSON members, including a leading comma.
+    extra: &'static str,
+}
+
+const ENTITIES: [Entity; 4] = [
+    Entity {
+        component: "sensor",
+        object_id: "time",
+        name: "Time",
+        subtopic: "minute",
+        template: "{{ '20%02d-%02d-%02dT%02d:%02d:00%s' % (value_json.year, value_json.month, \
+                   value_json.day, value_json.hour, value_json.minute, \
+                   '+01:00' if value_json.summer else '+00:00') }}",
+        extra: ",\"device_class\":\"timestamp\"",
+    },
+    Entity {
+        component: "sensor",
+        object_id: "quality",
+        name: "Signal quality",
+        subtopic: "stats",
+        template: "{{ value_json.quality }}",
+        extra: ",\"unit_of_measurement\":\"%\",\"state_class\":\"measurement\"",
+    },
+    Entity {
+        component: "sensor",
+        object_id: "dut1",
+        name: "DUT1",
+        subtopic: "minute",
+        template: "{{ value_json.dut1 / 10 if value_json.dut1 is not none else none }}",
+        extra: ",\"unit_of_measurement\":\"s\"",
+    },
+    Entity {
+        component: "binary_sensor",
+        object_id: "summer_time",
+        name: "Summer time",
+        subtopic: "minute",
+        template: "{{ 'ON' if value_json.summer else 'OFF' }}",
+        extra: "",
+    },
+];
+
+impl MqttMapper {
+    /// Return the discovery messages of all entities, to be published once after connecting.
+    ///
+    /// The messages are retained, so Home Assistant finds them after a restart as well.
+    ///
+    /// # Arguments
+    /// * `discovery_prefix` - the discovery prefix configured in Home Assistant, usually
+    ///   `DISCOVERY_PREFIX`
+    /// * `node_id` - unique name of this receiver, letters, digits, and underscores only
+    pub fn discovery(&self, discovery_prefix: &str, node_id: &str) -> Vec<MqttMessage> {
+        ENTITIES
+            .iter()
+            .map(|entity| MqttMessage {
+                topic: format!(
+                    "{}/{}/{}/{}/config",
+                    discovery_prefix, entity.component, node_id, entity.object_id
+                ),
+                payload: format!(
+                    "{{\"name\":\"{}\",\"unique_id\":\"{}_{}\",\"state_topic\":\"{}\",\
+                     \"value_template\":\"{}\"{},\"device\":{{\"identifiers\":[\"{}\"],\
+                     \"name\":\"MSF receiver\",\"model\":\"msf60_utils\"}}}}",
+                    entity.name,
+                    node_id,
+                    entity.object_id,
+                    self.topic(entity.subtopic),
+                    entity.template,
+                    entity.extra,
+                    node_id,
+                ),
+                retain: true,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_discovery() {
+        let messages = MqttMapper::default().discovery(DISCOVERY_PREFIX, "msf_attic");
+        assert_eq!(messages.len(), 4);
+        assert_eq!(
+            messages[3],
+            MqttMessage {
+                topic: String::from("homeassistant/binary_sensor/msf_attic/summer_time/config"),
+                payload: String::from(
+                    "{\"name\":\"Summer time\",\"unique_id\":\"msf_attic_summer_time\",\
+                     \"state_topic\":\"msf60/minute\",\
+                     \"value_template\":\"{{ 'ON' if value_json.summer else 'OFF' }}\",\
+                     \"device\":{\"identifiers\":[\"msf_attic\"],\"name\":\"MSF receiver\",\
+                     \"model\":\"msf60_utils\"}}"
+                ),
+                retain: true,
+            }
+        );
+        assert_eq!(
+            messages[1]
+                .payload
+                .contains("\"state_topic\":\"msf60/stats\""),
+            true
+        );
+    }
+}
@@ -0,0 +1,17 @@
+//! Serializers for the decoded data.
+//!
+//! Enabled with the `formats` feature.
+
+pub mod dcf77_log;
+pub mod frame_view;
+#[cfg(feature = "std")]
+pub mod ha_discovery;
+#[cfg(feature = "std")]
+pub mod mqtt;
+pub mod packed_minute;
+#[cfg(feature = "protocol")]
+pub mod protocol;
+pub mod remote_minute;
+pub mod uart_status;
+#[cfg(feature = "net")]
+pub mod udp_publisher;
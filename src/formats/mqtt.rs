@@ -0,0 +1,208 @@
+//! Mapping of the decoder state onto MQTT topics with compact JSON payloads, for integration
+//! into home-automation systems.
+//!
+//! No MQTT client is included, the messages can be published with any of them. All topics
+//! start with a configurable prefix (`msf60` by default):
+//! * `<prefix>/minute` - the last decoded minute, retained:
+//!   `{"year":22,"month":10,"day":23,"weekday":0,"hour":14,"minute":58,"summer":true,
+//!   "dst_announced":false,"leap_announced":false,"dut1":-2,"crc":1234}`
+//! * `<prefix>/status` - the state of the decoder, retained:
+//!   `{"synced":true,"strictness":"relaxed","origin":"radio","minute_length":60}`
+//! * `<prefix>/stats` - reception statistics:
+//!   `{"uptime":120,"since_decode":0,"resyncs":3,"steps":1,"count_0_1":2,"pulse_bias":-1500,
+//!   "quality":98}`, the quality is the percentage of minutes decoded today
+//!
+//! Unknown values are sent as `null`. Enabled with the `formats` and `std` features.
+
+use crate::strictness::Strictness;
+use crate::{MSFUtils, MinuteOrigin};
+use core::fmt::Display;
+use radio_datetime_utils::{DST_ANNOUNCED, DST_SUMMER, LEAP_ANNOUNCED};
+
+/// Topic prefix used by `MqttMapper` unless another one is chosen.
+pub const DEFAULT_PREFIX: &str = "msf60";
+
+/// A message to publish.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MqttMessage {
+    pub topic: String,
+    pub payload: String,
+    /// The broker should keep the message for new subscribers.
+    pub retain: bool,
+}
+
+/// Maps the decoder state onto MQTT messages.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MqttMapper {
+    prefix: String,
+}
+
+impl Default for MqttMapper {
+    fn default() -> Self {
+        Self::new(DEFAULT_PREFIX)
+    }
+}
+
+/// Format an optional value as JSON.
+fn json<T: Display>(value: Option<T>) -> String {
+    value.map_or_else(|| String::from("null"), |v| v.to_string())
+}
+
+/// Format an optional flag of a bit field as JSON.
+fn json_flag(value: Option<u8>, mask: u8) -> String {
+    json(value.map(|v| v & mask != 0))
+}
+
+impl MqttMapper {
+    /// Create a mapper for the given topic prefix.
+    ///
+    /// # Arguments
+    /// * `prefix` - the first level(s) of each topic, without a trailing slash
+    pub fn new(prefix: &str) -> Self {
+        Self {
+            prefix: String::from(prefix),
+        }
+    }
+
+    /// Get the topic prefix.
+    pub fn get_prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    /// Return the full topic of the given subtopic.
+    ///
+    /// # Arguments
+    /// * `subtopic` - `minute`, `status`, or `stats`
+    pub fn topic(&self, subtopic: &str) -> String {
+        format!("{}/{}", self.prefix, subtopic)
+    }
+
+    /// Map the last decoded minute.
+    ///
+    /// This method should be called after a successful `decode_minute()`.
+    ///
+    /// # Arguments
+    /// * `msf` - the decoder
+    pub fn minute(&self, msf: &MSFUtils) -> MqttMessage {
+        let rdt = msf.get_radio_datetime();
+        let dst = rdt.get_dst();
+        MqttMessage {
+            topic: self.topic("minute"),
+            payload: format!(
+                "{{\"year\":{},\"month\":{},\"day\":{},\"weekday\":{},\"hour\":{},\"minute\":{},\
+                 \"summer\":{},\"dst_announced\":{},\"leap_announced\":{},\"dut1\":{},\"crc\":{}}}",
+                json(rdt.get_year()),
+                json(rdt.get_month()),
+                json(rdt.get_day()),
+                json(rdt.get_weekday()),
+                json(rdt.get_hour()),
+                json(rdt.get_minute()),
+                json_flag(dst, DST_SUMMER),
+                json_flag(dst, DST_ANNOUNCED),
+                json_flag(rdt.get_leap_second(), LEAP_ANNOUNCED),
+                json(msf.get_dut1()),
+                json(msf.get_minute_crc()),
+            ),
+            retain: true,
+        }
+    }
+
+    /// Map the state of the decoder, to be published when it changes.
+    ///
+    /// # Arguments
+    /// * `msf` - the decoder
+    pub fn status(&self, msf: &MSFUtils) -> MqttMessage {
+        MqttMessage {
+            topic: self.topic("status"),
+            payload: format!(
+                "{{\"synced\":{},\"strictness\":\"{}\",\"origin\":\"{}\",\"minute_length\":{}}}",
+                !msf.first_minute,
+                match msf.get_strictness() {
+                    Strictness::Relaxed => "relaxed",
+                    Strictness::Strict => "strict",
+                },
+                match msf.get_minute_origin() {
+                    MinuteOrigin::Radio => "radio",
+                    MinuteOrigin::Forced => "forced",
+                },
+                msf.get_minute_length(),
+            ),
+            retain: true,
+        }
+    }
+
+    /// Map the reception statistics.
+    ///
+    /// # Arguments
+    /// * `msf` - the decoder
+    pub fn stats(&self, msf: &MSFUtils) -> MqttMessage {
+        let today = &msf.audit.today;
+        let quality =
+            (today.minutes > 0).then(|| today.decoded_minutes as u32 * 100 / today.minutes as u32);
+        MqttMessage {
+            topic: self.topic("stats"),
+            payload: format!(
+                "{{\"uptime\":{},\"since_decode\":{},\"resyncs\":{},\"steps\":{},\
+                 \"count_0_1\":{},\"pulse_bias\":{},\"quality\":{}}}",
+                msf.uptime_minutes(),
+                json(msf.minutes_since_decode),
+                msf.total_resyncs(),
+                msf.total_steps(),
+                msf.get_count_0_1(),
+                json(msf.get_pulse_bias()),
+                json(quality),
+            ),
+            retain: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::filled_msf;
+
+    #[test]
+    fn test_mqtt_minute() {
+        let mut msf = filled_msf();
+        let _ = msf.decode_minute(false);
+        let mapper = MqttMapper::new("home/clock");
+        let message = mapper.minute(&msf);
+        assert_eq!(message.topic, "home/clock/minute");
+        assert_eq!(message.retain, true);
+        assert_eq!(
+            message.payload.starts_with(
+                "{\"year\":22,\"month\":10,\"day\":23,\"weekday\":6,\"hour\":14,\"minute\":58,\
+                 \"summer\":true,\"dst_announced\":false,\"leap_announced\":null,"
+            ),
+            true
+        );
+        assert_eq!(
+            message
+                .payload
+                .ends_with(&format!(",\"crc\":{}}}", msf.get_minute_crc().unwrap())),
+            true
+        );
+    }
+    #[test]
+    fn test_mqtt_status_stats() {
+        let msf = MSFUtils::default();
+        let mapper = MqttMapper::default();
+        assert_eq!(
+            mapper.status(&msf),
+            MqttMessage {
+                topic: String::from("msf60/status"),
+                payload: String::from(
+                    "{\"synced\":false,\"strictness\":\"relaxed\",\"origin\":\"radio\",\
+                     \"minute_length\":60}"
+                ),
+                retain: true,
+            }
+        );
+        assert_eq!(
+            mapper.stats(&msf).payload,
+            "{\"uptime\":0,\"since_decode\":null,\"resyncs\":0,\"steps\":0,\"count_0_1\":0,\
+             \"pulse_bias\":null,\"quality\":null}"
+        );
+    }
+}
@@ -0,0 +1,147 @@
+//! Compact fixed-size frame holding a decoded minute, to pass it to other devices.
+//!
+//! Layout (all single bytes unless noted, 0xff means unknown):
+//! * 0: magic `b'M'`
+//! * 1: format version
+//! * 2-7: year, month, day, weekday, hour, minute
+//! * 8: DST flags, see `RadioDateTimeUtils::get_dst()`
+//! * 9: leap second flags, see `RadioDateTimeUtils::get_leap_second()`
+//! * 10: DUT1 in deci-seconds as two's complement, 0x80 means unknown
+//! * 11-12: CRC-16 over the raw bits of the minute, big endian
+//! * 13: bit 0 is set if the raw-bit CRC is known
+//! * 14-15: CRC-16 over bytes 0-13, big endian
+
+use crate::msf_helpers;
+use crate::MSFUtils;
+
+/// Size of a packed frame in bytes.
+pub const PACKED_MINUTE_SIZE: usize = 16;
+/// First byte of each frame.
+const MAGIC: u8 = b'M';
+/// Version of the frame layout.
+const VERSION: u8 = 1;
+/// Encoding of an unknown byte value.
+const UNKNOWN: u8 = 0xff;
+/// Encoding of an unknown DUT1.
+const UNKNOWN_DUT1: u8 = 0x80;
+
+/// A decoded minute in a form which can be packed into a frame.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PackedMinute {
+    pub year: Option<u8>,
+    pub month: Option<u8>,
+    pub day: Option<u8>,
+    pub weekday: Option<u8>,
+    pub hour: Option<u8>,
+    pub minute: Option<u8>,
+    pub dst: Option<u8>,
+    pub leap_second: Option<u8>,
+    pub dut1: Option<i8>,
+    pub minute_crc: Option<u16>,
+}
+
+impl PackedMinute {
+    /// Take the last decoded minute from the decoder.
+    ///
+    /// # Arguments
+    /// * `msf` - the decoder
+    pub fn from_msf(msf: &MSFUtils) -> Self {
+        let rdt = msf.get_radio_datetime();
+        Self {
+            year: rdt.get_year(),
+            month: rdt.get_month(),
+            day: rdt.get_day(),
+            weekday: rdt.get_weekday(),
+            hour: rdt.get_hour(),
+            minute: rdt.get_minute(),
+            dst: rdt.get_dst(),
+            leap_second: rdt.get_leap_second(),
+            dut1: msf.get_dut1(),
+            minute_crc: msf.get_minute_crc(),
+        }
+    }
+
+    /// Pack the minute into a frame.
+    pub fn pack(&self) -> [u8; PACKED_MINUTE_SIZE] {
+        let mut frame = [0; PACKED_MINUTE_SIZE];
+        frame[0] = MAGIC;
+        frame[1] = VERSION;
+        for (idx, value) in [
+            self.year,
+            self.month,
+            self.day,
+            self.weekday,
+            self.hour,
+            self.minute,
+            self.dst,
+            self.leap_second,
+        ]
+        .iter()
+        .enumerate()
+        {
+            frame[2 + idx] = value.unwrap_or(UNKNOWN);
+        }
+        frame[10] = self.dut1.map_or(UNKNOWN_DUT1, |d| d as u8);
+        frame[11..13].copy_from_slice(&self.minute_crc.unwrap_or(0).to_be_bytes());
+        frame[13] = self.minute_crc.is_some() as u8;
+        let crc = msf_helpers::get_crc16(frame[..14].iter().copied());
+        frame[14..16].copy_from_slice(&crc.to_be_bytes());
+        frame
+    }
+
+    /// Unpack a frame, returns None if it is not a valid frame.
+    ///
+    /// # Arguments
+    /// * `frame` - the received frame
+    pub fn unpack(frame: &[u8]) -> Option<Self> {
+        if frame.len() != PACKED_MINUTE_SIZE
+            || frame[0] != MAGIC
+            || frame[1] != VERSION
+            || msf_helpers::get_crc16(frame[..14].iter().copied())
+                != u16::from_be_bytes([frame[14], frame[15]])
+        {
+            return None;
+        }
+        let byte = |idx: usize| (frame[idx] != UNKNOWN).then_some(frame[idx]);
+        Some(Self {
+            year: byte(2),
+            month: byte(3),
+            day: byte(4),
+            weekday: byte(5),
+            hour: byte(6),
+            minute: byte(7),
+            dst: byte(8),
+            leap_second: byte(9),
+            dut1: (frame[10] != UNKNOWN_DUT1).then_some(frame[10] as i8),
+            minute_crc: (frame[13] & 1 != 0).then_some(u16::from_be_bytes([frame[11], frame[12]])),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::filled_msf;
+
+    #[test]
+    fn test_pack_unpack() {
+        let mut msf = filled_msf();
+        let _ = msf.decode_minute(false);
+        let minute = PackedMinute::from_msf(&msf);
+        assert_eq!(minute.hour, Some(14));
+        #[cfg(not(feature = "no-dut1"))]
+        assert_eq!(minute.dut1, Some(-2));
+        let frame = minute.pack();
+        assert_eq!(frame[0], b'M');
+        assert_eq!(PackedMinute::unpack(&frame), Some(minute));
+        assert_eq!(PackedMinute::unpack(&frame[..15]), None);
+        let mut corrupted = frame;
+        corrupted[7] ^= 1;
+        assert_eq!(PackedMinute::unpack(&corrupted), None);
+    }
+    #[test]
+    fn test_pack_unknown() {
+        let minute = PackedMinute::default();
+        assert_eq!(PackedMinute::unpack(&minute.pack()), Some(minute));
+    }
+}
@@ -0,0 +1,260 @@
+//! Compact request/response protocol to manage a headless receiver from a host tool.
+//!
+//! Messages are serialized with postcard and COBS-framed, so they can be sent over any byte
+//! pipe (UART, USB CDC, ...) where a zero byte delimits the messages. The receiver passes each
+//! decoded `Request` to `MSFUtils::handle_request()` and sends back the `Response`.
+//!
+//! Enabled with the `protocol` feature.
+
+use crate::api2::Parities;
+use crate::gap_histogram::GAP_HISTOGRAM_SIZE;
+use crate::MSFUtils;
+use serde::{Deserialize, Serialize};
+
+/// Commands sent from the host to the receiver.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Request {
+    /// Get the state of the current second.
+    GetStatus,
+    /// Get the date/time of the last decoded minute.
+    GetLastMinute,
+    /// Get the reception statistics.
+    GetStats,
+    /// Change the configuration of the decoder.
+    Reconfigure(Config),
+}
+
+/// Answers sent from the receiver to the host.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Response {
+    Status(Status),
+    LastMinute(LastMinute),
+    Stats(Stats),
+    /// The configuration after applying `Request::Reconfigure`, out of range values are ignored.
+    Reconfigured(Config),
+}
+
+/// Run-time configuration of the decoder.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Config {
+    pub spike_limit_low: u32,
+    pub spike_limit_high: u32,
+    pub refractory_window: u32,
+    pub pulse_bias_compensation: bool,
+    pub suspect_0_1: bool,
+    pub fixed_minute_length: bool,
+}
+
+/// State of the current second.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Status {
+    pub first_minute: bool,
+    pub new_minute: bool,
+    pub past_new_minute: bool,
+    pub second: u8,
+    pub minute_length: u8,
+    pub bit_a: Option<bool>,
+    pub bit_b: Option<bool>,
+}
+
+/// Date/time and checks of the last decoded minute.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LastMinute {
+    pub year: Option<u8>,
+    pub month: Option<u8>,
+    pub day: Option<u8>,
+    pub weekday: Option<u8>,
+    pub hour: Option<u8>,
+    pub minute: Option<u8>,
+    pub dst: Option<u8>,
+    pub leap_second: Option<u8>,
+    pub dut1: Option<i8>,
+    pub parities: Parities,
+    /// CRC-16 over the raw A and B bits, to deduplicate retransmissions.
+    pub minute_crc: Option<u16>,
+}
+
+/// Reception statistics.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Stats {
+    /// Buckets of the gap histogram, see `GapHistogram`.
+    pub gap_buckets: [u32; GAP_HISTOGRAM_SIZE],
+    /// Minutes since the last successful decode, None if there was none yet.
+    pub minutes_since_decode: Option<u16>,
+    pub count_0_1: u32,
+    pub pulse_bias: Option<i32>,
+}
+
+impl Config {
+    /// Read the configuration from the decoder.
+    ///
+    /// # Arguments
+    /// * `msf` - the decoder
+    pub fn from_msf(msf: &MSFUtils) -> Self {
+        Self {
+            spike_limit_low: msf.get_spike_limit_low(),
+            spike_limit_high: msf.get_spike_limit_high(),
+            refractory_window: msf.get_refractory_window(),
+            pulse_bias_compensation: msf.get_pulse_bias_compensation(),
+            suspect_0_1: msf.get_suspect_0_1(),
+            fixed_minute_length: msf.get_fixed_minute_length(),
+        }
+    }
+
+    /// Apply the configuration to the decoder, out of range values are ignored.
+    ///
+    /// # Arguments
+    /// * `msf` - the decoder
+    pub fn apply(&self, msf: &mut MSFUtils) {
+        msf.set_spike_limit_low(self.spike_limit_low);
+        msf.set_spike_limit_high(self.spike_limit_high);
+        msf.set_refractory_window(self.refractory_window);
+        msf.set_pulse_bias_compensation(self.pulse_bias_compensation);
+        msf.set_suspect_0_1(self.suspect_0_1);
+        msf.set_fixed_minute_length(self.fixed_minute_length);
+    }
+}
+
+impl MSFUtils {
+    /// Execute a request of the host and return the answer.
+    ///
+    /// # Arguments
+    /// * `request` - the request to execute
+    pub fn handle_request(&mut self, request: &Request) -> Response {
+        match request {
+            Request::GetStatus => Response::Status(Status {
+                first_minute: self.first_minute,
+                new_minute: self.new_minute,
+                past_new_minute: self.past_new_minute,
+                second: self.second,
+                minute_length: self.get_minute_length(),
+                bit_a: self.get_current_bit_a(),
+                bit_b: self.get_current_bit_b(),
+            }),
+            Request::GetLastMinute => {
+                let snapshot = self.snapshot();
+                let rdt = snapshot.radio_datetime;
+                Response::LastMinute(LastMinute {
+                    year: rdt.get_year(),
+                    month: rdt.get_month(),
+                    day: rdt.get_day(),
+                    weekday: rdt.get_weekday(),
+                    hour: rdt.get_hour(),
+                    minute: rdt.get_minute(),
+                    dst: rdt.get_dst(),
+                    leap_second: rdt.get_leap_second(),
+                    dut1: snapshot.dut1,
+                    parities: snapshot.parities,
+                    minute_crc: snapshot.minute_crc,
+                })
+            }
+            Request::GetStats => Response::Stats(Stats {
+                gap_buckets: *self.gap_histogram.get_buckets(),
+                minutes_since_decode: self.minutes_since_decode,
+                count_0_1: self.count_0_1,
+                pulse_bias: self.get_pulse_bias(),
+            }),
+            Request::Reconfigure(config) => {
+                config.apply(self);
+                Response::Reconfigured(Config::from_msf(self))
+            }
+        }
+    }
+}
+
+/// Serialize a request into a COBS frame including the terminating zero byte.
+///
+/// Returns the used part of `buf`, or None if it is too small.
+///
+/// # Arguments
+/// * `request` - the request to serialize
+/// * `buf` - buffer to serialize into
+pub fn encode_request<'a>(request: &Request, buf: &'a mut [u8]) -> Option<&'a mut [u8]> {
+    postcard::to_slice_cobs(request, buf).ok()
+}
+
+/// Deserialize a request from a COBS frame, the frame is decoded in place.
+///
+/// # Arguments
+/// * `frame` - the received frame, including the terminating zero byte
+pub fn decode_request(frame: &mut [u8]) -> Option<Request> {
+    postcard::from_bytes_cobs(frame).ok()
+}
+
+/// Serialize a response into a COBS frame including the terminating zero byte.
+///
+/// Returns the used part of `buf`, or None if it is too small.
+///
+/// # Arguments
+/// * `response` - the response to serialize
+/// * `buf` - buffer to serialize into
+pub fn encode_response<'a>(response: &Response, buf: &'a mut [u8]) -> Option<&'a mut [u8]> {
+    postcard::to_slice_cobs(response, buf).ok()
+}
+
+/// Deserialize a response from a COBS frame, the frame is decoded in place.
+///
+/// # Arguments
+/// * `frame` - the received frame, including the terminating zero byte
+pub fn decode_response(frame: &mut [u8]) -> Option<Response> {
+    postcard::from_bytes_cobs(frame).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::filled_msf;
+
+    #[test]
+    fn test_request_roundtrip() {
+        let mut buf = [0u8; 64];
+        let request = Request::Reconfigure(Config::from_msf(&MSFUtils::default()));
+        let frame = encode_request(&request, &mut buf).unwrap();
+        assert_eq!(frame.last(), Some(&0));
+        assert_eq!(frame[..frame.len() - 1].contains(&0), false);
+        assert_eq!(decode_request(frame), Some(request));
+        assert_eq!(encode_request(&request, &mut [0u8; 2]), None);
+    }
+    #[test]
+    fn test_handle_request_last_minute() {
+        let mut msf = filled_msf();
+        let _ = msf.decode_minute(false);
+        let response = msf.handle_request(&Request::GetLastMinute);
+        let mut buf = [0u8; 64];
+        let frame = encode_response(&response, &mut buf).unwrap();
+        match decode_response(frame) {
+            Some(Response::LastMinute(last)) => {
+                assert_eq!(last.year, Some(22));
+                assert_eq!(last.hour, Some(14));
+                assert_eq!(last.minute, Some(58));
+                #[cfg(not(feature = "no-dut1"))]
+                assert_eq!(last.dut1, Some(-2));
+                assert_eq!(last.parities.all_ok(), true);
+                assert_eq!(last.minute_crc, msf.get_minute_crc());
+            }
+            _ => panic!("unexpected response"),
+        }
+    }
+    #[test]
+    fn test_handle_request_reconfigure() {
+        let mut msf = MSFUtils::default();
+        let mut config = Config::from_msf(&msf);
+        config.refractory_window = 5_000;
+        config.spike_limit_low = 1_000_000; // out of range
+        assert_eq!(
+            msf.handle_request(&Request::Reconfigure(config)),
+            Response::Reconfigured(Config {
+                spike_limit_low: msf.get_spike_limit_high(),
+                ..config
+            })
+        );
+        assert_eq!(msf.get_refractory_window(), 5_000);
+        match msf.handle_request(&Request::GetStats) {
+            Response::Stats(stats) => {
+                assert_eq!(stats.minutes_since_decode, None);
+                assert_eq!(stats.pulse_bias, None);
+            }
+            _ => panic!("unexpected response"),
+        }
+    }
+}
@@ -0,0 +1,102 @@
+//! Receive path for packed minute frames, for display-only nodes without a radio.
+
+use crate::formats::packed_minute::PackedMinute;
+use crate::MSFUtils;
+use radio_datetime_utils::{DST_ANNOUNCED, DST_SUMMER};
+
+/// Feeds received packed minutes into a decoder, so the date/time handling (holdover, DST) of
+/// `MSFUtils` is available without a radio.
+#[derive(Default)]
+pub struct RemoteMinuteConsumer {
+    msf: MSFUtils,
+    last_crc: Option<u16>,
+}
+
+impl RemoteMinuteConsumer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the decoder holding the received date/time.
+    pub fn msf(&self) -> &MSFUtils {
+        &self.msf
+    }
+
+    /// Validate a received frame and ingest it as the current minute.
+    ///
+    /// Returns false if the frame is invalid or a retransmission of the previous one, in which
+    /// case nothing changes.
+    ///
+    /// # Arguments
+    /// * `frame` - the received frame
+    pub fn ingest(&mut self, frame: &[u8]) -> bool {
+        let Some(minute) = PackedMinute::unpack(frame) else {
+            return false;
+        };
+        if minute.minute_crc.is_some() && minute.minute_crc == self.last_crc {
+            return false;
+        }
+        self.last_crc = minute.minute_crc;
+        let msf = &mut self.msf;
+        msf.radio_datetime.clear_jumps();
+        let check_jump = !msf.first_minute && msf.radio_datetime.add_minute();
+        msf.radio_datetime.set_year(minute.year, true, check_jump);
+        msf.radio_datetime.set_month(minute.month, true, check_jump);
+        msf.radio_datetime
+            .set_weekday(minute.weekday, true, check_jump);
+        msf.radio_datetime.set_day(minute.day, true, check_jump);
+        msf.radio_datetime.set_hour(minute.hour, true, check_jump);
+        msf.radio_datetime
+            .set_minute(minute.minute, true, check_jump);
+        msf.radio_datetime.set_dst(
+            minute.dst.map(|d| d & DST_SUMMER != 0),
+            minute.dst.map(|d| d & DST_ANNOUNCED != 0),
+            check_jump,
+        );
+        msf.radio_datetime.bump_minutes_running();
+        #[cfg(not(feature = "no-dut1"))]
+        {
+            msf.dut1 = minute.dut1;
+        }
+        msf.minute_crc = minute.minute_crc;
+        if msf.radio_datetime.is_valid() {
+            msf.first_minute = false;
+        }
+        msf.force_new_minute();
+        true
+    }
+
+    /// Advance the date/time by one minute when no frame arrived for a minute.
+    pub fn hold(&mut self) -> bool {
+        self.msf.force_new_minute();
+        self.msf.add_minute()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::filled_msf;
+
+    #[test]
+    fn test_ingest() {
+        let mut msf = filled_msf();
+        let _ = msf.decode_minute(false);
+        let frame = PackedMinute::from_msf(&msf).pack();
+        let mut consumer = RemoteMinuteConsumer::new();
+        assert_eq!(consumer.ingest(&frame[1..]), false);
+        assert_eq!(consumer.msf().snapshot().first_minute, true);
+        assert_eq!(consumer.ingest(&frame), true);
+        assert_eq!(consumer.ingest(&frame), false); // retransmission
+        let rdt = consumer.msf().get_radio_datetime();
+        assert_eq!(consumer.msf().snapshot().first_minute, false);
+        assert_eq!(consumer.msf().get_new_minute(), true);
+        assert_eq!(rdt.get_year(), Some(22));
+        assert_eq!(rdt.get_hour(), Some(14));
+        assert_eq!(rdt.get_minute(), Some(58));
+        #[cfg(not(feature = "no-dut1"))]
+        assert_eq!(consumer.msf().get_dut1(), Some(-2));
+        assert_eq!(consumer.hold(), true);
+        assert_eq!(consumer.msf().get_radio_datetime().get_minute(), Some(59));
+    }
+}
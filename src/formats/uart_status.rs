@@ -0,0 +1,109 @@
+//! Fixed-width ASCII status frame, to be sent once per second over a UART to a display module
+//! which only has to parse fixed positions.
+//!
+//! Layout, unknown digits are sent as `-`:
+//! `$MSF,YYMMDD,W,HHMM,SS,D,QQQ,FF*CS\r\n`
+//! * `YYMMDD` - date
+//! * `W` - weekday
+//! * `HHMM` - hour and minute
+//! * `SS` - second counter
+//! * `D` - `S` for summer time, `W` for winter time
+//! * `QQQ` - percentage of minutes decoded today
+//! * `FF` - flags as two hexadecimal digits:
+//!   * bit 0 - the date/time was decoded at least once
+//!   * bit 1 - the last minute was not decoded, the date/time is propagated
+//!   * bit 2 - a DST change is announced
+//!   * bit 3 - a leap second is announced
+//!   * bit 4 - strict checks are in use
+//! * `CS` - XOR of all bytes between `$` and `*` as two hexadecimal digits, like NMEA 0183
+
+use crate::strictness::Strictness;
+use crate::MSFUtils;
+use radio_datetime_utils::{DST_ANNOUNCED, DST_SUMMER, LEAP_ANNOUNCED};
+
+/// Size of a status frame in bytes.
+pub const STATUS_FRAME_SIZE: usize = 35;
+
+const HEX_DIGITS: &[u8; 16] = b"0123456789ABCDEF";
+
+/// Write `value` as decimal digits at `pos`, or dashes if it is unknown.
+fn put_decimal(frame: &mut [u8], pos: usize, width: usize, value: Option<u32>) {
+    let mut value = value;
+    for idx in (pos..pos + width).rev() {
+        frame[idx] = match value {
+            Some(v) => {
+                value = Some(v / 10);
+                b'0' + (v % 10) as u8
+            }
+            None => b'-',
+        };
+    }
+}
+
+/// Write `value` as two hexadecimal digits at `pos`.
+fn put_hex(frame: &mut [u8], pos: usize, value: u8) {
+    frame[pos] = HEX_DIGITS[(value >> 4) as usize];
+    frame[pos + 1] = HEX_DIGITS[(value & 0xf) as usize];
+}
+
+/// Build the status frame of the current state of the decoder.
+///
+/// This is meant to be called once per second, after `increase_second()`.
+///
+/// # Arguments
+/// * `msf` - the decoder
+pub fn status_frame(msf: &MSFUtils) -> [u8; STATUS_FRAME_SIZE] {
+    let mut frame = *b"$MSF,------,-,----,--,-,---,--*--\r\n";
+    let rdt = msf.get_radio_datetime();
+    let field = |v: Option<u8>| v.map(u32::from);
+    put_decimal(&mut frame, 5, 2, field(rdt.get_year()));
+    put_decimal(&mut frame, 7, 2, field(rdt.get_month()));
+    put_decimal(&mut frame, 9, 2, field(rdt.get_day()));
+    put_decimal(&mut frame, 12, 1, field(rdt.get_weekday()));
+    put_decimal(&mut frame, 14, 2, field(rdt.get_hour()));
+    put_decimal(&mut frame, 16, 2, field(rdt.get_minute()));
+    put_decimal(&mut frame, 19, 2, Some(msf.get_second() as u32));
+    let dst = rdt.get_dst();
+    if let Some(dst) = dst {
+        frame[22] = if dst & DST_SUMMER != 0 { b'S' } else { b'W' };
+    }
+    let today = &msf.audit.today;
+    put_decimal(
+        &mut frame,
+        24,
+        3,
+        (today.minutes > 0).then(|| today.decoded_minutes as u32 * 100 / today.minutes as u32),
+    );
+    let flags = !msf.first_minute as u8
+        | ((msf.minutes_since_decode != Some(0)) as u8) << 1
+        | (dst.is_some_and(|d| d & DST_ANNOUNCED != 0) as u8) << 2
+        | (rdt
+            .get_leap_second()
+            .is_some_and(|l| l & LEAP_ANNOUNCED != 0) as u8)
+            << 3
+        | ((msf.get_strictness() == Strictness::Strict) as u8) << 4;
+    put_hex(&mut frame, 28, flags);
+    let checksum = frame[1..30].iter().fold(0, |acc, b| acc ^ b);
+    put_hex(&mut frame, 31, checksum);
+    frame
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::filled_msf;
+
+    #[test]
+    fn test_status_frame() {
+        assert_eq!(
+            &status_frame(&MSFUtils::default()),
+            b"$MSF,------,-,----,00,-,---,02*5B\r\n"
+        );
+        let mut msf = filled_msf();
+        msf.decode(true);
+        assert_eq!(
+            &status_frame(&msf),
+            b"$MSF,221023,6,1458,59,S,100,01*25\r\n"
+        );
+    }
+}
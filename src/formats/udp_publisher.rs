@@ -0,0 +1,111 @@
+//! Publisher sending each decoded minute as a packed frame over UDP, e.g. as a broadcast to
+//! time-sync other devices on a LAN without an NTP stack.
+//!
+//! Enabled with the `net` feature, uses the `embedded-nal` traits.
+
+use crate::formats::packed_minute::PackedMinute;
+use crate::MSFUtils;
+use core::net::SocketAddr;
+use embedded_nal::UdpClientStack;
+
+/// Port used by `MinutePublisher` unless another one is chosen.
+pub const DEFAULT_PORT: u16 = 6060;
+
+/// Sends packed minutes to a fixed (broadcast) address.
+pub struct MinutePublisher<S: UdpClientStack> {
+    socket: S::UdpSocket,
+}
+
+impl<S: UdpClientStack> MinutePublisher<S> {
+    /// Open a socket sending to the given address.
+    ///
+    /// # Arguments
+    /// * `stack` - the network stack
+    /// * `remote` - the (broadcast) address to send to
+    pub fn new(stack: &mut S, remote: SocketAddr) -> Result<Self, S::Error> {
+        let mut socket = stack.socket()?;
+        stack.connect(&mut socket, remote)?;
+        Ok(Self { socket })
+    }
+
+    /// Send the last decoded minute.
+    ///
+    /// This method should be called after a successful `decode_minute()`.
+    ///
+    /// # Arguments
+    /// * `stack` - the network stack
+    /// * `msf` - the decoder
+    pub fn publish(&mut self, stack: &mut S, msf: &MSFUtils) -> nb::Result<(), S::Error> {
+        stack.send(&mut self.socket, &PackedMinute::from_msf(msf).pack())
+    }
+
+    /// Close the socket.
+    ///
+    /// # Arguments
+    /// * `stack` - the network stack
+    pub fn close(self, stack: &mut S) -> Result<(), S::Error> {
+        stack.close(self.socket)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::formats::packed_minute::PACKED_MINUTE_SIZE;
+    use crate::test_utils::filled_msf;
+    use core::net::{IpAddr, Ipv4Addr};
+
+    #[derive(Default)]
+    struct MockStack {
+        remote: Option<SocketAddr>,
+        sent: Vec<Vec<u8>>,
+        closed: bool,
+    }
+
+    impl UdpClientStack for MockStack {
+        type UdpSocket = ();
+        type Error = ();
+
+        fn socket(&mut self) -> Result<(), ()> {
+            Ok(())
+        }
+        fn connect(&mut self, _socket: &mut (), remote: SocketAddr) -> Result<(), ()> {
+            self.remote = Some(remote);
+            Ok(())
+        }
+        fn send(&mut self, _socket: &mut (), buffer: &[u8]) -> nb::Result<(), ()> {
+            self.sent.push(buffer.to_vec());
+            Ok(())
+        }
+        fn receive(
+            &mut self,
+            _socket: &mut (),
+            _buffer: &mut [u8],
+        ) -> nb::Result<(usize, SocketAddr), ()> {
+            Err(nb::Error::WouldBlock)
+        }
+        fn close(&mut self, _socket: ()) -> Result<(), ()> {
+            self.closed = true;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_publish() {
+        let mut stack = MockStack::default();
+        let remote = SocketAddr::new(IpAddr::V4(Ipv4Addr::BROADCAST), DEFAULT_PORT);
+        let mut publisher = MinutePublisher::new(&mut stack, remote).unwrap();
+        assert_eq!(stack.remote, Some(remote));
+        let mut msf = filled_msf();
+        let _ = msf.decode_minute(false);
+        publisher.publish(&mut stack, &msf).unwrap();
+        assert_eq!(stack.sent.len(), 1);
+        assert_eq!(stack.sent[0].len(), PACKED_MINUTE_SIZE);
+        assert_eq!(
+            PackedMinute::unpack(&stack.sent[0]).unwrap().minute,
+            Some(58)
+        );
+        publisher.close(&mut stack).unwrap();
+        assert_eq!(stack.closed, true);
+    }
+}
@@ -0,0 +1,233 @@
+//! A complete minute of A and B bits, to be decoded without driving an `MSFUtils` instance edge
+//! by edge, e.g. in offline analysis tools, or encoded from a date/time, e.g. for round-trip
+//! tests and signal simulators.
+
+use crate::api2::{DecodeError, Snapshot};
+use crate::frame_layout;
+use crate::MSFUtils;
+use radio_datetime_utils::{RadioDateTimeUtils, BIT_BUFFER_SIZE, DST_SUMMER};
+
+/// What to broadcast besides the date/time, see `MSFFrame::encode()`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EncodeOptions {
+    /// DUT1 in deci-seconds, -8 to 8.
+    pub dut1: i8,
+    /// Summer time is active, None to take it from the date/time.
+    pub summer_time: Option<bool>,
+    /// A change to or from summer time is announced.
+    pub dst_warning: bool,
+    /// Length of the minute in seconds: 59 or 61 for a negative or positive leap second.
+    pub minute_length: u8,
+}
+
+impl Default for EncodeOptions {
+    fn default() -> Self {
+        Self {
+            dut1: 0,
+            summer_time: None,
+            dst_warning: false,
+            minute_length: 60,
+        }
+    }
+}
+
+/// Set `bits[start..=stop]` to the BCD value, most significant bit first.
+fn put_bcd(bits: &mut [bool], start: usize, stop: usize, value: u8) {
+    let bcd = (value / 10) << 4 | (value % 10);
+    for (idx, bit) in bits[start..=stop].iter_mut().rev().enumerate() {
+        *bit = bcd & (1 << idx) != 0;
+    }
+}
+
+/// Return the odd parity bit of `bits[start..=stop]`.
+fn odd_parity(bits: &[bool], start: usize, stop: usize) -> bool {
+    bits[start..=stop].iter().filter(|b| **b).count() % 2 == 0
+}
+
+/// The bits of one minute of 59, 60, or 61 seconds.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MSFFrame {
+    /// A bits, bit 0 is the begin-of-minute marker, None if not received.
+    pub bits_a: [Option<bool>; BIT_BUFFER_SIZE],
+    /// B bits, bit 0 is the begin-of-minute marker, None if not received.
+    pub bits_b: [Option<bool>; BIT_BUFFER_SIZE],
+    /// Length of the minute in seconds, the bits past it are ignored.
+    pub minute_length: u8,
+}
+
+impl Default for MSFFrame {
+    fn default() -> Self {
+        Self {
+            bits_a: [None; BIT_BUFFER_SIZE],
+            bits_b: [None; BIT_BUFFER_SIZE],
+            minute_length: 60,
+        }
+    }
+}
+
+impl MSFFrame {
+    /// Create a frame from completely received bits, None if the slices differ in length or
+    /// are not 59 to 61 bits long.
+    ///
+    /// # Arguments
+    /// * `bits_a` - the A bits of the minute
+    /// * `bits_b` - the B bits of the minute
+    pub fn from_bits(bits_a: &[bool], bits_b: &[bool]) -> Option<Self> {
+        if bits_a.len() != bits_b.len() || !(59..=61).contains(&bits_a.len()) {
+            return None;
+        }
+        let mut frame = Self {
+            minute_length: bits_a.len() as u8,
+            ..Self::default()
+        };
+        for (idx, (&a, &b)) in bits_a.iter().zip(bits_b).enumerate() {
+            frame.bits_a[idx] = Some(a);
+            frame.bits_b[idx] = Some(b);
+        }
+        Some(frame)
+    }
+
+    /// Encode the bits broadcast in the minute before the given date/time, including the
+    /// parities and the end-of-minute marker.
+    ///
+    /// Returns None if a field of the date/time is unknown, or if the options are out of range.
+    ///
+    /// # Arguments
+    /// * `radio_datetime` - the date/time to encode, the weekday counts from 0 for Sunday
+    /// * `options` - DUT1, summer time, and the length of the minute
+    pub fn encode(radio_datetime: &RadioDateTimeUtils, options: &EncodeOptions) -> Option<Self> {
+        if options.dut1.unsigned_abs() > 8 || !(59..=61).contains(&options.minute_length) {
+            return None;
+        }
+        let summer = match options.summer_time {
+            Some(summer) => summer,
+            None => radio_datetime.get_dst()? & DST_SUMMER != 0,
+        };
+        let mut a = [false; 60];
+        let mut b = [false; 60];
+        a[0] = true;
+        b[0] = true;
+        put_bcd(&mut a, 17, 24, radio_datetime.get_year()?);
+        put_bcd(&mut a, 25, 29, radio_datetime.get_month()?);
+        put_bcd(&mut a, 30, 35, radio_datetime.get_day()?);
+        put_bcd(&mut a, 36, 38, radio_datetime.get_weekday()?);
+        put_bcd(&mut a, 39, 44, radio_datetime.get_hour()?);
+        put_bcd(&mut a, 45, 51, radio_datetime.get_minute()?);
+        a[53..59].fill(true);
+        for idx in 0..options.dut1.unsigned_abs() as usize {
+            b[if options.dut1 > 0 { 1 } else { 9 } + idx] = true;
+        }
+        b[53] = options.dst_warning;
+        b[54] = odd_parity(&a, 17, 24);
+        b[55] = odd_parity(&a, 25, 35);
+        b[56] = odd_parity(&a, 36, 38);
+        b[57] = odd_parity(&a, 39, 51);
+        b[58] = summer;
+        let mut frame = Self {
+            minute_length: options.minute_length,
+            ..Self::default()
+        };
+        for second in 0..options.minute_length {
+            // the inserted leap second carries (0,0)
+            let nominal = frame_layout::nominal_second(second, options.minute_length);
+            frame.bits_a[second as usize] = Some(nominal.is_some_and(|n| a[n as usize]));
+            frame.bits_b[second as usize] = Some(nominal.is_some_and(|n| b[n as usize]));
+        }
+        Some(frame)
+    }
+
+    /// Decode the frame, like `MSFUtils::decode_minute()` does at the end of a minute.
+    ///
+    /// Returns the decoded snapshot, or `DecodeError::Incomplete` if the end-of-minute marker
+    /// does not end the frame at its minute length.
+    ///
+    /// # Arguments
+    /// * `strict_checks` - require all parities and DUT1 to be valid
+    pub fn decode(&self, strict_checks: bool) -> Result<Snapshot, DecodeError> {
+        if !(59..=61).contains(&self.minute_length) {
+            return Err(DecodeError::Incomplete);
+        }
+        let length = self.minute_length as usize;
+        let mut msf = MSFUtils::default();
+        msf.bit_buffer_a[..length].copy_from_slice(&self.bits_a[..length]);
+        msf.bit_buffer_b[..length].copy_from_slice(&self.bits_b[..length]);
+        msf.second = self.minute_length - 1;
+        msf.decode_minute(strict_checks)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{filled_msf, BIT_BUFFER_A, BIT_BUFFER_B};
+
+    #[test]
+    fn test_decode() {
+        assert_eq!(
+            MSFFrame::from_bits(&BIT_BUFFER_A, &BIT_BUFFER_B[..59]),
+            None
+        );
+        let frame = MSFFrame::from_bits(&BIT_BUFFER_A, &BIT_BUFFER_B).unwrap();
+        let snapshot = frame.decode(false).ok().unwrap();
+        assert_eq!(snapshot.radio_datetime.get_hour(), Some(14));
+        assert_eq!(snapshot.radio_datetime.get_minute(), Some(58));
+        assert_eq!(snapshot.parities.all_ok(), true);
+        // a positive leap second inserted after second 16
+        let mut a = [false; 61];
+        let mut b = [false; 61];
+        a[..17].copy_from_slice(&BIT_BUFFER_A[..17]);
+        a[18..].copy_from_slice(&BIT_BUFFER_A[17..]);
+        b[..17].copy_from_slice(&BIT_BUFFER_B[..17]);
+        b[18..].copy_from_slice(&BIT_BUFFER_B[17..]);
+        let frame = MSFFrame::from_bits(&a, &b).unwrap();
+        assert_eq!(
+            frame
+                .decode(false)
+                .ok()
+                .unwrap()
+                .radio_datetime
+                .get_minute(),
+            Some(58)
+        );
+        let frame = MSFFrame {
+            minute_length: 60,
+            ..frame
+        };
+        assert_eq!(frame.decode(false).err(), Some(DecodeError::Incomplete));
+    }
+    #[test]
+    fn test_encode() {
+        let mut msf = filled_msf();
+        msf.decode(true);
+        let rdt = msf.get_radio_datetime();
+        let options = EncodeOptions {
+            dut1: -2,
+            ..EncodeOptions::default()
+        };
+        assert_eq!(
+            MSFFrame::encode(&rdt, &options),
+            MSFFrame::from_bits(&BIT_BUFFER_A, &BIT_BUFFER_B)
+        );
+        assert_eq!(
+            MSFFrame::encode(&RadioDateTimeUtils::new(0), &options),
+            None
+        );
+        for minute_length in 59..=61 {
+            let options = EncodeOptions {
+                dut1: 5,
+                dst_warning: true,
+                minute_length,
+                ..EncodeOptions::default()
+            };
+            let snapshot = MSFFrame::encode(&rdt, &options)
+                .unwrap()
+                .decode(true)
+                .ok()
+                .unwrap();
+            assert_eq!(snapshot.minute_length, minute_length);
+            assert_eq!(snapshot.radio_datetime.get_minute(), Some(58));
+            #[cfg(not(feature = "no-dut1"))]
+            assert_eq!(snapshot.dut1, Some(5));
+        }
+    }
+}
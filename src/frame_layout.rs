@@ -0,0 +1,205 @@
+//! Layout of the MSF minute, describing the meaning of each second of the A and B channels.
+//!
+//! The tables use the layout of a regular minute of 60 seconds, `nominal_second()` maps the
+//! seconds of a minute containing a leap second onto it.
+
+/// What a field of the A or B channel carries.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FieldKind {
+    /// Begin-of-minute or end-of-minute marker.
+    Marker,
+    /// Unused bits, always 0.
+    Unused,
+    /// Date or time of day.
+    DateTime,
+    /// DUT1, positive or negative.
+    Dut1,
+    /// Summer time (warning).
+    Dst,
+    /// Parity bit.
+    Parity,
+}
+
+/// A named field of the A or B channel.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FrameField {
+    /// Name of the field.
+    pub name: &'static str,
+    /// What the field carries.
+    pub kind: FieldKind,
+    /// First second of the field.
+    pub start: u8,
+    /// Last second of the field.
+    pub stop: u8,
+    /// Fixed value of the field, the bit of `stop` is the least significant one.
+    pub fixed: Option<u64>,
+}
+
+impl FrameField {
+    /// Return if the given (nominal) second is part of this field.
+    ///
+    /// # Arguments
+    /// * `second` - second in the layout of a 60 second minute
+    pub fn contains(&self, second: u8) -> bool {
+        (self.start..=self.stop).contains(&second)
+    }
+
+    /// Return the fixed value of the bit at the given (nominal) second, if any.
+    ///
+    /// # Arguments
+    /// * `second` - second in the layout of a 60 second minute
+    pub fn fixed_bit(&self, second: u8) -> Option<bool> {
+        if !self.contains(second) {
+            return None;
+        }
+        self.fixed.map(|f| (f >> (self.stop - second)) & 1 == 1)
+    }
+}
+
+const fn field(
+    name: &'static str,
+    kind: FieldKind,
+    start: u8,
+    stop: u8,
+    fixed: Option<u64>,
+) -> FrameField {
+    FrameField {
+        name,
+        kind,
+        start,
+        stop,
+        fixed,
+    }
+}
+
+/// Fields of the A channel.
+pub const FIELDS_A: [FrameField; 9] = [
+    field("begin-of-minute marker", FieldKind::Marker, 0, 0, Some(1)),
+    field("unused", FieldKind::Unused, 1, 16, Some(0)),
+    field("year", FieldKind::DateTime, 17, 24, None),
+    field("month", FieldKind::DateTime, 25, 29, None),
+    field("day", FieldKind::DateTime, 30, 35, None),
+    field("weekday", FieldKind::DateTime, 36, 38, None),
+    field("hour", FieldKind::DateTime, 39, 44, None),
+    field("minute", FieldKind::DateTime, 45, 51, None),
+    field(
+        "end-of-minute marker",
+        FieldKind::Marker,
+        52,
+        59,
+        Some(0b0111_1110),
+    ),
+];
+
+/// Fields of the B channel.
+pub const FIELDS_B: [FrameField; 11] = [
+    field("begin-of-minute marker", FieldKind::Marker, 0, 0, Some(1)),
+    field("DUT1 positive", FieldKind::Dut1, 1, 8, None),
+    field("DUT1 negative", FieldKind::Dut1, 9, 16, None),
+    field("unused", FieldKind::Unused, 17, 52, Some(0)),
+    field("summer time warning", FieldKind::Dst, 53, 53, None),
+    field("year parity", FieldKind::Parity, 54, 54, None),
+    field("month/day parity", FieldKind::Parity, 55, 55, None),
+    field("weekday parity", FieldKind::Parity, 56, 56, None),
+    field("hour/minute parity", FieldKind::Parity, 57, 57, None),
+    field("summer time", FieldKind::Dst, 58, 58, None),
+    field("unused", FieldKind::Unused, 59, 59, Some(0)),
+];
+
+/// Map a second of a minute with the given length to its second in a 60 second minute.
+///
+/// Returns None for the second inserted by a positive leap second and for seconds past the end
+/// of the minute.
+///
+/// # Arguments
+/// * `second` - the second to map
+/// * `minute_length` - length of the minute in seconds, 59 to 61
+pub fn nominal_second(second: u8, minute_length: u8) -> Option<u8> {
+    if second >= minute_length {
+        return None;
+    }
+    match minute_length {
+        // second 16 is dropped in case of a negative leap second
+        59 if second >= 16 => Some(second + 1),
+        // the positive leap second is inserted after second 16
+        61 if second == 17 => None,
+        61 if second > 17 => Some(second - 1),
+        _ => Some(second),
+    }
+}
+
+/// Return the A channel field at the given second, None for a positive leap second.
+///
+/// # Arguments
+/// * `second` - the second to look up
+/// * `minute_length` - length of the minute in seconds, 59 to 61
+pub fn field_a(second: u8, minute_length: u8) -> Option<&'static FrameField> {
+    let nominal = nominal_second(second, minute_length)?;
+    FIELDS_A.iter().find(|f| f.contains(nominal))
+}
+
+/// Return the B channel field at the given second, None for a positive leap second.
+///
+/// # Arguments
+/// * `second` - the second to look up
+/// * `minute_length` - length of the minute in seconds, 59 to 61
+pub fn field_b(second: u8, minute_length: u8) -> Option<&'static FrameField> {
+    let nominal = nominal_second(second, minute_length)?;
+    FIELDS_B.iter().find(|f| f.contains(nominal))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nominal_second_regular() {
+        assert_eq!(nominal_second(0, 60), Some(0));
+        assert_eq!(nominal_second(17, 60), Some(17));
+        assert_eq!(nominal_second(59, 60), Some(59));
+        assert_eq!(nominal_second(60, 60), None);
+    }
+    #[test]
+    fn test_nominal_second_negative_leap_second() {
+        assert_eq!(nominal_second(15, 59), Some(15));
+        assert_eq!(nominal_second(16, 59), Some(17));
+        assert_eq!(nominal_second(58, 59), Some(59));
+        assert_eq!(nominal_second(59, 59), None);
+    }
+    #[test]
+    fn test_nominal_second_positive_leap_second() {
+        assert_eq!(nominal_second(16, 61), Some(16));
+        assert_eq!(nominal_second(17, 61), None);
+        assert_eq!(nominal_second(18, 61), Some(17));
+        assert_eq!(nominal_second(60, 61), Some(59));
+    }
+    #[test]
+    fn test_fields_cover_minute() {
+        for s in 0..60 {
+            assert_eq!(FIELDS_A.iter().filter(|f| f.contains(s)).count(), 1);
+            assert_eq!(FIELDS_B.iter().filter(|f| f.contains(s)).count(), 1);
+        }
+    }
+    #[test]
+    fn test_field_lookup() {
+        assert_eq!(field_a(20, 60).unwrap().name, "year");
+        assert_eq!(field_b(54, 60).unwrap().name, "year parity");
+        assert_eq!(field_b(53, 59).unwrap().name, "year parity");
+        assert_eq!(field_a(17, 61), None);
+        assert_eq!(field_b(58, 60).unwrap().kind, FieldKind::Dst);
+        assert_eq!(field_b(5, 60).unwrap().kind, FieldKind::Dut1);
+        assert_eq!(field_b(57, 60).unwrap().kind, FieldKind::Parity);
+    }
+    #[test]
+    fn test_fixed_bit() {
+        let eom = field_a(52, 60).unwrap();
+        assert_eq!(eom.fixed_bit(52), Some(false));
+        assert_eq!(eom.fixed_bit(53), Some(true));
+        assert_eq!(eom.fixed_bit(58), Some(true));
+        assert_eq!(eom.fixed_bit(59), Some(false));
+        assert_eq!(eom.fixed_bit(51), None); // outside of field
+        assert_eq!(field_a(0, 60).unwrap().fixed_bit(0), Some(true));
+        assert_eq!(field_b(30, 60).unwrap().fixed_bit(30), Some(false));
+        assert_eq!(field_a(45, 60).unwrap().fixed_bit(45), None);
+    }
+}
@@ -0,0 +1,9 @@
+//! Front-ends which feed the decoder, like samplers and HAL adapters.
+//!
+//! Enabled with the `frontends` feature.
+
+#[cfg(feature = "async")]
+pub mod msf_async;
+#[cfg(feature = "hal")]
+pub mod msf_hal;
+pub mod sampler;
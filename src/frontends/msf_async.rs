@@ -0,0 +1,153 @@
+//! Async receiver which awaits the edges of an `embedded-hal-async` input pin, e.g. in Embassy
+//! firmware.
+//!
+//! Enabled with the `async` feature.
+//!
+//! This is the async counterpart of `msf_hal::MsfHal`: instead of polling the pin, the firmware
+//! awaits `next_event()` in a loop, like a stream of events.
+
+use crate::frontends::msf_hal::{self, HalEvent, Monotonic};
+use crate::frontends::sampler::Sampler;
+use crate::MSFUtils;
+use embedded_hal::digital::InputPin;
+use embedded_hal_async::digital::Wait;
+
+/// Decoder driven by the edges of an async input pin and a monotonic clock.
+pub struct MsfAsync<P, C> {
+    pin: P,
+    clock: C,
+    msf: MSFUtils,
+    sampler: Sampler,
+}
+
+impl<P: InputPin + Wait, C: Monotonic> MsfAsync<P, C> {
+    /// Create a receiver.
+    ///
+    /// # Arguments
+    /// * `pin` - the input pin connected to the output of the receiver
+    /// * `clock` - the monotonic clock
+    /// * `msf` - the decoder, e.g. configured using `MSFUtils::builder()`
+    pub fn new(pin: P, clock: C, msf: MSFUtils) -> Self {
+        Self {
+            pin,
+            clock,
+            msf,
+            sampler: Sampler::new(),
+        }
+    }
+
+    /// Get the decoder, e.g. to query the date/time.
+    pub fn msf(&self) -> &MSFUtils {
+        &self.msf
+    }
+
+    /// Give back the pin, the clock, and the decoder.
+    pub fn release(self) -> (P, C, MSFUtils) {
+        (self.pin, self.clock, self.msf)
+    }
+
+    /// Wait for the edges of the pin until one of them causes an event.
+    ///
+    /// Returns the event, or the error of waiting for or reading the pin.
+    pub async fn next_event(&mut self) -> Result<HalEvent, P::Error> {
+        loop {
+            self.pin.wait_for_any_edge().await?;
+            let level = self.pin.is_high()?;
+            let t = self.clock.now_micros();
+            if let Some(event) = msf_hal::drive(&mut self.msf, &mut self.sampler, level, t) {
+                return Ok(event);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::minute_edges;
+    use core::convert::Infallible;
+    use core::future::Future;
+    use core::pin::pin;
+    use core::task::{Context, Poll, Waker};
+    use embedded_hal::digital::ErrorType;
+    use std::cell::Cell;
+    use std::rc::Rc;
+    use std::vec::Vec;
+
+    /// Pin which replays recorded edges, each of them available right away.
+    struct TestPin {
+        edges: Vec<(bool, u32)>,
+        level: bool,
+        now: Rc<Cell<u32>>,
+    }
+
+    impl ErrorType for TestPin {
+        type Error = Infallible;
+    }
+
+    impl InputPin for TestPin {
+        fn is_high(&mut self) -> Result<bool, Infallible> {
+            Ok(self.level)
+        }
+        fn is_low(&mut self) -> Result<bool, Infallible> {
+            Ok(!self.level)
+        }
+    }
+
+    // only wait_for_any_edge() is used by the decoder
+    impl Wait for TestPin {
+        async fn wait_for_high(&mut self) -> Result<(), Infallible> {
+            Ok(())
+        }
+        async fn wait_for_low(&mut self) -> Result<(), Infallible> {
+            Ok(())
+        }
+        async fn wait_for_rising_edge(&mut self) -> Result<(), Infallible> {
+            Ok(())
+        }
+        async fn wait_for_falling_edge(&mut self) -> Result<(), Infallible> {
+            Ok(())
+        }
+        async fn wait_for_any_edge(&mut self) -> Result<(), Infallible> {
+            let (is_low_edge, t) = self.edges.remove(0);
+            self.level = !is_low_edge;
+            self.now.set(t);
+            Ok(())
+        }
+    }
+
+    /// Run a future which never waits to completion.
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = pin!(future);
+        match future
+            .as_mut()
+            .poll(&mut Context::from_waker(Waker::noop()))
+        {
+            Poll::Ready(output) => output,
+            Poll::Pending => panic!("future is pending"),
+        }
+    }
+
+    #[test]
+    fn test_next_event() {
+        let now = Rc::new(Cell::new(0));
+        let clock_now = now.clone();
+        let pin = TestPin {
+            edges: minute_edges(1_000_000),
+            level: false,
+            now,
+        };
+        let mut receiver = MsfAsync::new(pin, move || clock_now.get(), MSFUtils::default());
+        // the first edge only starts the edge tracking, so the first event is the next second
+        assert_eq!(
+            matches!(block_on(receiver.next_event()), Ok(HalEvent::NewSecond(1))),
+            true
+        );
+        let mut events = 1;
+        while !matches!(block_on(receiver.next_event()), Ok(HalEvent::Minute(_))) {
+            events += 1;
+        }
+        assert_eq!(events, 60); // 59 new seconds and the end-of-minute marker
+        assert_eq!(receiver.msf().get_second(), 0);
+    }
+}
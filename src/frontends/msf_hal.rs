@@ -0,0 +1,167 @@
+//! Receiver which owns an `embedded-hal` input pin and a monotonic clock, and drives the decoder
+//! itself.
+//!
+//! Enabled with the `hal` feature.
+//!
+//! The receiver feeds `handle_new_edge()`, decodes each complete minute with the checks chosen by
+//! `decode_minute_auto()`, and calls `increase_second()`, so the firmware only has to handle the
+//! returned events. Call `poll()` from the main loop often enough (see
+//! `sampler::MIN_SAMPLE_RATE`), or from the interrupt handler of the pin.
+
+use crate::api2::{DecodeError, Snapshot};
+use crate::frontends::sampler::Sampler;
+use crate::MSFUtils;
+use embedded_hal::digital::InputPin;
+
+/// Source of monotonic time stamps in microseconds, wrapping at `u32::MAX`.
+pub trait Monotonic {
+    /// Return the current time stamp in microseconds.
+    fn now_micros(&mut self) -> u32;
+}
+
+impl<F: FnMut() -> u32> Monotonic for F {
+    fn now_micros(&mut self) -> u32 {
+        self()
+    }
+}
+
+/// What happened during a call to `MsfHal::poll()`.
+#[derive(Clone, Copy)]
+pub enum HalEvent {
+    /// A new second has started, with the second counter after increasing it.
+    NewSecond(u8),
+    /// The end-of-minute marker has arrived, the minute completes at the next second.
+    NewMinute,
+    /// A minute is complete, with the result of decoding it.
+    Minute(Result<Snapshot, DecodeError>),
+}
+
+/// Decoder driven by an input pin and a monotonic clock.
+pub struct MsfHal<P, C> {
+    pin: P,
+    clock: C,
+    msf: MSFUtils,
+    sampler: Sampler,
+}
+
+impl<P: InputPin, C: Monotonic> MsfHal<P, C> {
+    /// Create a receiver.
+    ///
+    /// # Arguments
+    /// * `pin` - the input pin connected to the output of the receiver
+    /// * `clock` - the monotonic clock
+    /// * `msf` - the decoder, e.g. configured using `MSFUtils::builder()`
+    pub fn new(pin: P, clock: C, msf: MSFUtils) -> Self {
+        Self {
+            pin,
+            clock,
+            msf,
+            sampler: Sampler::new(),
+        }
+    }
+
+    /// Get the decoder, e.g. to query the date/time.
+    pub fn msf(&self) -> &MSFUtils {
+        &self.msf
+    }
+
+    /// Give back the pin, the clock, and the decoder.
+    pub fn release(self) -> (P, C, MSFUtils) {
+        (self.pin, self.clock, self.msf)
+    }
+
+    /// Read the pin and drive the decoder if its level changed.
+    ///
+    /// Returns the event caused by the change, if any, or the error of reading the pin.
+    pub fn poll(&mut self) -> Result<Option<HalEvent>, P::Error> {
+        let level = self.pin.is_high()?;
+        let t = self.clock.now_micros();
+        Ok(drive(&mut self.msf, &mut self.sampler, level, t))
+    }
+}
+
+/// Feed a sample to the decoder and handle a new second, shared by the receivers.
+///
+/// # Arguments
+/// * `msf` - the decoder
+/// * `sampler` - the edge extractor
+/// * `level` - level of the receiver output
+/// * `t` - time stamp of the sample, in microseconds
+pub(crate) fn drive(
+    msf: &mut MSFUtils,
+    sampler: &mut Sampler,
+    level: bool,
+    t: u32,
+) -> Option<HalEvent> {
+    if !sampler.process_sample(msf, level, t) {
+        return None;
+    }
+    if !msf.get_new_second() {
+        return msf.get_new_minute().then_some(HalEvent::NewMinute);
+    }
+    let event = (msf.get_second() + 1 == msf.get_minute_length())
+        .then(|| HalEvent::Minute(msf.decode_minute_auto()));
+    msf.increase_second();
+    event.or(Some(HalEvent::NewSecond(msf.get_second())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::minute_edges;
+    use core::convert::Infallible;
+    use embedded_hal::digital::ErrorType;
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    /// Pin whose level is set by the test.
+    struct TestPin(Rc<Cell<bool>>);
+
+    impl ErrorType for TestPin {
+        type Error = Infallible;
+    }
+
+    impl InputPin for TestPin {
+        fn is_high(&mut self) -> Result<bool, Infallible> {
+            Ok(self.0.get())
+        }
+        fn is_low(&mut self) -> Result<bool, Infallible> {
+            Ok(!self.0.get())
+        }
+    }
+
+    #[test]
+    fn test_poll() {
+        let level = Rc::new(Cell::new(false));
+        let now = Rc::new(Cell::new(0));
+        let clock_now = now.clone();
+        let mut receiver = MsfHal::new(
+            TestPin(level.clone()),
+            move || clock_now.get(),
+            MSFUtils::default(),
+        );
+        assert_eq!(receiver.poll().ok().unwrap().is_none(), true);
+        let (mut seconds, mut markers, mut minutes) = (0, 0, 0);
+        // two minutes, so the first one starts with a complete marker
+        let mut edges = minute_edges(1_000_000);
+        edges.pop();
+        edges.extend(minute_edges(61_000_000));
+        for (is_low_edge, t) in edges {
+            level.set(!is_low_edge);
+            now.set(t);
+            match receiver.poll().ok().unwrap() {
+                Some(HalEvent::NewSecond(_)) => seconds += 1,
+                Some(HalEvent::NewMinute) => markers += 1,
+                Some(HalEvent::Minute(res)) => {
+                    minutes += 1;
+                    if minutes == 2 {
+                        assert_eq!(res.ok().unwrap().radio_datetime.get_minute(), Some(58));
+                    }
+                }
+                None => {}
+            }
+        }
+        assert_eq!((seconds, markers, minutes), (118, 2, 2));
+        assert_eq!(receiver.msf().get_second(), 0);
+    }
+}
@@ -0,0 +1,126 @@
+//! Front-end for platforms which poll the receiver output at a fixed rate instead of using edge
+//! interrupts.
+//!
+//! The sampler extracts the edges from the levels and feeds them to `MSFUtils::handle_new_edge()`,
+//! so the levels follow the signal convention set on the decoder.
+
+use crate::{MSFUtils, ACTIVE_0_LIMIT, ACTIVE_0_NOMINAL};
+
+/// Lowest usable sample rate in Hz, so a 100 ms pulse is measured within its classification
+/// margin.
+pub const MIN_SAMPLE_RATE: u32 = 1_000_000 / (ACTIVE_0_LIMIT - ACTIVE_0_NOMINAL);
+
+/// Edge extractor for sampled receiver output.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Sampler {
+    level: Option<bool>,
+    last_t: Option<u32>,
+    remainder: u32, // accumulated fractions of a sample period, in microseconds * sample rate
+}
+
+impl Sampler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the level of the last sample, None if there was none.
+    pub fn get_level(&self) -> Option<bool> {
+        self.level
+    }
+
+    /// Process a single sample and feed the decoder if the level changed.
+    ///
+    /// Returns if an edge was fed to the decoder, the caller should then check for a new second
+    /// like after calling `handle_new_edge()` directly.
+    ///
+    /// # Arguments
+    /// * `msf` - the decoder to feed
+    /// * `level` - level of the receiver output
+    /// * `t` - time stamp of the sample, in microseconds
+    pub fn process_sample(&mut self, msf: &mut MSFUtils, level: bool, t: u32) -> bool {
+        let edge = self.level.is_some_and(|old| old != level);
+        self.level = Some(level);
+        self.last_t = Some(t);
+        if edge {
+            msf.handle_new_edge(!level, t);
+        }
+        edge
+    }
+
+    /// Process a block of samples taken at a fixed rate, continuing one sample period after the
+    /// last sample (or at 0 for the very first one). Sample rates below `MIN_SAMPLE_RATE` or above
+    /// 1 MHz are ignored.
+    ///
+    /// # Arguments
+    /// * `msf` - the decoder to feed
+    /// * `levels` - levels of the receiver output, oldest first
+    /// * `sample_rate` - sample rate in Hz
+    /// * `on_edge` - called after each edge fed to the decoder, e.g. to check for a new second
+    pub fn process_samples<F: FnMut(&mut MSFUtils)>(
+        &mut self,
+        msf: &mut MSFUtils,
+        levels: &[bool],
+        sample_rate: u32,
+        mut on_edge: F,
+    ) {
+        if !(MIN_SAMPLE_RATE..=1_000_000).contains(&sample_rate) {
+            return;
+        }
+        for &level in levels {
+            let t = match self.last_t {
+                Some(last_t) => {
+                    self.remainder += 1_000_000 % sample_rate;
+                    let step = 1_000_000 / sample_rate + self.remainder / sample_rate;
+                    self.remainder %= sample_rate;
+                    last_t.wrapping_add(step)
+                }
+                None => 0,
+            };
+            if self.process_sample(msf, level, t) {
+                on_edge(msf);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{minute_edges, BIT_BUFFER_A, BIT_BUFFER_B};
+
+    #[test]
+    fn test_process_samples() {
+        const SAMPLE_RATE: u32 = 100;
+        let edges = minute_edges(1_000_000);
+        let levels: Vec<bool> = (0..61_010_000 / (1_000_000 / SAMPLE_RATE))
+            .map(|idx| {
+                let t = idx * (1_000_000 / SAMPLE_RATE);
+                edges
+                    .iter()
+                    .rev()
+                    .find(|&&(_, edge_t)| edge_t <= t)
+                    .is_some_and(|&(is_low_edge, _)| !is_low_edge)
+            })
+            .collect();
+        let mut msf = MSFUtils::default();
+        let mut sampler = Sampler::new();
+        sampler.process_samples(&mut msf, &levels, 1, |_| {});
+        assert_eq!(sampler.get_level(), None); // too slow
+        let mut seconds = 0;
+        sampler.process_samples(&mut msf, &levels, SAMPLE_RATE, |msf| {
+            if msf.get_new_second() {
+                // the first marker is incomplete
+                if seconds > 0 {
+                    assert_eq!(
+                        (msf.get_current_bit_a(), msf.get_current_bit_b()),
+                        (Some(BIT_BUFFER_A[seconds]), Some(BIT_BUFFER_B[seconds]))
+                    );
+                }
+                msf.increase_second();
+                seconds += 1;
+            }
+        });
+        assert_eq!(seconds, 60);
+        assert_eq!(msf.get_second(), 0); // wrapped into the next minute
+    }
+}
@@ -0,0 +1,101 @@
+//! Histogram of the gaps (in minutes) between successfully decoded minutes.
+
+/// Number of buckets in the histogram, the last bucket also counts all longer gaps.
+pub const GAP_HISTOGRAM_SIZE: usize = 16;
+
+/// Distribution of the gaps between successive successful decodes.
+///
+/// Bucket `i` counts gaps of `i + 1` minutes, so a perfectly received signal only fills the
+/// first bucket.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct GapHistogram {
+    buckets: [u32; GAP_HISTOGRAM_SIZE],
+}
+
+impl GapHistogram {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a gap between two successful decodes.
+    ///
+    /// # Arguments
+    /// * `gap` - gap in minutes, 0 is ignored
+    pub fn add_gap(&mut self, gap: u16) {
+        if gap == 0 {
+            return;
+        }
+        let idx = (gap as usize - 1).min(GAP_HISTOGRAM_SIZE - 1);
+        self.buckets[idx] = self.buckets[idx].saturating_add(1);
+    }
+
+    /// Get the number of gaps of the given length, gaps of `GAP_HISTOGRAM_SIZE` minutes also
+    /// include all longer gaps.
+    ///
+    /// # Arguments
+    /// * `gap` - gap in minutes
+    pub fn get_count(&self, gap: u16) -> u32 {
+        if gap == 0 {
+            return 0;
+        }
+        self.buckets[(gap as usize - 1).min(GAP_HISTOGRAM_SIZE - 1)]
+    }
+
+    /// Get all buckets of the histogram.
+    pub fn get_buckets(&self) -> &[u32; GAP_HISTOGRAM_SIZE] {
+        &self.buckets
+    }
+
+    /// Get the total number of recorded gaps, which equals the number of successful decodes
+    /// after the first one.
+    pub fn get_total(&self) -> u32 {
+        self.buckets.iter().fold(0, |acc, b| acc.saturating_add(*b))
+    }
+
+    /// Get the percentage of gaps of just one minute, i.e. of minutes directly following a
+    /// successfully decoded minute which also decoded successfully.
+    pub fn get_consecutive_percentage(&self) -> Option<u8> {
+        let total = self.get_total();
+        if total == 0 {
+            return None;
+        }
+        Some((self.buckets[0] as u64 * 100 / total as u64) as u8)
+    }
+
+    /// Clear all buckets.
+    pub fn reset(&mut self) {
+        self.buckets = [0; GAP_HISTOGRAM_SIZE];
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_gap() {
+        let mut hist = GapHistogram::new();
+        hist.add_gap(0); // ignored
+        hist.add_gap(1);
+        hist.add_gap(1);
+        hist.add_gap(3);
+        hist.add_gap(GAP_HISTOGRAM_SIZE as u16 + 10); // clamped to last bucket
+        assert_eq!(hist.get_count(0), 0);
+        assert_eq!(hist.get_count(1), 2);
+        assert_eq!(hist.get_count(2), 0);
+        assert_eq!(hist.get_count(3), 1);
+        assert_eq!(hist.get_count(GAP_HISTOGRAM_SIZE as u16), 1);
+        assert_eq!(hist.get_count(500), 1);
+        assert_eq!(hist.get_total(), 4);
+        assert_eq!(hist.get_consecutive_percentage(), Some(50));
+    }
+    #[test]
+    fn test_reset() {
+        let mut hist = GapHistogram::new();
+        assert_eq!(hist.get_consecutive_percentage(), None);
+        hist.add_gap(2);
+        hist.reset();
+        assert_eq!(hist.get_total(), 0);
+        assert_eq!(hist.get_buckets(), &[0; GAP_HISTOGRAM_SIZE]);
+    }
+}
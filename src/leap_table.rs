@@ -0,0 +1,120 @@
+//! Table of known leap seconds, for use when MSF minute lengths should not be inferred from the
+//! end-of-minute marker alone.
+//!
+//! MSF does not announce leap seconds in the time code, so the entries have to be provided by the
+//! application, e.g. from IERS Bulletin C.
+
+use crate::upcoming_events::LeapDirection;
+use radio_datetime_utils::RadioDateTimeUtils;
+
+/// A minute with a leap second.
+///
+/// The date/time is the one decoded at the start of that minute, i.e. the one held by the
+/// decoder while the minute containing the leap second is being received.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LeapSecondEntry {
+    pub year: u8,
+    pub month: u8,
+    pub day: u8,
+    pub hour: u8,
+    pub minute: u8,
+    /// Length of the minute in seconds, 59 or 61.
+    pub minute_length: u8,
+}
+
+impl LeapSecondEntry {
+    /// Create an entry for the minute at the given date/time, None if a field of it is unknown
+    /// or the minute length is not 59 or 61.
+    ///
+    /// # Arguments
+    /// * `radio_datetime` - the date/time of the minute
+    /// * `minute_length` - length of the minute in seconds
+    pub fn new(radio_datetime: &RadioDateTimeUtils, minute_length: u8) -> Option<Self> {
+        if minute_length != 59 && minute_length != 61 {
+            return None;
+        }
+        Some(Self {
+            year: radio_datetime.get_year()?,
+            month: radio_datetime.get_month()?,
+            day: radio_datetime.get_day()?,
+            hour: radio_datetime.get_hour()?,
+            minute: radio_datetime.get_minute()?,
+            minute_length,
+        })
+    }
+
+    /// Return if a second was inserted or removed.
+    pub fn direction(&self) -> LeapDirection {
+        if self.minute_length > 60 {
+            LeapDirection::Positive
+        } else {
+            LeapDirection::Negative
+        }
+    }
+}
+
+/// Look up the length of the minute at the given date/time, if it is listed in the table.
+///
+/// # Arguments
+/// * `table` - the leap second table to search
+/// * `radio_datetime` - the date/time of the minute
+pub fn find_minute_length(
+    table: &[LeapSecondEntry],
+    radio_datetime: &RadioDateTimeUtils,
+) -> Option<u8> {
+    let year = radio_datetime.get_year()?;
+    let month = radio_datetime.get_month()?;
+    let day = radio_datetime.get_day()?;
+    let hour = radio_datetime.get_hour()?;
+    let minute = radio_datetime.get_minute()?;
+    table
+        .iter()
+        .find(|e| {
+            e.year == year
+                && e.month == month
+                && e.day == day
+                && e.hour == hour
+                && e.minute == minute
+        })
+        .map(|e| e.minute_length)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::filled_msf;
+
+    const TABLE: [LeapSecondEntry; 2] = [
+        LeapSecondEntry {
+            year: 16,
+            month: 12,
+            day: 31,
+            hour: 23,
+            minute: 59,
+            minute_length: 61,
+        },
+        LeapSecondEntry {
+            year: 22,
+            month: 10,
+            day: 23,
+            hour: 14,
+            minute: 58,
+            minute_length: 59,
+        },
+    ];
+
+    #[test]
+    fn test_find_minute_length() {
+        let mut msf = filled_msf();
+        assert_eq!(find_minute_length(&TABLE, &msf.get_radio_datetime()), None); // nothing decoded yet
+        let _ = msf.decode_minute(false);
+        assert_eq!(
+            find_minute_length(&TABLE, &msf.get_radio_datetime()),
+            Some(59)
+        );
+        assert_eq!(
+            find_minute_length(&TABLE[..1], &msf.get_radio_datetime()),
+            None
+        );
+    }
+}
@@ -1,25 +1,320 @@
 //! Collection of utilities for MSF receivers.
-
+//!
+//! The decoder core (`MSFUtils` and the `core` module) is always built, the other parts are
+//! selected using features:
+//! * `frontends` - samplers and HAL adapters feeding the decoder
+//! * `hal` - receiver driven by an `embedded-hal` input pin, implies `frontends`
+//! * `async` - receiver awaiting the edges of an `embedded-hal-async` input pin, implies `hal`
+//! * `formats` - serializers for the decoded data, implies `statistics`
+//! * `discipline` - phase and drift estimation
+//! * `statistics` - histograms and counters of the reception
+//! * `single-shot` - single-shot sync with `disarm()` and `rearm()`
+//! * `dut1-filter` - DUT1 confirmed over several minutes
+//! * `marker-policy` - arbitration of conflicting minute markers
+//! * `flywheel` - bridging of missed seconds once the second boundaries are locked
+//! * `soft-bits` - likelihoods of the bit pairs besides the hard bits
+//! * `voting` - majority voting of the date and DUT1 bits across minutes
+//! * `protocol` - request/response protocol for remote management, implies `formats` and `serde`
+//! * `net` - publish decoded minutes over UDP, implies `formats`
+//! * `full` - all of the above plus `serde`
+//! * `log` - emit diagnostics through the log crate
+//! * `defmt` - implement `defmt::Format` for diagnostics
+//! * `time-only` - stripped decoder for the hour and minute only
+//! * `serde` - serializable decoder state, to persist it across restarts
+//! * `chrono` - conversions of decoded minutes to chrono date/time types
+//! * `time` - conversions of decoded minutes to time crate date/time types
+//! * `std` - tools for hosts, like comparing decoder configurations on recorded edges or
+//!   replaying them in real time, replaying bit logs, demodulating PCM recordings of the signal,
+//!   and publishing decoded minutes to ntpd or chrony through their SHM driver
+//! * `fault-injection` - `inject_fault()`, `advance_minutes()`, and a noisy signal simulator to
+//!   test the reaction of applications
+//! * `soak` - long-run test of the decoder on a simulated signal, implies `std` and
+//!   `fault-injection`
+//!
+//! The `no-dut1` feature compiles out DUT1 decoding, `get_dut1()` then always returns None and
+//! strict checks no longer require a valid DUT1.
+//!
 //! Build with no_std for embedded platforms.
-#![cfg_attr(not(test), no_std)]
+//!
+//! The crate contains no unsafe code, which is enforced using `forbid(unsafe_code)`, and no
+//! floating point, so it runs predictably on cores without an FPU. Debug builds check the
+//! internal state of the decoder after each edge and second, see
+//! `MSFUtils::set_invariant_checks()` to also do so in release builds.
+#![cfg_attr(not(any(test, feature = "std")), no_std)]
+#![forbid(unsafe_code)]
 
-use core::cmp::Ordering;
+use crate::core::framing::{EdgeFraming, FramedEdge, FramingLimits};
+use crate::core::pulse::Pulse;
+use crate::core::EdgeClass;
+use ::core::cmp::Ordering;
+use ::core::ops::RangeInclusive;
+use adaptive_limits::{ActiveLimits, PulseClusters};
+use api2::{MinuteSecondFlags, PredictedFields, Snapshot};
+#[cfg(feature = "statistics")]
+use audit::{Audit, DailySummary};
+#[cfg(feature = "statistics")]
+use bit_errors::BitErrorRate;
+#[cfg(feature = "statistics")]
+use confidence::ConfidenceScores;
+use core_types::{BitPair, SignalQuality};
+use decoder_config::DecoderConfig;
+use diagnostics::{Diagnostic, Severity};
+#[cfg(feature = "discipline")]
+use discipline::phase_samples::PhaseSamples;
+#[cfg(feature = "statistics")]
+use edge_ring::EdgeRing;
+use fixed::Q16;
+use frame::{EncodeOptions, MSFFrame};
+#[cfg(feature = "statistics")]
+use gap_histogram::GapHistogram;
+use leap_table::LeapSecondEntry;
+use marker_policy::MinuteMarker;
+#[cfg(feature = "marker-policy")]
+use marker_policy::{MarkerPolicy, MarkerVotes};
+use merge_policy::{FieldConfidence, MergePolicy};
+#[cfg(feature = "statistics")]
+use metrics::Metrics;
+#[cfg(feature = "voting")]
+use msf_voting::{BitVoter, VOTING_DEPTH_MAX};
+use observer::MSFObserver;
 use radio_datetime_utils::{radio_datetime_helpers, RadioDateTimeUtils};
+#[cfg(feature = "statistics")]
+use reception_quality::ReceptionQuality;
+use signal_convention::SignalConvention;
+#[cfg(feature = "soft-bits")]
+use soft_bits::BitLikelihoods;
+use spike_tuning::SpikeTuner;
+use strictness::{Escalation, Strictness};
+
+pub mod adaptive_limits;
+pub mod alarms;
+pub mod alignment;
+pub mod api2;
+#[cfg(feature = "statistics")]
+pub mod audit;
+#[cfg(feature = "statistics")]
+pub mod bit_errors;
+#[cfg(feature = "chrono")]
+pub mod chrono_conv;
+pub mod civil_time;
+pub mod compact_state;
+#[cfg(feature = "statistics")]
+pub mod confidence;
+#[cfg(feature = "std")]
+pub mod config_comparison;
+pub mod core;
+pub mod core_types;
+pub mod decoder_config;
+pub mod diagnostics;
+#[cfg(feature = "discipline")]
+pub mod discipline;
+#[cfg(feature = "statistics")]
+pub mod edge_ring;
+pub mod edge_time;
+mod fixed;
+#[cfg(feature = "formats")]
+pub mod formats;
+pub mod frame;
+pub mod frame_layout;
+#[cfg(feature = "frontends")]
+pub mod frontends;
+#[cfg(feature = "statistics")]
+pub mod gap_histogram;
+pub mod leap_table;
+pub mod marker_policy;
+pub mod merge_policy;
+#[cfg(feature = "statistics")]
+pub mod metrics;
+pub mod minute_history;
+#[cfg(feature = "std")]
+pub mod msf_replay;
+#[cfg(feature = "voting")]
+pub mod msf_voting;
+#[cfg(feature = "std")]
+pub mod ntp_shm;
+pub mod observer;
+pub mod ook;
+#[cfg(feature = "std")]
+pub mod pcm;
+pub mod prelude;
+pub mod preset;
+#[cfg(feature = "std")]
+pub mod realtime_replay;
+#[cfg(feature = "statistics")]
+pub mod reception_quality;
+pub mod reference_check;
+pub mod signal_convention;
+pub mod signalling_history;
+#[cfg(any(test, feature = "fault-injection"))]
+pub mod simulator;
+#[cfg(any(test, feature = "soak"))]
+pub mod soak;
+#[cfg(feature = "soft-bits")]
+pub mod soft_bits;
+pub mod spike_tuning;
+#[cfg(feature = "serde")]
+pub mod state;
+pub mod stream;
+pub mod strictness;
+#[cfg(test)]
+mod test_utils;
+pub mod test_vectors;
+#[cfg(feature = "time")]
+pub mod time_conv;
+#[cfg(feature = "time-only")]
+pub mod time_only;
+pub mod transition;
+pub mod upcoming_events;
+
+pub use crate::core::msf_helpers;
 
-pub mod msf_helpers;
+pub use core_types::MinuteLength as MinuteLengthEstimate;
 
 /// Default upper limit for spike detection in microseconds
 const SPIKE_LIMIT: u32 = 30_000;
+/// Nominal length in microseconds of a pulse for bit A being 0
+pub(crate) const ACTIVE_0_NOMINAL: u32 = 100_000;
 /// Maximum time in microseconds for a bit to be considered 0 (0/x cases)
-const ACTIVE_0_LIMIT: u32 = 150_000;
+pub(crate) const ACTIVE_0_LIMIT: u32 = 150_000;
 /// Maximum time in microseconds for bit A to be considered 1
 const ACTIVE_A_LIMIT: u32 = 250_000;
 /// Maximum time in microseconds for bit A and B to te considered 1
 const ACTIVE_AB_LIMIT: u32 = 350_000;
 /// Maximum time in microseconds for a minute marker to be detected
 const MINUTE_LIMIT: u32 = 550_000;
-/// Signal is considered lost after this many microseconds
+/// Default time in microseconds after which the signal is considered lost
 const PASSIVE_RUNAWAY: u32 = 1_500_000;
+/// The timer of the caller is considered to have been paused after this many microseconds
+/// without edges
+const TIMER_DISCONTINUITY: u32 = 60_000_000;
+/// Number of classified pulses needed before the pulse bias is reported
+const PULSE_BIAS_MIN_SAMPLES: u16 = 16;
+/// Number of consecutive seconds starting on time before the flywheel is locked
+#[cfg(feature = "flywheel")]
+const FLYWHEEL_LOCK_SECONDS: u8 = 4;
+/// Maximum deviation in microseconds of a second start from the one predicted by the flywheel
+#[cfg(feature = "flywheel")]
+const FLYWHEEL_TOLERANCE: u32 = 50_000;
+/// Maximum number of missed seconds bridged by the flywheel
+#[cfg(feature = "flywheel")]
+const FLYWHEEL_MAX_MISSED: u32 = 10;
+/// Largest magnitude of DUT1 in deci-seconds which the signal can carry
+const DUT1_LIMIT: u8 = 8;
+/// First second after the DUT1 field, (0,1) bit pairs before it encode DUT1
+const DUT1_END: u8 = 17;
+/// Length of the end-of-minute marker in seconds
+const EOM_MARKER_LENGTH: u8 = 8;
+/// Default number of consecutive seconds without a valid bit pair tolerated by the watchdog
+const WATCHDOG_TOLERANCE: u8 = 5;
+
+/// Computed and received parity bit of a parity group.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ParityBits {
+    /// Parity bit computed from the received A bits, None if any of them is missing.
+    pub computed: Option<bool>,
+    /// Parity bit as received on the B channel.
+    pub received: Option<bool>,
+}
+
+/// Faults which can be injected into the decoder to test the reaction of an application.
+#[cfg(feature = "fault-injection")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FaultKind {
+    /// Ignore the next edge passed to `handle_new_edge()`.
+    DropEdge,
+    /// Invert the A bit of the next bit determined by `handle_new_edge()` or set by
+    /// `set_current_bit_a()`.
+    CorruptBit,
+    /// Let the next call to `increase_second()` keep the second counter as-is.
+    DelaySecond,
+}
+
+/// How the last new minute was detected, see `MSFUtils::get_minute_origin()`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MinuteOrigin {
+    /// Detected from the received signal by `handle_new_edge()`.
+    #[default]
+    Radio,
+    /// Forced by `force_new_minute()` or `force_past_new_minute()`.
+    Forced,
+}
+
+/// DUT1 as broadcast in the last decoded minute, see `MSFUtils::get_dut1_status()`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Dut1Status {
+    /// Not (completely) received, or DUT1 decoding is compiled out.
+    #[default]
+    Unknown,
+    /// A plausible value in deci-seconds, also returned by `get_dut1()`.
+    Valid(i8),
+    /// A value in deci-seconds outside the plausible range, or with both signs set.
+    Suspicious(i8),
+}
+
+/// Progress of the current minute, see `MSFUtils::minute_progress()`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MinuteProgress {
+    /// The second counter.
+    pub second: u8,
+    /// Number of bits needed for decoding which were received so far in this minute.
+    pub received: u8,
+    /// Number of bits needed for decoding in the whole minute.
+    pub required: u8,
+    /// `received` as a percentage of `required`.
+    pub percent: u8,
+}
+
+/// Optional subsystems which were compiled in, see `MSFUtils::capabilities()`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Capabilities {
+    /// Samplers and HAL adapters (`frontends` feature).
+    pub frontends: bool,
+    /// Receiver driven by an `embedded-hal` input pin (`hal` feature).
+    pub hal: bool,
+    /// Receiver awaiting the edges of an `embedded-hal-async` input pin (`async` feature).
+    pub async_receiver: bool,
+    /// Serializers (`formats` feature).
+    pub formats: bool,
+    /// Phase and drift estimation (`discipline` feature).
+    pub discipline: bool,
+    /// Histograms and counters of the reception (`statistics` feature).
+    pub statistics: bool,
+    /// Likelihoods of the bit pairs (`soft-bits` feature).
+    pub soft_bits: bool,
+    /// Single-shot sync with `disarm()` and `rearm()` (`single-shot` feature).
+    pub single_shot: bool,
+    /// DUT1 confirmed over several minutes (`dut1-filter` feature).
+    pub dut1_filter: bool,
+    /// Arbitration of conflicting minute markers (`marker-policy` feature).
+    pub marker_policy: bool,
+    /// Bridging of missed seconds (`flywheel` feature).
+    pub flywheel: bool,
+    /// Majority voting of the bits across minutes (`voting` feature).
+    pub voting: bool,
+    /// Request/response protocol (`protocol` feature).
+    pub protocol: bool,
+    /// UDP publisher (`net` feature).
+    pub net: bool,
+    /// `inject_fault()` and `advance_minutes()` (`fault-injection` feature).
+    pub fault_injection: bool,
+    /// Diagnostics emitted through the log crate (`log` feature).
+    pub log: bool,
+    /// Diagnostics printable using defmt (`defmt` feature).
+    pub defmt: bool,
+    /// Hour/minute-only decoder (`time-only` feature).
+    pub time_only: bool,
+    /// Host tools (`std` feature).
+    pub std: bool,
+    /// Serializable decoder state (`serde` feature).
+    pub serde: bool,
+    /// Conversions to chrono date/time types (`chrono` feature).
+    pub chrono: bool,
+    /// Conversions to time crate date/time types (`time` feature).
+    pub time: bool,
+    /// DUT1 decoding (absent with the `no-dut1` feature).
+    pub dut1: bool,
+}
 
 /// MSF decoder class
 pub struct MSFUtils {
@@ -27,20 +322,123 @@ pub struct MSFUtils {
     new_minute: bool,      // 0111_1110 marker seen
     past_new_minute: bool, // long bit at begin-of-minute seen
     new_second: bool,
+    minute_origin: MinuteOrigin,
+    latched_flags: MinuteSecondFlags,
     second: u8,
     bit_buffer_a: [Option<bool>; radio_datetime_utils::BIT_BUFFER_SIZE],
     bit_buffer_b: [Option<bool>; radio_datetime_utils::BIT_BUFFER_SIZE],
+    #[cfg(feature = "soft-bits")]
+    soft_buffer: [BitLikelihoods; radio_datetime_utils::BIT_BUFFER_SIZE],
     radio_datetime: RadioDateTimeUtils,
     parity_1: Option<bool>,
     parity_2: Option<bool>,
     parity_3: Option<bool>,
     parity_4: Option<bool>,
+    parity_bits: [ParityBits; 4],
+    predictive_fallback: bool,
+    predicted_fields: PredictedFields,
+    #[cfg(not(feature = "no-dut1"))]
     dut1: Option<i8>, // DUT1 in deci-seconds
+    #[cfg(not(feature = "no-dut1"))]
+    dut1_suspicious: Option<i8>,
+    dut1_limit: u8,
+    #[cfg(feature = "dut1-filter")]
+    dut1_confirmations: u8,
+    #[cfg(all(feature = "dut1-filter", not(feature = "no-dut1")))]
+    dut1_candidate: Option<i8>,
+    #[cfg(all(feature = "dut1-filter", not(feature = "no-dut1")))]
+    dut1_candidate_count: u8,
+    #[cfg(all(feature = "dut1-filter", not(feature = "no-dut1")))]
+    dut1_filtered: Option<i8>,
+    #[cfg(feature = "statistics")]
+    gap_histogram: GapHistogram,
+    #[cfg(feature = "statistics")]
+    reception_quality: ReceptionQuality,
+    #[cfg(feature = "statistics")]
+    metrics: Metrics,
+    #[cfg(feature = "statistics")]
+    bit_error_rate: BitErrorRate,
+    minutes_since_decode: Option<u16>,
+    last_decoded: Option<Snapshot>,
+    last_edge_class: Option<EdgeClass>,
+    #[cfg(feature = "single-shot")]
+    sync_streak: u8, // consecutive consistent minutes
+    #[cfg(feature = "single-shot")]
+    sync_snapshot: Option<Snapshot>,
+    #[cfg(feature = "single-shot")]
+    disarmed: bool,
+    minute_crc: Option<u16>,
+    field_confidence: FieldConfidence,
+    #[cfg(feature = "marker-policy")]
+    marker_policy: MarkerPolicy,
+    #[cfg(feature = "marker-policy")]
+    marker_votes: MarkerVotes,
+    #[cfg(feature = "marker-policy")]
+    eom_conflict_second: Option<u8>,
+    strictness: Strictness,
+    strictness_streak: u8,
+    #[cfg(feature = "statistics")]
+    audit: Audit,
     // below for handle_new_edge()
     before_first_edge: bool,
+    timer_discontinuity: bool,
     t0: u32,
     old_t_diff: u32,
-    spike_limit: u32,
+    spike_limit_low: u32,  // for high-to-low edges
+    spike_limit_high: u32, // for low-to-high edges
+    auto_spike_limit: bool,
+    spike_tuner: SpikeTuner,
+    suspect_0_1: bool,
+    count_0_1: u32,
+    a_only: bool,
+    #[cfg(feature = "voting")]
+    voting_depth: u8,
+    #[cfg(feature = "voting")]
+    voter: BitVoter,
+    #[cfg(feature = "voting")]
+    voted_bits: u8,
+    // the received bits after voting, which decode() works on if voting is enabled
+    #[cfg(feature = "voting")]
+    decoded_buffer_a: [Option<bool>; radio_datetime_utils::BIT_BUFFER_SIZE],
+    #[cfg(feature = "voting")]
+    decoded_buffer_b: [Option<bool>; radio_datetime_utils::BIT_BUFFER_SIZE],
+    #[cfg(feature = "statistics")]
+    confidence_scores: ConfidenceScores,
+    #[cfg(feature = "statistics")]
+    edge_ring: EdgeRing,
+    watchdog_tolerance: u8,
+    bad_seconds: u8,
+    valid_second: bool, // a bit pair or minute marker arrived during this second
+    watchdog_kick: bool,
+    decoder_config: DecoderConfig,
+    signal_convention: SignalConvention,
+    prediction_horizon: u8,
+    invariant_checks: bool,
+    diagnostic_hook: Option<fn(Diagnostic)>,
+    observer: Option<&'static dyn MSFObserver>,
+    pulse_bias: Q16, // moving average in milliseconds
+    pulse_bias_count: u16,
+    pulse_clusters: PulseClusters,
+    fixed_minute_length: bool,
+    leap_table: &'static [LeapSecondEntry],
+    leap_second_pending: Option<LeapSecondEntry>, // awaiting confirmation by the next minute
+    leap_second_event: Option<LeapSecondEntry>,
+    last_edge: Option<(bool, u32)>, // (is_low_edge, t) of the last edge not filtered out
+    last_t64: Option<u64>,          // time stamp of the last edge passed to handle_new_edge_u64()
+    t64_offset: u64,                // subtracted from 64-bit time stamps to skip over long gaps
+    second_edge: Option<u32>,       // start of a second not yet counted by increase_second()
+    second_start: Option<u32>,      // start of the second `second` points to
+    minute_marker_t: Option<u32>,   // start of the last begin-of-minute marker
+    minute_marker_expected: Option<u32>, // previous marker plus the length of its minute
+    minute_phase_offset: Option<i32>,
+    #[cfg(feature = "flywheel")]
+    flywheel_lock: u8, // number of consecutive seconds which started on time
+    #[cfg(feature = "flywheel")]
+    missed_seconds: u8, // seconds bridged by the flywheel, for increase_second()
+    #[cfg(feature = "discipline")]
+    phase_samples: PhaseSamples,
+    #[cfg(feature = "fault-injection")]
+    pending_fault: Option<FaultKind>,
 }
 
 impl MSFUtils {
@@ -50,23 +448,163 @@ impl MSFUtils {
             new_minute: false,
             past_new_minute: false,
             new_second: false,
+            minute_origin: MinuteOrigin::Radio,
+            latched_flags: MinuteSecondFlags::default(),
             second: 0,
             bit_buffer_a: [None; radio_datetime_utils::BIT_BUFFER_SIZE],
             bit_buffer_b: [None; radio_datetime_utils::BIT_BUFFER_SIZE],
+            #[cfg(feature = "soft-bits")]
+            soft_buffer: [BitLikelihoods::default(); radio_datetime_utils::BIT_BUFFER_SIZE],
             radio_datetime: RadioDateTimeUtils::new(0),
             parity_1: None,
             parity_2: None,
             parity_3: None,
             parity_4: None,
+            parity_bits: [ParityBits::default(); 4],
+            predictive_fallback: false,
+            predicted_fields: PredictedFields::default(),
+            #[cfg(not(feature = "no-dut1"))]
             dut1: None,
+            #[cfg(not(feature = "no-dut1"))]
+            dut1_suspicious: None,
+            dut1_limit: DUT1_LIMIT,
+            #[cfg(feature = "dut1-filter")]
+            dut1_confirmations: 1,
+            #[cfg(all(feature = "dut1-filter", not(feature = "no-dut1")))]
+            dut1_candidate: None,
+            #[cfg(all(feature = "dut1-filter", not(feature = "no-dut1")))]
+            dut1_candidate_count: 0,
+            #[cfg(all(feature = "dut1-filter", not(feature = "no-dut1")))]
+            dut1_filtered: None,
+            #[cfg(feature = "statistics")]
+            gap_histogram: GapHistogram::new(),
+            #[cfg(feature = "statistics")]
+            reception_quality: ReceptionQuality::new(),
+            #[cfg(feature = "statistics")]
+            metrics: Metrics::new(),
+            #[cfg(feature = "statistics")]
+            bit_error_rate: BitErrorRate::new(),
+            minutes_since_decode: None,
+            last_decoded: None,
+            last_edge_class: None,
+            #[cfg(feature = "single-shot")]
+            sync_streak: 0,
+            #[cfg(feature = "single-shot")]
+            sync_snapshot: None,
+            #[cfg(feature = "single-shot")]
+            disarmed: false,
+            minute_crc: None,
+            field_confidence: FieldConfidence::default(),
+            #[cfg(feature = "marker-policy")]
+            marker_policy: MarkerPolicy::PreferLast,
+            #[cfg(feature = "marker-policy")]
+            marker_votes: MarkerVotes::default(),
+            #[cfg(feature = "marker-policy")]
+            eom_conflict_second: None,
+            strictness: Strictness::Relaxed,
+            strictness_streak: 0,
+            #[cfg(feature = "statistics")]
+            audit: Audit::default(),
             before_first_edge: true,
+            timer_discontinuity: false,
             t0: 0,
             old_t_diff: 0,
-            spike_limit: SPIKE_LIMIT,
+            spike_limit_low: SPIKE_LIMIT,
+            spike_limit_high: SPIKE_LIMIT,
+            auto_spike_limit: false,
+            spike_tuner: SpikeTuner::default(),
+            suspect_0_1: false,
+            count_0_1: 0,
+            a_only: false,
+            #[cfg(feature = "voting")]
+            voting_depth: 0,
+            #[cfg(feature = "voting")]
+            voter: BitVoter::default(),
+            #[cfg(feature = "voting")]
+            voted_bits: 0,
+            #[cfg(feature = "voting")]
+            decoded_buffer_a: [None; radio_datetime_utils::BIT_BUFFER_SIZE],
+            #[cfg(feature = "voting")]
+            decoded_buffer_b: [None; radio_datetime_utils::BIT_BUFFER_SIZE],
+            #[cfg(feature = "statistics")]
+            confidence_scores: ConfidenceScores::default(),
+            #[cfg(feature = "statistics")]
+            edge_ring: EdgeRing::default(),
+            watchdog_tolerance: WATCHDOG_TOLERANCE,
+            bad_seconds: 0,
+            valid_second: false,
+            watchdog_kick: false,
+            decoder_config: DecoderConfig::DEFAULT,
+            signal_convention: SignalConvention::ActiveHigh,
+            prediction_horizon: 1,
+            invariant_checks: false,
+            diagnostic_hook: None,
+            observer: None,
+            pulse_bias: Q16::ZERO,
+            pulse_bias_count: 0,
+            pulse_clusters: PulseClusters::default(),
+            fixed_minute_length: false,
+            leap_table: &[],
+            leap_second_pending: None,
+            leap_second_event: None,
+            last_edge: None,
+            last_t64: None,
+            t64_offset: 0,
+            second_edge: None,
+            second_start: None,
+            minute_marker_t: None,
+            minute_marker_expected: None,
+            minute_phase_offset: None,
+            #[cfg(feature = "flywheel")]
+            flywheel_lock: 0,
+            #[cfg(feature = "flywheel")]
+            missed_seconds: 0,
+            #[cfg(feature = "discipline")]
+            phase_samples: PhaseSamples::new(),
+            #[cfg(feature = "fault-injection")]
+            pending_fault: None,
         }
     }
 
+    /// Return which optional subsystems were compiled in.
+    pub const fn capabilities() -> Capabilities {
+        Capabilities {
+            frontends: cfg!(feature = "frontends"),
+            hal: cfg!(feature = "hal"),
+            async_receiver: cfg!(feature = "async"),
+            formats: cfg!(feature = "formats"),
+            discipline: cfg!(feature = "discipline"),
+            statistics: cfg!(feature = "statistics"),
+            soft_bits: cfg!(feature = "soft-bits"),
+            flywheel: cfg!(feature = "flywheel"),
+            marker_policy: cfg!(feature = "marker-policy"),
+            dut1_filter: cfg!(feature = "dut1-filter"),
+            single_shot: cfg!(feature = "single-shot"),
+            voting: cfg!(feature = "voting"),
+            protocol: cfg!(feature = "protocol"),
+            net: cfg!(feature = "net"),
+            fault_injection: cfg!(feature = "fault-injection"),
+            log: cfg!(feature = "log"),
+            defmt: cfg!(feature = "defmt"),
+            time_only: cfg!(feature = "time-only"),
+            std: cfg!(feature = "std"),
+            serde: cfg!(feature = "serde"),
+            chrono: cfg!(feature = "chrono"),
+            time: cfg!(feature = "time"),
+            dut1: !cfg!(feature = "no-dut1"),
+        }
+    }
+
+    /// Return a builder to configure a new decoder.
+    pub fn builder() -> MSFUtilsBuilder {
+        MSFUtilsBuilder::new()
+    }
+
     /// Return if this is the first minute that is decoded.
+    #[deprecated(
+        since = "0.5.0",
+        note = "use snapshot().first_minute from the api2 module"
+    )]
     pub fn get_first_minute(&self) -> bool {
         self.first_minute
     }
@@ -81,29 +619,103 @@ impl MSFUtils {
         self.past_new_minute
     }
 
+    /// Return if the last new minute was detected from the signal or forced.
+    pub fn get_minute_origin(&self) -> MinuteOrigin {
+        self.minute_origin
+    }
+
     /// Force the arrival of a new minute (0111_1110 version).
     ///
-    /// This could be useful when reading from a log file.
+    /// This could be useful when reading from a log file. The minute is handled like a
+    /// detected one, with `get_minute_origin()` returning `MinuteOrigin::Forced`.
     ///
     /// This method must be called _before_ `increase_second()`
     pub fn force_new_minute(&mut self) {
-        self.new_minute = true;
         self.past_new_minute = false;
+        self.mark_new_minute(MinuteOrigin::Forced);
     }
 
     /// Force the arrival of a new minute (begin-of-minute version).
     ///
-    /// This could be useful when reading from a log file.
+    /// This could be useful when reading from a log file. The minute is handled like a
+    /// detected one, including the diagnostic and resync count if the second counter was not
+    /// at the start of the minute, with `get_minute_origin()` returning `MinuteOrigin::Forced`.
     ///
     /// This method must be called _before_ `increase_second()`
     pub fn force_past_new_minute(&mut self) {
         self.new_minute = false;
+        self.mark_past_new_minute(MinuteOrigin::Forced);
+    }
+
+    /// Helper for handle_new_edge() and force_new_minute()
+    ///
+    /// # Arguments
+    /// * `origin` - how the end-of-minute marker was detected
+    fn mark_new_minute(&mut self, origin: MinuteOrigin) {
+        self.new_minute = true;
+        self.latched_flags.new_minute = true;
+        self.minute_origin = origin;
+        if let Some(observer) = self.observer {
+            observer.on_new_minute();
+        }
+    }
+
+    /// Helper for handle_new_edge(), report a minute marker which conflicts with the second
+    /// counter and return if the marker policy accepts the marker. Without the `marker-policy`
+    /// feature, every marker is accepted.
+    ///
+    /// # Arguments
+    /// * `marker` - the minute marker which arrived
+    /// * `conflict` - the marker disagrees with the second counter
+    fn accept_minute_marker(&mut self, marker: MinuteMarker, conflict: bool) -> bool {
+        if conflict {
+            self.diagnose(Diagnostic::MarkerConflict {
+                second: self.second,
+                marker,
+            });
+        }
+        #[cfg(feature = "marker-policy")]
+        {
+            let confirmed = marker == MinuteMarker::BeginOfMinute
+                && self.eom_conflict_second.map(|second| second + 1) == Some(self.second);
+            self.eom_conflict_second =
+                (conflict && marker == MinuteMarker::EndOfMinute).then_some(self.second);
+            self.marker_policy
+                .arbitrate(&mut self.marker_votes, marker, conflict, confirmed)
+        }
+        #[cfg(not(feature = "marker-policy"))]
+        true
+    }
+
+    /// Helper for handle_new_edge() and force_past_new_minute()
+    ///
+    /// # Arguments
+    /// * `origin` - how the begin-of-minute marker was detected
+    fn mark_past_new_minute(&mut self, origin: MinuteOrigin) {
+        if self.second != 0 {
+            self.diagnose(Diagnostic::Misalignment {
+                second: self.second,
+            });
+            #[cfg(feature = "statistics")]
+            self.audit.add_resync();
+        }
         self.past_new_minute = true;
+        self.latched_flags.past_new_minute = true;
+        self.minute_origin = origin;
         self.second = 0;
         self.bit_buffer_a[0] = Some(true);
         self.bit_buffer_b[0] = Some(true);
     }
 
+    /// Return if the last edge arrived so late that the timer of the caller is assumed to have
+    /// been paused (e.g. by a debugger or a sleep mode), or had its time stamp out of order.
+    ///
+    /// Edge tracking is then restarted as if this was the very first edge, instead of treating
+    /// the gap as a reception error.
+    pub fn get_timer_discontinuity(&self) -> bool {
+        self.timer_discontinuity
+    }
+
     /// Return if a new second has arrived.
     pub fn get_new_second(&self) -> bool {
         self.new_second
@@ -124,6 +736,67 @@ impl MSFUtils {
         self.bit_buffer_b[self.second as usize]
     }
 
+    /// Get the A and B bits of the current second.
+    pub fn get_current_bit_pair(&self) -> BitPair {
+        BitPair {
+            a: self.get_current_bit_a(),
+            b: self.get_current_bit_b(),
+        }
+    }
+
+    /// Get the A bits of the current minute, indexed by second, e.g. to render the raw minute.
+    ///
+    /// Seconds which are not received yet are None, as are the seconds past the end of the
+    /// minute.
+    pub fn get_bit_buffer_a(&self) -> &[Option<bool>] {
+        &self.bit_buffer_a
+    }
+
+    /// Get the B bits of the current minute, indexed by second, see `get_bit_buffer_a()`.
+    pub fn get_bit_buffer_b(&self) -> &[Option<bool>] {
+        &self.bit_buffer_b
+    }
+
+    /// Get the reception quality of the current minute, i.e. how many of the seconds before the
+    /// current one have both bits received.
+    pub fn get_signal_quality(&self) -> SignalQuality {
+        let seconds = self.second as usize;
+        SignalQuality {
+            valid_seconds: (0..seconds)
+                .filter(|&s| self.bit_buffer_a[s].is_some() && self.bit_buffer_b[s].is_some())
+                .count() as u8,
+            seconds: self.second,
+        }
+    }
+
+    /// Get the reception quality of the last few minutes from 0 to 100, e.g. for signal bars.
+    ///
+    /// Each minute is scored when it is decoded, by the share of seconds with received bits
+    /// minus penalties for spikes, runaways, and parity failures. Returns 0 before the first
+    /// minute was decoded.
+    #[cfg(feature = "statistics")]
+    pub fn get_reception_quality(&self) -> u8 {
+        self.reception_quality.get_quality()
+    }
+
+    /// Get the likelihoods of the bit pairs of the current second.
+    #[cfg(feature = "soft-bits")]
+    pub fn get_current_bit_likelihoods(&self) -> BitLikelihoods {
+        self.soft_buffer[self.second as usize]
+    }
+
+    /// Get the likelihoods of the bit pairs of the given second of the current minute, None if
+    /// the second is out of range.
+    ///
+    /// Unlike the bit buffers, these are kept for a (0,1) pair when 0-1 bits are suspect.
+    ///
+    /// # Arguments
+    /// * `second` - the second to get the likelihoods for
+    #[cfg(feature = "soft-bits")]
+    pub fn get_bit_likelihoods(&self, second: u8) -> Option<BitLikelihoods> {
+        self.soft_buffer.get(second as usize).copied()
+    }
+
     /// Set the value of the current A bit and clear the flag indicating arrival of a new minute.
     ///
     /// This could be useful when reading from a log file.
@@ -134,6 +807,10 @@ impl MSFUtils {
     /// * `value` - the value to set the current bit to
     pub fn set_current_bit_a(&mut self, value: Option<bool>) {
         self.bit_buffer_a[self.second as usize] = value;
+        #[cfg(feature = "fault-injection")]
+        if value.is_some() && self.take_fault(FaultKind::CorruptBit) {
+            self.bit_buffer_a[self.second as usize] = value.map(|v| !v);
+        }
         self.new_minute = false;
         self.past_new_minute = false;
     }
@@ -158,332 +835,2181 @@ impl MSFUtils {
     }
 
     /// Get the year parity bit, Some(true) means OK.
+    #[deprecated(since = "0.5.0", note = "use snapshot().parities from the api2 module")]
     pub fn get_parity_1(&self) -> Option<bool> {
         self.parity_1
     }
 
     /// Get the month/day parity bit, Some(true) means OK.
+    #[deprecated(since = "0.5.0", note = "use snapshot().parities from the api2 module")]
     pub fn get_parity_2(&self) -> Option<bool> {
         self.parity_2
     }
 
     /// Get the weekday parity bit, Some(true) means OK.
+    #[deprecated(since = "0.5.0", note = "use snapshot().parities from the api2 module")]
     pub fn get_parity_3(&self) -> Option<bool> {
         self.parity_3
     }
 
     /// Get the hour/minute parity bit, Some(true) means OK.
+    #[deprecated(since = "0.5.0", note = "use snapshot().parities from the api2 module")]
     pub fn get_parity_4(&self) -> Option<bool> {
         self.parity_4
     }
 
-    /// Get the value of DUT1 (UT1 - UTC) in deci-seconds.
+    /// Get the computed and received year parity bits.
+    pub fn get_parity_bits_1(&self) -> ParityBits {
+        self.parity_bits[0]
+    }
+
+    /// Get the computed and received month/day parity bits.
+    pub fn get_parity_bits_2(&self) -> ParityBits {
+        self.parity_bits[1]
+    }
+
+    /// Get the computed and received weekday parity bits.
+    pub fn get_parity_bits_3(&self) -> ParityBits {
+        self.parity_bits[2]
+    }
+
+    /// Get the computed and received hour/minute parity bits.
+    pub fn get_parity_bits_4(&self) -> ParityBits {
+        self.parity_bits[3]
+    }
+
+    /// Get the value of DUT1 (UT1 - UTC) in deci-seconds, always None with the `no-dut1`
+    /// feature.
     pub fn get_dut1(&self) -> Option<i8> {
-        self.dut1
+        #[cfg(not(feature = "no-dut1"))]
+        return self.dut1;
+        #[cfg(feature = "no-dut1")]
+        None
     }
 
-    /// Return the current spike limit in microseconds.
-    pub fn get_spike_limit(&self) -> u32 {
-        self.spike_limit
+    /// Get the value of DUT1 (UT1 - UTC) of the last decoded minute including implausible values,
+    /// so monitoring applications can see what was broadcast.
+    pub fn get_dut1_status(&self) -> Dut1Status {
+        #[cfg(not(feature = "no-dut1"))]
+        return match (self.dut1, self.dut1_suspicious) {
+            (Some(dut1), _) => Dut1Status::Valid(dut1),
+            (None, Some(dut1)) => Dut1Status::Suspicious(dut1),
+            (None, None) => Dut1Status::Unknown,
+        };
+        #[cfg(feature = "no-dut1")]
+        Dut1Status::Unknown
+    }
+
+    /// Get the largest magnitude of DUT1 in deci-seconds which is considered plausible.
+    pub fn get_dut1_limit(&self) -> u8 {
+        self.dut1_limit
     }
 
-    /// Set the new spike limit in microseconds, [0(off)..ACTIVE_0_LIMIT)
+    /// Set the largest magnitude of DUT1 in deci-seconds which is considered plausible, values
+    /// above 8 (the most the signal can carry) are ignored.
+    ///
+    /// Larger values are not returned by `get_dut1()` and fail strict checks, but are still
+    /// reported by `get_dut1_status()`. The IERS keeps |DUT1| below 0.9 s, but the limit can be
+    /// tightened to what is plausible in the current era.
     ///
     /// # Arguments
-    /// * `value` - the value to set the spike limit to.
-    pub fn set_spike_limit(&mut self, value: u32) {
-        if value < ACTIVE_0_LIMIT {
-            self.spike_limit = value;
+    /// * `limit` - largest magnitude in deci-seconds
+    pub fn set_dut1_limit(&mut self, limit: u8) {
+        if limit <= DUT1_LIMIT {
+            self.dut1_limit = limit;
         }
     }
 
-    /// Determine the bit value if a new edge is received. indicates reception errors,
-    /// and checks if a new minute has started.
-    ///
-    /// This function can deal with spikes, which are arbitrarily set to `spike_limit` microseconds.
+    /// Get the value of DUT1 (UT1 - UTC) in deci-seconds which was last received in
+    /// `get_dut1_confirmations()` decoded minutes in a row, always None with the `no-dut1` feature.
     ///
-    /// This method must be called _before_ `increase_second()`.
+    /// Unlike `get_dut1()`, this value does not follow a single corrupted minute. Minutes without
+    /// a valid DUT1 do not break the row.
+    #[cfg(feature = "dut1-filter")]
+    pub fn get_filtered_dut1(&self) -> Option<i8> {
+        #[cfg(not(feature = "no-dut1"))]
+        return self.dut1_filtered;
+        #[cfg(feature = "no-dut1")]
+        None
+    }
+
+    /// Get the number of minutes in a row in which a new DUT1 value must be received before it
+    /// is returned by `get_filtered_dut1()`.
+    #[cfg(feature = "dut1-filter")]
+    pub fn get_dut1_confirmations(&self) -> u8 {
+        self.dut1_confirmations
+    }
+
+    /// Set the number of minutes in a row in which a new DUT1 value must be received before it
+    /// is returned by `get_filtered_dut1()`, 0 is ignored.
     ///
     /// # Arguments
-    /// * `is_low_edge` - indicates that the edge has gone from high to low (as opposed to
-    ///                   low-to-high).
-    /// * `t` - time stamp of the received edge, in microseconds
-    pub fn handle_new_edge(&mut self, is_low_edge: bool, t: u32) {
-        if self.before_first_edge {
-            self.before_first_edge = false;
-            self.t0 = t;
-            return;
-        }
-        let t_diff = radio_datetime_helpers::time_diff(self.t0, t);
-        if t_diff < self.spike_limit {
-            // Shift t0 to deal with a train of spikes adding up to more than `spike_limit` microseconds.
-            self.t0 += t_diff;
-            return; // random positive or negative spike, ignore
-        }
-        self.new_minute = false;
-        self.past_new_minute = false;
-        self.t0 = t;
-        if is_low_edge {
-            self.new_second = false;
-            if t_diff < ACTIVE_0_LIMIT {
-                if self.old_t_diff > 0 && self.old_t_diff < ACTIVE_0_LIMIT {
-                    self.bit_buffer_a[self.second as usize] = Some(false);
-                    self.bit_buffer_b[self.second as usize] = Some(true);
-                } else if self.old_t_diff > 1_000_000 - MINUTE_LIMIT {
-                    self.bit_buffer_a[self.second as usize] = Some(false);
-                    self.bit_buffer_b[self.second as usize] = Some(false);
-                }
-                self.new_minute = self.end_of_minute_marker_present();
-            } else if t_diff < ACTIVE_A_LIMIT && self.old_t_diff > 1_000_000 - ACTIVE_AB_LIMIT {
-                self.bit_buffer_a[self.second as usize] = Some(true);
-                self.bit_buffer_b[self.second as usize] = Some(false);
-            } else if t_diff < ACTIVE_AB_LIMIT && self.old_t_diff > 1_000_000 - ACTIVE_AB_LIMIT {
-                self.bit_buffer_a[self.second as usize] = Some(true);
-                self.bit_buffer_b[self.second as usize] = Some(true);
-            } else if t_diff < MINUTE_LIMIT && self.old_t_diff > 1_000_000 - ACTIVE_AB_LIMIT {
-                self.past_new_minute = true;
-                self.second = 0;
-                self.bit_buffer_a[0] = Some(true);
-                self.bit_buffer_b[0] = Some(true);
-            } else {
-                // active runaway or first low edge
-                self.bit_buffer_a[self.second as usize] = None;
-                self.bit_buffer_b[self.second as usize] = None;
-            }
-        } else if t_diff < PASSIVE_RUNAWAY {
-            self.new_second = t_diff > 1_000_000 - MINUTE_LIMIT;
-        } else {
-            self.bit_buffer_a[self.second as usize] = None;
-            self.bit_buffer_b[self.second as usize] = None;
+    /// * `value` - the number of minutes, 1 follows every valid DUT1 value
+    #[cfg(feature = "dut1-filter")]
+    pub fn set_dut1_confirmations(&mut self, value: u8) {
+        if value > 0 {
+            self.dut1_confirmations = value;
         }
-        self.old_t_diff = t_diff;
     }
 
-    /// Determine the length of this minute in seconds.
-    pub fn get_minute_length(&self) -> u8 {
-        if (58..=60).contains(&self.second) && self.search_eom_marker(false) {
-            self.second + 1
-        } else if self.second == 59 && self.search_eom_marker(true) {
-            61
+    /// Count the DUT1 value of the minute just decoded towards the filtered value.
+    #[cfg(all(feature = "dut1-filter", not(feature = "no-dut1")))]
+    fn filter_dut1(&mut self) {
+        let Some(dut1) = self.dut1 else {
+            return;
+        };
+        if self.dut1_candidate == Some(dut1) {
+            self.dut1_candidate_count = self.dut1_candidate_count.saturating_add(1);
         } else {
-            60
+            self.dut1_candidate = Some(dut1);
+            self.dut1_candidate_count = 1;
+        }
+        if self.dut1_candidate_count >= self.dut1_confirmations {
+            self.dut1_filtered = Some(dut1);
         }
     }
 
-    /// Return if the end-of-minute marker (0111_1110) is present at the end of the A bits.
-    ///
-    /// This method must be called _before_ `increase_second()`
-    pub fn end_of_minute_marker_present(&self) -> bool {
-        self.search_eom_marker(false)
+    /// Return if DUT1 satisfies the checks, which it always does with the `no-dut1` feature.
+    fn dut1_ok(&self) -> bool {
+        cfg!(feature = "no-dut1") || self.get_dut1().is_some()
     }
 
-    /// Helper for end_of_minute_marker_present() and get_minute_length()
-    fn search_eom_marker(&self, predict: bool) -> bool {
-        if self.second < 7 {
-            return false; // not enough bits to test
-        }
-        const MARKER: [bool; 8] = [false, true, true, true, true, true, true, false];
-        for (idx, bit) in self.bit_buffer_a
-            [(self.second - 7 + predict as u8) as usize..=self.second as usize]
-            .iter()
-            .enumerate()
-        {
-            if bit.is_none() || *bit != Some(MARKER[idx]) {
-                return false;
-            }
-        }
-        true
+    /// Get the CRC-16 over the raw A and B bits of the last decoded minute, to detect corruption
+    /// or duplicates when relaying it.
+    pub fn get_minute_crc(&self) -> Option<u16> {
+        self.minute_crc
     }
 
-    /// Increase or reset `second`.
-    ///
-    /// Returns if the second counter was increased/wrapped normally (true)
-    /// or due to an overflow (false).
+    /// Return a hash over the configuration and the decoding state, to verify that two decoders
+    /// (e.g. on a device and in a replay on a host) are in the same state.
     ///
-    /// This method must be called _after_ `decode_time()`, `handle_new_edge()`,
-    /// `set_current_bit_a()`, `set_current_bit_b()`, `end_of_minute_marker_present()`
-    /// and `force_new_minute()`.
-    pub fn increase_second(&mut self) -> bool {
-        let minute_length = self.get_minute_length();
-        RadioDateTimeUtils::increase_second(&mut self.second, self.new_minute, minute_length)
+    /// Time stamps are left out, so decoders fed with the same edges at a different time base
+    /// still match.
+    pub fn state_fingerprint(&self) -> u32 {
+        let value = |v: Option<u8>| v.unwrap_or(0xff);
+        let bit = |b: Option<bool>| b.map_or(2, |b| b as u8);
+        let merge_policy = match self.decoder_config.merge_policy {
+            MergePolicy::Parity => [0, 0],
+            MergePolicy::Confidence { threshold } => [1, threshold],
+        };
+        #[cfg(not(feature = "marker-policy"))]
+        let marker_policy = [0, 0]; // always the last marker
+        #[cfg(feature = "marker-policy")]
+        let marker_policy = match self.marker_policy {
+            MarkerPolicy::PreferLast => [0, 0],
+            MarkerPolicy::PreferEndOfMinute => [1, 0],
+            MarkerPolicy::PreferBeginOfMinute => [2, 0],
+            MarkerPolicy::RequireBoth => [3, 0],
+            MarkerPolicy::Majority { minutes } => [4, minutes],
+        };
+        #[cfg(feature = "single-shot")]
+        let disarmed = self.disarmed;
+        #[cfg(not(feature = "single-shot"))]
+        let disarmed = false;
+        #[cfg(feature = "dut1-filter")]
+        let dut1_confirmations = self.dut1_confirmations;
+        #[cfg(not(feature = "dut1-filter"))]
+        let dut1_confirmations = 1;
+        #[cfg(feature = "voting")]
+        let voting_depth = self.voting_depth;
+        #[cfg(not(feature = "voting"))]
+        let voting_depth = 0;
+        let rdt = &self.radio_datetime;
+        let mut hash = msf_helpers::FNV1A_BASIS;
+        for limit in [
+            self.spike_limit_low,
+            self.spike_limit_high,
+            self.decoder_config.refractory_window,
+            self.decoder_config.active_0_limit,
+            self.decoder_config.active_a_limit,
+            self.decoder_config.active_ab_limit,
+            self.decoder_config.minute_limit,
+            self.decoder_config.passive_runaway,
+        ] {
+            hash = msf_helpers::get_fnv1a(hash, limit.to_le_bytes());
+        }
+        hash = msf_helpers::get_fnv1a(hash, merge_policy);
+        hash = msf_helpers::get_fnv1a(hash, marker_policy);
+        hash = msf_helpers::get_fnv1a(
+            hash,
+            [
+                self.decoder_config.pulse_bias_compensation as u8,
+                self.decoder_config.adaptive_limits as u8,
+                self.auto_spike_limit as u8,
+                self.decoder_config.flywheel as u8,
+                self.suspect_0_1 as u8,
+                self.fixed_minute_length as u8,
+                self.a_only as u8,
+                self.predictive_fallback as u8,
+                voting_depth,
+                self.watchdog_tolerance,
+                self.dut1_limit,
+                dut1_confirmations,
+                self.decoder_config.sync_minutes,
+                disarmed as u8,
+                self.signal_convention as u8,
+                self.prediction_horizon,
+                self.first_minute as u8,
+                self.new_minute as u8,
+                self.past_new_minute as u8,
+                self.second,
+                value(rdt.get_year()),
+                value(rdt.get_month()),
+                value(rdt.get_day()),
+                value(rdt.get_weekday()),
+                value(rdt.get_hour()),
+                value(rdt.get_minute()),
+                value(rdt.get_dst()),
+                value(rdt.get_leap_second()),
+                self.get_dut1().map_or(0x80, |d| d as u8),
+                bit(self.parity_1),
+                bit(self.parity_2),
+                bit(self.parity_3),
+                bit(self.parity_4),
+            ],
+        );
+        msf_helpers::get_fnv1a(
+            hash,
+            self.bit_buffer_a
+                .iter()
+                .chain(&self.bit_buffer_b)
+                .map(|b| bit(*b)),
+        )
     }
 
-    /// Call add_minute() on `self.radio_datetime` and passes on that result.
+    /// Get the policy deciding which fields of a minute decoded using relaxed checks are merged
+    /// into the date/time.
+    pub fn get_merge_policy(&self) -> MergePolicy {
+        self.decoder_config.merge_policy
+    }
+
+    /// Set the policy deciding which fields of a minute decoded using relaxed checks are merged
+    /// into the date/time.
     ///
-    /// This could be useful for consumers just wanting to advance their current date/time.
-    pub fn add_minute(&mut self) -> bool {
-        self.radio_datetime.clear_jumps();
-        self.radio_datetime.add_minute()
+    /// # Arguments
+    /// * `policy` - the policy to use
+    pub fn set_merge_policy(&mut self, policy: MergePolicy) {
+        self.decoder_config.merge_policy = policy;
     }
 
-    /// Decode the time broadcast during the last minute and clear `first_minute` when appropriate.
+    /// Get the policy deciding which minute marker aligns the second counter if the
+    /// end-of-minute and the begin-of-minute marker disagree.
+    #[cfg(feature = "marker-policy")]
+    pub fn get_marker_policy(&self) -> MarkerPolicy {
+        self.marker_policy
+    }
+
+    /// Set the policy deciding which minute marker aligns the second counter if the
+    /// end-of-minute and the begin-of-minute marker disagree, a majority over 0 minutes is
+    /// ignored.
     ///
-    /// This method must be called _before_ `increase_second()`
+    /// Each conflict is reported as `Diagnostic::MarkerConflict`.
     ///
     /// # Arguments
-    /// * `strict_checks` - checks all parities, DUT1 validity, and EOM marker presence when setting
-    ///                     date/time and clearing self.first_minute
-    pub fn decode_time(&mut self, strict_checks: bool) {
-        self.radio_datetime.clear_jumps();
-        let minute_length = self.get_minute_length(); // calculation depends on self.second
-        let mut added_minute = false;
-        if !self.first_minute {
-            added_minute = self.radio_datetime.add_minute();
+    /// * `policy` - the policy to use
+    #[cfg(feature = "marker-policy")]
+    pub fn set_marker_policy(&mut self, policy: MarkerPolicy) {
+        if policy != (MarkerPolicy::Majority { minutes: 0 }) {
+            self.marker_policy = policy;
+            self.marker_votes = MarkerVotes::default();
         }
-        if self.second + 1 == minute_length {
-            let offset: isize = match 60.cmp(&minute_length) {
-                Ordering::Less => 1,
-                Ordering::Equal => 0,
-                Ordering::Greater => -1,
-            };
+    }
 
-            self.parity_1 = radio_datetime_helpers::get_parity(
-                &self.bit_buffer_a,
-                (17 + offset) as usize,
-                (24 + offset) as usize,
-                self.bit_buffer_b[(54 + offset) as usize],
-            );
-            self.parity_2 = radio_datetime_helpers::get_parity(
-                &self.bit_buffer_a,
-                (25 + offset) as usize,
-                (35 + offset) as usize,
-                self.bit_buffer_b[(55 + offset) as usize],
-            );
-            self.parity_3 = radio_datetime_helpers::get_parity(
-                &self.bit_buffer_a,
-                (36 + offset) as usize,
-                (38 + offset) as usize,
-                self.bit_buffer_b[(56 + offset) as usize],
-            );
-            self.parity_4 = radio_datetime_helpers::get_parity(
-                &self.bit_buffer_a,
-                (39 + offset) as usize,
-                (51 + offset) as usize,
-                self.bit_buffer_b[(57 + offset) as usize],
-            );
+    /// Get the number of consecutive minutes in which each group of received fields matched the
+    /// held date/time.
+    pub fn get_field_confidence(&self) -> FieldConfidence {
+        self.field_confidence
+    }
 
-            self.dut1 = None;
-            // bit 16 is dropped in case of a negative leap second
-            let stop = if offset == -1 { 15 } else { 16 };
-            if let Some(dut1p) = msf_helpers::get_unary_value(&self.bit_buffer_b, 1, 8) {
-                if let Some(dut1n) = msf_helpers::get_unary_value(&self.bit_buffer_b, 9, stop) {
-                    self.dut1 = if dut1p * dut1n == 0 {
-                        Some(dut1p - dut1n)
-                    } else {
-                        None
-                    };
-                }
-            }
+    /// Get when `decode_minute_auto()` switches between relaxed and strict checks.
+    pub fn get_escalation(&self) -> Escalation {
+        self.decoder_config.escalation
+    }
 
-            let strict_ok = self.parity_1 == Some(true)
-                && self.parity_2 == Some(true)
-                && self.parity_3 == Some(true)
-                && self.parity_4 == Some(true)
-                && self.dut1.is_some()
-                && self.end_of_minute_marker_present();
+    /// Set when `decode_minute_auto()` switches between relaxed and strict checks, limits of 0
+    /// are ignored.
+    ///
+    /// # Arguments
+    /// * `escalation` - the limits to use
+    pub fn set_escalation(&mut self, escalation: Escalation) {
+        if escalation.is_valid() {
+            self.decoder_config.escalation = escalation;
+        }
+    }
 
-            self.radio_datetime.set_year(
-                radio_datetime_helpers::get_bcd_value(
-                    &self.bit_buffer_a,
-                    (24 + offset) as usize,
-                    (17 + offset) as usize,
-                ),
-                if strict_checks {
-                    strict_ok
-                } else {
-                    self.parity_1 == Some(true)
-                },
-                added_minute && !self.first_minute,
-            );
-            self.radio_datetime.set_month(
-                radio_datetime_helpers::get_bcd_value(
-                    &self.bit_buffer_a,
-                    (29 + offset) as usize,
-                    (25 + offset) as usize,
-                ),
-                if strict_checks {
-                    strict_ok
-                } else {
-                    self.parity_2 == Some(true)
-                },
-                added_minute && !self.first_minute,
-            );
-            self.radio_datetime.set_weekday(
-                radio_datetime_helpers::get_bcd_value(
-                    &self.bit_buffer_a,
-                    (38 + offset) as usize,
-                    (36 + offset) as usize,
-                ),
-                if strict_checks {
-                    strict_ok
-                } else {
-                    self.parity_3 == Some(true)
-                },
-                added_minute && !self.first_minute,
-            );
-            self.radio_datetime.set_day(
-                radio_datetime_helpers::get_bcd_value(
-                    &self.bit_buffer_a,
-                    (35 + offset) as usize,
-                    (30 + offset) as usize,
-                ),
-                if strict_checks {
-                    strict_ok
-                } else {
-                    self.parity_1 == Some(true)
-                        && self.parity_2 == Some(true)
-                        && self.parity_3 == Some(true)
-                },
-                added_minute && !self.first_minute,
-            );
+    /// Get the level of the checks `decode_minute_auto()` applies to the next minute.
+    pub fn get_strictness(&self) -> Strictness {
+        self.strictness
+    }
 
-            self.radio_datetime.set_hour(
-                radio_datetime_helpers::get_bcd_value(
-                    &self.bit_buffer_a,
-                    (44 + offset) as usize,
-                    (39 + offset) as usize,
-                ),
-                if strict_checks {
-                    strict_ok
-                } else {
-                    self.parity_4 == Some(true)
-                },
-                added_minute && !self.first_minute,
-            );
-            self.radio_datetime.set_minute(
-                radio_datetime_helpers::get_bcd_value(
-                    &self.bit_buffer_a,
-                    (51 + offset) as usize,
-                    (45 + offset) as usize,
-                ),
-                if strict_checks {
-                    strict_ok
-                } else {
-                    self.parity_4 == Some(true)
-                },
-                added_minute && !self.first_minute,
-            );
+    /// Set the level of the checks `decode_minute_auto()` applies to the next minute, e.g. to
+    /// start with strict checks when a wrong date/time is worse than none. The escalation
+    /// restarts counting from this level.
+    ///
+    /// # Arguments
+    /// * `strictness` - the level to use
+    pub fn set_strictness(&mut self, strictness: Strictness) {
+        self.strictness = strictness;
+        self.strictness_streak = 0;
+    }
 
-            self.radio_datetime.set_dst(
-                self.bit_buffer_b[(58 + offset) as usize],
-                self.bit_buffer_b[(53 + offset) as usize],
-                added_minute && !self.first_minute,
-            );
+    /// Get the number of minutes seen by `decode_minute()`.
+    #[cfg(feature = "statistics")]
+    pub fn uptime_minutes(&self) -> u32 {
+        self.audit.uptime_minutes
+    }
 
-            if if strict_checks {
-                strict_ok
-            } else {
-                self.dut1.is_some()
-            } && self.radio_datetime.is_valid()
-            {
-                // allow displaying of information after the first properly decoded minute
-                self.first_minute = false;
-            }
+    /// Get the number of times the second counter was realigned by a begin-of-minute marker.
+    #[cfg(feature = "statistics")]
+    pub fn total_resyncs(&self) -> u32 {
+        self.audit.total_resyncs
+    }
 
-            self.radio_datetime.bump_minutes_running();
-        }
+    /// Get the number of minutes in which a field of the held date/time jumped.
+    #[cfg(feature = "statistics")]
+    pub fn total_steps(&self) -> u32 {
+        self.audit.total_steps
     }
-}
+
+    /// Return the summary of the day which ended with the last decoded minute, only once.
+    ///
+    /// This method should be called _after_ `decode_minute()`, a summary which is not taken is
+    /// replaced at the end of the next day.
+    #[cfg(feature = "statistics")]
+    pub fn take_daily_summary(&mut self) -> Option<DailySummary> {
+        self.audit.summary.take()
+    }
+
+    /// Get the counters of the reception problems and decoding results.
+    #[cfg(feature = "statistics")]
+    pub fn get_metrics(&self) -> &Metrics {
+        &self.metrics
+    }
+
+    /// Set all counters of the metrics to 0, e.g. after reporting them.
+    #[cfg(feature = "statistics")]
+    pub fn reset_metrics(&mut self) {
+        self.metrics.reset();
+    }
+
+    /// Get the bit error rate of the received date/time bits, compared against the bits
+    /// predicted from the previous minute.
+    #[cfg(feature = "statistics")]
+    pub fn get_bit_error_rate(&self) -> &BitErrorRate {
+        &self.bit_error_rate
+    }
+
+    /// Set the counters of the bit error rate to 0.
+    #[cfg(feature = "statistics")]
+    pub fn reset_bit_error_rate(&mut self) {
+        self.bit_error_rate.reset();
+    }
+
+    /// Get the histogram of the gaps (in minutes) between successfully decoded minutes.
+    #[cfg(feature = "statistics")]
+    pub fn get_gap_histogram(&self) -> &GapHistogram {
+        &self.gap_histogram
+    }
+
+    /// Clear the histogram of the gaps between successfully decoded minutes.
+    #[cfg(feature = "statistics")]
+    pub fn reset_gap_histogram(&mut self) {
+        self.gap_histogram.reset();
+    }
+
+    /// Get the raw deviations of the second boundaries which were not drained yet.
+    #[cfg(feature = "discipline")]
+    pub fn get_phase_samples(&self) -> &PhaseSamples {
+        &self.phase_samples
+    }
+
+    /// Move the oldest raw deviations of the second boundaries into `buf` and return how many
+    /// were moved, see `PhaseSamples`.
+    ///
+    /// # Arguments
+    /// * `buf` - buffer to move the samples into
+    #[cfg(feature = "discipline")]
+    pub fn drain_phase_samples(&mut self, buf: &mut [i32]) -> usize {
+        self.phase_samples.drain(buf)
+    }
+
+    /// Return the current spike limit in microseconds, the larger one of both edge polarities.
+    pub fn get_spike_limit(&self) -> u32 {
+        self.spike_limit_low.max(self.spike_limit_high)
+    }
+
+    /// Set the new spike limit for both edge polarities in microseconds, [0(off)..ACTIVE_0_LIMIT)
+    ///
+    /// # Arguments
+    /// * `value` - the value to set the spike limit to.
+    pub fn set_spike_limit(&mut self, value: u32) {
+        self.set_spike_limit_low(value);
+        self.set_spike_limit_high(value);
+    }
+
+    /// Return the current spike limit for high-to-low edges in microseconds.
+    pub fn get_spike_limit_low(&self) -> u32 {
+        self.spike_limit_low
+    }
+
+    /// Set the new spike limit for high-to-low edges in microseconds, [0(off)..ACTIVE_0_LIMIT)
+    ///
+    /// # Arguments
+    /// * `value` - the value to set the spike limit to.
+    pub fn set_spike_limit_low(&mut self, value: u32) {
+        if value < ACTIVE_0_LIMIT {
+            self.spike_limit_low = value;
+        }
+    }
+
+    /// Return the current spike limit for low-to-high edges in microseconds.
+    pub fn get_spike_limit_high(&self) -> u32 {
+        self.spike_limit_high
+    }
+
+    /// Set the new spike limit for low-to-high edges in microseconds, [0(off)..ACTIVE_0_LIMIT)
+    ///
+    /// # Arguments
+    /// * `value` - the value to set the spike limit to.
+    pub fn set_spike_limit_high(&mut self, value: u32) {
+        if value < ACTIVE_0_LIMIT {
+            self.spike_limit_high = value;
+        }
+    }
+
+    /// Return if the spike limit is tuned from the observed short pulses.
+    pub fn get_auto_spike_limit(&self) -> bool {
+        self.auto_spike_limit
+    }
+
+    /// Set if the spike limit is tuned from the observed short pulses, see the `spike_tuning`
+    /// module.
+    ///
+    /// Once per minute, the tuner sets the spike limit of both edge polarities to a value
+    /// between `AUTO_SPIKE_LIMIT_MIN` and `AUTO_SPIKE_LIMIT_MAX`, which is then returned by
+    /// `get_spike_limit()`. The last tuned value is kept when tuning is disabled again.
+    ///
+    /// # Arguments
+    /// * `value` - tune the spike limit
+    pub fn set_auto_spike_limit(&mut self, value: bool) {
+        self.auto_spike_limit = value;
+    }
+
+    /// Return the window in microseconds within which a second edge of the same direction is
+    /// ignored, 0 means off.
+    pub fn get_refractory_window(&self) -> u32 {
+        self.decoder_config.refractory_window
+    }
+
+    /// Set the window in microseconds within which a second edge of the same direction is
+    /// ignored, [0(off)..ACTIVE_0_LIMIT)
+    ///
+    /// This filters double triggers like contact bounce of some open-collector receiver outputs,
+    /// which the spike filter does not catch because it needs edges of opposite directions.
+    ///
+    /// # Arguments
+    /// * `value` - the value to set the window to.
+    pub fn set_refractory_window(&mut self, value: u32) {
+        if value < ACTIVE_0_LIMIT {
+            self.decoder_config.refractory_window = value;
+        }
+    }
+
+    /// Return the time in microseconds without a pulse after which the signal is considered
+    /// lost.
+    pub fn get_passive_runaway(&self) -> u32 {
+        self.decoder_config.passive_runaway
+    }
+
+    /// Set the time in microseconds without a pulse after which the signal is considered lost,
+    /// [1_000_000..TIMER_DISCONTINUITY)
+    ///
+    /// Larger values bridge longer dropouts of the receiver output, but then a second which is
+    /// missed completely is merged into the next one unless the flywheel is locked.
+    ///
+    /// # Arguments
+    /// * `value` - the value to set the limit to.
+    pub fn set_passive_runaway(&mut self, value: u32) {
+        self.set_decoder_config(DecoderConfig {
+            passive_runaway: value,
+            ..self.decoder_config
+        });
+    }
+
+    /// Get the configuration of the decoder.
+    pub fn get_decoder_config(&self) -> DecoderConfig {
+        self.decoder_config
+    }
+
+    /// Set the configuration of the decoder, an invalid configuration (see
+    /// `DecoderConfig::is_valid()`) is ignored.
+    ///
+    /// The active limits are the fallback of the adaptive limits. The spike limits and the
+    /// refractory window are still checked against the default `active_0_limit`. A changed
+    /// number of sync minutes restarts the single-shot sync, see `set_sync_minutes()`.
+    ///
+    /// # Arguments
+    /// * `config` - the configuration to use
+    pub fn set_decoder_config(&mut self, config: DecoderConfig) {
+        if config.is_valid() {
+            #[cfg(feature = "single-shot")]
+            if config.sync_minutes != self.decoder_config.sync_minutes {
+                self.sync_snapshot = None;
+            }
+            self.decoder_config = config;
+        }
+    }
+
+    /// Get the estimated systematic deviation of the active (low) pulses from their nominal
+    /// 100/200/300/500 ms lengths in microseconds, positive values mean stretched pulses.
+    ///
+    /// Returns None until enough pulses were classified.
+    pub fn get_pulse_bias(&self) -> Option<i32> {
+        if self.pulse_bias_count < PULSE_BIAS_MIN_SAMPLES {
+            None
+        } else {
+            Some(self.pulse_bias.mul_int(1_000) as i32)
+        }
+    }
+
+    /// Return if the pulse lengths are corrected for the estimated pulse bias before classifying
+    /// them.
+    pub fn get_pulse_bias_compensation(&self) -> bool {
+        self.decoder_config.pulse_bias_compensation
+    }
+
+    /// Set if the pulse lengths should be corrected for the estimated pulse bias before
+    /// classifying them.
+    ///
+    /// # Arguments
+    /// * `value` - correct the pulse lengths
+    pub fn set_pulse_bias_compensation(&mut self, value: bool) {
+        self.decoder_config.pulse_bias_compensation = value;
+    }
+
+    /// Return if the limits of the pulse lengths are learned from the observed pulses.
+    pub fn get_adaptive_limits(&self) -> bool {
+        self.decoder_config.adaptive_limits
+    }
+
+    /// Set if the limits of the pulse lengths are learned from the observed pulses, see the
+    /// `adaptive_limits` module. The fixed limits are used until enough pulses of each length
+    /// have been seen.
+    ///
+    /// # Arguments
+    /// * `value` - learn the limits
+    pub fn set_adaptive_limits(&mut self, value: bool) {
+        self.decoder_config.adaptive_limits = value;
+    }
+
+    /// Get the limits of the pulse lengths currently used for classifying the pulses.
+    pub fn get_active_limits(&self) -> ActiveLimits {
+        let configured = self.decoder_config.active_limits();
+        if self.decoder_config.adaptive_limits {
+            self.pulse_clusters
+                .limits(configured, self.decoder_config.minute_limit)
+        } else {
+            configured
+        }
+    }
+
+    /// Return if decoded (0,1) bit pairs are treated as noise.
+    pub fn get_suspect_0_1(&self) -> bool {
+        self.suspect_0_1
+    }
+
+    /// Set if decoded (0,1) bit pairs should be treated as noise, which sets the bits to None.
+    ///
+    /// This combination is currently not broadcast outside of the DUT1 field (seconds 1-16),
+    /// where it is always accepted.
+    ///
+    /// # Arguments
+    /// * `value` - treat (0,1) bit pairs as noise
+    pub fn set_suspect_0_1(&mut self, value: bool) {
+        self.suspect_0_1 = value;
+    }
+
+    /// Get the number of (0,1) bit pairs decoded by `handle_new_edge()` outside of the DUT1
+    /// field, also when they are treated as noise.
+    pub fn get_count_0_1(&self) -> u32 {
+        self.count_0_1
+    }
+
+    /// Return if minutes are decoded from the A bits alone.
+    pub fn get_a_only(&self) -> bool {
+        self.a_only
+    }
+
+    /// Set if minutes are decoded from the A bits alone, for receivers which do not recover the
+    /// B channel reliably.
+    ///
+    /// The parities and DUT1 are then not decoded, so the date/time is only protected by the
+    /// range checks and, with strict checks, the end-of-minute marker. The parities are reported
+    /// as None, and `Snapshot::a_only` is set. The DST bits, which have no parity, are still
+    /// taken from the B channel, the date/time is valid once they were received.
+    ///
+    /// # Arguments
+    /// * `value` - decode from the A bits alone
+    pub fn set_a_only(&mut self, value: bool) {
+        self.a_only = value;
+    }
+
+    /// Return if fields failing their parity may be taken from the prediction.
+    pub fn get_predictive_fallback(&self) -> bool {
+        self.predictive_fallback
+    }
+
+    /// Set if fields failing their parity may be taken from the prediction.
+    ///
+    /// Once a minute is known, the date/time of the next one is predicted by `add_minute()`. If
+    /// the received bits of a parity group then differ from the predicted bits in at most one
+    /// position, the predicted value is decoded instead and the field is reported by
+    /// `get_predicted_fields()`. This bridges single bit errors without discarding the minute.
+    /// It has no effect when decoding from the A bits alone, and only with relaxed checks because
+    /// strict checks need all received parities to pass.
+    ///
+    /// # Arguments
+    /// * `value` - take failing fields from the prediction
+    pub fn set_predictive_fallback(&mut self, value: bool) {
+        self.predictive_fallback = value;
+    }
+
+    /// Get the fields of the last decoded minute which were taken from the prediction, see
+    /// `set_predictive_fallback()`.
+    pub fn get_predicted_fields(&self) -> PredictedFields {
+        self.predicted_fields
+    }
+
+    /// Get the number of minutes over which the date and DUT1 bits are voted, 0 if voting is
+    /// disabled.
+    #[cfg(feature = "voting")]
+    pub fn get_voting_depth(&self) -> u8 {
+        self.voting_depth
+    }
+
+    /// Set the number of minutes over which the date and DUT1 bits are voted before each minute
+    /// is decoded, see the `msf_voting` module. Values above `VOTING_DEPTH_MAX` are ignored.
+    ///
+    /// # Arguments
+    /// * `depth` - number of minutes, 0 to disable voting
+    #[cfg(feature = "voting")]
+    pub fn set_voting_depth(&mut self, depth: u8) {
+        if depth <= VOTING_DEPTH_MAX {
+            self.voting_depth = depth;
+            self.voter.clear();
+        }
+    }
+
+    /// Get the number of bits of the last minute which were changed by voting.
+    #[cfg(feature = "voting")]
+    pub fn get_voted_bits(&self) -> u8 {
+        self.voted_bits
+    }
+
+    /// Get the confidence of each field of the last minute, all 0 if the minute was incomplete.
+    #[cfg(feature = "statistics")]
+    pub fn get_confidence_scores(&self) -> ConfidenceScores {
+        self.confidence_scores
+    }
+
+    /// Get the ring of the last raw edges and their classification.
+    #[cfg(feature = "statistics")]
+    pub fn get_edge_ring(&self) -> &EdgeRing {
+        &self.edge_ring
+    }
+
+    /// Set the number of raw edges to keep in the edge ring and clear it, values above
+    /// `EDGE_RING_SIZE` are ignored.
+    ///
+    /// The ring can be dumped when a minute fails to decode, to see what the decoder saw in the
+    /// preceding seconds.
+    ///
+    /// # Arguments
+    /// * `length` - number of edges, 0 to disable the ring
+    #[cfg(feature = "statistics")]
+    pub fn set_edge_ring_length(&mut self, length: usize) {
+        self.edge_ring.set_capacity(length);
+    }
+
+    /// Get the number of consecutive seconds without a valid bit pair after which the decoder is
+    /// no longer considered healthy.
+    pub fn get_watchdog_tolerance(&self) -> u8 {
+        self.watchdog_tolerance
+    }
+
+    /// Set the number of consecutive seconds without a valid bit pair after which the decoder is
+    /// no longer considered healthy, 0 is ignored.
+    ///
+    /// # Arguments
+    /// * `seconds` - number of seconds
+    pub fn set_watchdog_tolerance(&mut self, seconds: u8) {
+        if seconds > 0 {
+            self.watchdog_tolerance = seconds;
+        }
+    }
+
+    /// Return if a hardware watchdog should be kicked, i.e. if the decoder is healthy.
+    ///
+    /// The decoder is healthy when a second has passed since the last call and fewer than
+    /// `get_watchdog_tolerance()` consecutive seconds lacked a valid bit pair. Without incoming
+    /// seconds (e.g. a wedged receiver front-end) this keeps returning false, so the watchdog
+    /// resets the device based on decoder health rather than mere code liveness.
+    pub fn should_kick_watchdog(&mut self) -> bool {
+        ::core::mem::take(&mut self.watchdog_kick)
+    }
+
+    /// Get the level of the receiver output during the carrier-off pulses.
+    pub fn get_signal_convention(&self) -> SignalConvention {
+        self.signal_convention
+    }
+
+    /// Set the level of the receiver output during the carrier-off pulses, see
+    /// `SignalConvention::detect()` to determine it.
+    ///
+    /// # Arguments
+    /// * `value` - the convention of the receiver
+    pub fn set_signal_convention(&mut self, value: SignalConvention) {
+        self.signal_convention = value;
+    }
+
+    /// Determine the bit value if a new edge is received. indicates reception errors,
+    /// and checks if a new minute has started.
+    ///
+    /// This function can deal with spikes, which are arbitrarily set to `spike_limit_low` or
+    /// `spike_limit_high` microseconds, depending on the direction of the edge.
+    ///
+    /// This method must be called _before_ `increase_second()`.
+    ///
+    /// Which level marks the carrier-off pulses is set using `set_signal_convention()`.
+    ///
+    /// # Arguments
+    /// * `is_low_edge` - indicates that the edge has gone from high to low (as opposed to
+    ///                   low-to-high).
+    /// * `t` - time stamp of the received edge, in microseconds
+    pub fn handle_new_edge(&mut self, is_low_edge: bool, t: u32) {
+        self.last_edge_class = None;
+        #[cfg(feature = "single-shot")]
+        if self.disarmed {
+            return;
+        }
+        self.timer_discontinuity = false;
+        let is_low_edge = match self.signal_convention {
+            SignalConvention::ActiveHigh => is_low_edge,
+            SignalConvention::ActiveLow => !is_low_edge,
+        };
+        self.process_edge(is_low_edge, t);
+        self.check_invariants();
+    }
+
+    /// Like `handle_new_edge()`, but return what the decoder made of the edge.
+    ///
+    /// Returns None if the edge was not processed at all, i.e. while disarmed (`single-shot`
+    /// feature).
+    ///
+    /// # Arguments
+    /// * `is_low_edge` - indicates that the edge has gone from high to low (as opposed to
+    ///   low-to-high).
+    /// * `t` - time stamp of the received edge, in microseconds
+    pub fn handle_new_edge_classified(&mut self, is_low_edge: bool, t: u32) -> Option<EdgeClass> {
+        self.handle_new_edge(is_low_edge, t);
+        self.last_edge_class
+    }
+
+    /// Like `handle_new_edge()`, but with 64-bit time stamps, e.g. from a monotonic host clock.
+    ///
+    /// The decoder works with wrapping 32-bit time stamps, which alias after about 71.6 minutes.
+    /// Gaps of `TIMER_DISCONTINUITY` or more between two edges are therefore shortened to exactly
+    /// that before the time stamp is truncated, so they are always seen as a timer discontinuity.
+    ///
+    /// # Arguments
+    /// * `is_low_edge` - indicates that the edge has gone from high to low (as opposed to
+    ///   low-to-high).
+    /// * `t` - time stamp of the received edge, in microseconds
+    pub fn handle_new_edge_u64(&mut self, is_low_edge: bool, t: u64) {
+        if let Some(last_t) = self.last_t64 {
+            let gap = t.wrapping_sub(last_t);
+            if (TIMER_DISCONTINUITY as u64..1 << 63).contains(&gap) {
+                self.t64_offset = self
+                    .t64_offset
+                    .wrapping_add(gap - TIMER_DISCONTINUITY as u64);
+            }
+        }
+        self.last_t64 = Some(t);
+        self.handle_new_edge(is_low_edge, t.wrapping_sub(self.t64_offset) as u32);
+    }
+
+    /// Remember the classification of an edge and add it to the edge ring.
+    #[cfg_attr(not(feature = "statistics"), allow(unused_variables))]
+    fn record_edge(&mut self, is_low_edge: bool, t: u32, class: EdgeClass) {
+        self.last_edge_class = Some(class);
+        #[cfg(feature = "statistics")]
+        self.edge_ring.add(is_low_edge, t, class);
+        if class == EdgeClass::Spike {
+            #[cfg(feature = "statistics")]
+            {
+                self.reception_quality.add_spike();
+                self.metrics.add_spike();
+            }
+        }
+    }
+
+    /// Forget the bits of the current second.
+    fn clear_current_bits(&mut self) {
+        self.bit_buffer_a[self.second as usize] = None;
+        self.bit_buffer_b[self.second as usize] = None;
+        #[cfg(feature = "soft-bits")]
+        {
+            self.soft_buffer[self.second as usize] = BitLikelihoods::default();
+        }
+    }
+
+    /// Forget the edges received so far, after a timer discontinuity or when rearming.
+    ///
+    /// The bits of the current second are cleared, the bits of the earlier seconds are kept.
+    fn restart_edges(&mut self) {
+        self.new_second = false;
+        self.new_minute = false;
+        self.past_new_minute = false;
+        self.clear_current_bits();
+        self.valid_second = false;
+        self.old_t_diff = 0; // the next pulse is the first one again
+        self.second_edge = None;
+        self.second_start = None;
+        self.minute_marker_t = None;
+        self.minute_marker_expected = None;
+        #[cfg(feature = "flywheel")]
+        {
+            self.flywheel_lock = 0;
+            self.missed_seconds = 0;
+        }
+        #[cfg(feature = "discipline")]
+        self.phase_samples.restart();
+    }
+
+    /// Helper for handle_new_edge(), see there.
+    fn process_edge(&mut self, is_low_edge: bool, t: u32) {
+        #[cfg(feature = "fault-injection")]
+        if self.take_fault(FaultKind::DropEdge) {
+            return;
+        }
+        if self.decoder_config.refractory_window > 0 {
+            if let Some((last_is_low_edge, last_t)) = self.last_edge {
+                if last_is_low_edge == is_low_edge
+                    && radio_datetime_helpers::time_diff(last_t, t)
+                        < self.decoder_config.refractory_window
+                {
+                    self.record_edge(is_low_edge, t, EdgeClass::Refractory);
+                    return; // double edge, keep the first one
+                }
+            }
+        }
+        self.last_edge = Some((is_low_edge, t));
+        let limits = FramingLimits {
+            spike: if is_low_edge {
+                self.spike_limit_low
+            } else {
+                self.spike_limit_high
+            },
+            passive_runaway: self.decoder_config.passive_runaway,
+            minute: self.decoder_config.minute_limit,
+            discontinuity: TIMER_DISCONTINUITY,
+        };
+        let framed = self.frame_edge(is_low_edge, t, &limits);
+        if self.auto_spike_limit {
+            if let Some(length) = framed.length() {
+                self.spike_tuner.add(length);
+            }
+        }
+        match framed {
+            FramedEdge::First => {
+                self.record_edge(is_low_edge, t, EdgeClass::First);
+                return;
+            }
+            FramedEdge::Discontinuity => {
+                self.timer_discontinuity = true;
+                self.diagnose(Diagnostic::TimerDiscontinuity);
+                self.restart_edges();
+                self.record_edge(is_low_edge, t, EdgeClass::Discontinuity);
+                return;
+            }
+            FramedEdge::Spike { .. } => {
+                self.record_edge(is_low_edge, t, EdgeClass::Spike);
+                return; // random positive or negative spike, ignore
+            }
+            _ => {}
+        }
+        self.new_minute = false;
+        self.past_new_minute = false;
+        match framed {
+            FramedEdge::PulseEnd {
+                active: t_diff,
+                passive,
+            } => {
+                self.new_second = false;
+                let active = self.compensate_pulse(t_diff, true);
+                let passive = self.compensate_pulse(passive, false);
+                let limits = self.get_active_limits();
+                let pulse = if self.decoder_config.adaptive_limits
+                    || !self.decoder_config.has_default_limits()
+                {
+                    Pulse::classify_with(active, passive, &limits, self.decoder_config.minute_limit)
+                } else {
+                    Pulse::classify(active, passive)
+                };
+                let class = match pulse {
+                    Pulse::Bit { a, b } => EdgeClass::Bit { a, b },
+                    Pulse::MinuteMarker => EdgeClass::MinuteMarker,
+                    Pulse::Runaway => EdgeClass::ActiveRunaway,
+                    Pulse::Unchanged => EdgeClass::Unchanged,
+                };
+                self.record_edge(is_low_edge, t, class);
+                match pulse {
+                    Pulse::Bit { .. } | Pulse::MinuteMarker => self.valid_second = true,
+                    Pulse::Runaway => self.valid_second = false,
+                    Pulse::Unchanged => {}
+                }
+                #[cfg(feature = "soft-bits")]
+                {
+                    self.soft_buffer[self.second as usize] = match pulse {
+                        Pulse::Bit { a: false, b: true } => {
+                            BitLikelihoods::second_pulse(active, passive)
+                        }
+                        Pulse::Bit { .. } => BitLikelihoods::first_pulse(active),
+                        Pulse::MinuteMarker | Pulse::Runaway => BitLikelihoods::default(),
+                        Pulse::Unchanged => self.soft_buffer[self.second as usize],
+                    };
+                }
+                match pulse {
+                    Pulse::Bit { a: false, b: true } if self.second >= DUT1_END => {
+                        self.count_0_1 = self.count_0_1.saturating_add(1);
+                        if self.suspect_0_1 {
+                            self.bit_buffer_a[self.second as usize] = None;
+                            self.bit_buffer_b[self.second as usize] = None;
+                        } else {
+                            self.bit_buffer_a[self.second as usize] = Some(false);
+                            self.bit_buffer_b[self.second as usize] = Some(true);
+                        }
+                    }
+                    Pulse::Bit { a, b } => {
+                        self.bit_buffer_a[self.second as usize] = Some(a);
+                        self.bit_buffer_b[self.second as usize] = Some(b);
+                    }
+                    Pulse::MinuteMarker => {
+                        if self.accept_minute_marker(MinuteMarker::BeginOfMinute, self.second != 0)
+                        {
+                            let marker_t = t.wrapping_sub(t_diff);
+                            self.minute_phase_offset = self
+                                .minute_marker_expected
+                                .take()
+                                .map(|expected| marker_t.wrapping_sub(expected) as i32);
+                            self.minute_marker_t = Some(marker_t);
+                            self.mark_past_new_minute(MinuteOrigin::Radio);
+                        }
+                    }
+                    Pulse::Runaway => {
+                        // active runaway or first low edge
+                        if passive != 0 {
+                            self.diagnose(Diagnostic::ActiveRunaway {
+                                second: self.second,
+                            });
+                        }
+                        self.bit_buffer_a[self.second as usize] = None;
+                        self.bit_buffer_b[self.second as usize] = None;
+                    }
+                    Pulse::Unchanged => {}
+                }
+                // only corrupt a bit received with this edge, not one left over from the last minute
+                #[cfg(feature = "fault-injection")]
+                if matches!(pulse, Pulse::Bit { .. })
+                    && self.bit_buffer_a[self.second as usize].is_some()
+                    && self.take_fault(FaultKind::CorruptBit)
+                {
+                    self.bit_buffer_a[self.second as usize] =
+                        self.bit_buffer_a[self.second as usize].map(|v| !v);
+                }
+                // with fixed minute lengths, only a marker at the expected end of the minute counts
+                if active < limits.limit_0
+                    && self.end_of_minute_marker_present()
+                    && (!self.fixed_minute_length || self.second + 1 == self.get_minute_length())
+                    && self.accept_minute_marker(
+                        MinuteMarker::EndOfMinute,
+                        !(58..=60).contains(&self.second),
+                    )
+                {
+                    self.mark_new_minute(MinuteOrigin::Radio);
+                }
+                if let Some(nominal) = pulse.nominal() {
+                    self.pulse_clusters.add(nominal, active);
+                    self.update_pulse_bias(t_diff as i32 - nominal);
+                }
+            }
+            FramedEdge::PulseStart { new_second, .. } => {
+                self.new_second = new_second;
+                self.latched_flags.new_second |= self.new_second;
+                let class = if self.new_second {
+                    EdgeClass::NewSecond
+                } else {
+                    EdgeClass::Passive
+                };
+                self.record_edge(is_low_edge, t, class);
+                if self.new_second {
+                    self.notify_new_second();
+                    #[cfg(feature = "flywheel")]
+                    {
+                        self.flywheel_lock = if self.flywheel_seconds(t) == Some(1) {
+                            self.flywheel_lock.saturating_add(1)
+                        } else {
+                            0
+                        };
+                    }
+                    self.second_edge = Some(t);
+                    #[cfg(feature = "discipline")]
+                    self.phase_samples.add_boundary(t);
+                }
+            }
+            FramedEdge::PassiveRunaway { .. } => {
+                let bridged = self.bridge_missed_seconds(t);
+                let class = if bridged {
+                    EdgeClass::NewSecond
+                } else {
+                    EdgeClass::PassiveRunaway
+                };
+                self.record_edge(is_low_edge, t, class);
+                self.diagnose(Diagnostic::PassiveRunaway {
+                    second: self.second,
+                });
+                self.clear_current_bits();
+                if bridged {
+                    // the edge starts a second, the missed ones are skipped by increase_second()
+                    self.new_second = true;
+                    self.latched_flags.new_second = true;
+                    self.second_edge = Some(t);
+                    self.notify_new_second();
+                }
+            }
+            _ => {} // handled above
+        }
+    }
+
+    /// Return if minutes are assumed to last 60 seconds unless listed in the leap second table.
+    pub fn get_fixed_minute_length(&self) -> bool {
+        self.fixed_minute_length
+    }
+
+    /// Set if minutes should be assumed to last 60 seconds unless listed in the leap second table,
+    /// instead of inferring the minute length from the end-of-minute marker.
+    ///
+    /// This prevents a corrupted marker from spuriously shortening or lengthening a minute, an
+    /// end-of-minute marker then only ends the minute at its expected length.
+    ///
+    /// # Arguments
+    /// * `value` - assume fixed minute lengths
+    pub fn set_fixed_minute_length(&mut self, value: bool) {
+        self.fixed_minute_length = value;
+    }
+
+    /// Get the table of known leap seconds.
+    pub fn get_leap_table(&self) -> &'static [LeapSecondEntry] {
+        self.leap_table
+    }
+
+    /// Set the table of known leap seconds, used when minute lengths are fixed.
+    ///
+    /// # Arguments
+    /// * `table` - the leap second table
+    pub fn set_leap_table(&mut self, table: &'static [LeapSecondEntry]) {
+        self.leap_table = table;
+    }
+
+    /// Get the last leap second which was received, as the minute which contained it.
+    ///
+    /// A leap second is only reported once the minutes before and after the one containing it
+    /// were decoded too, without a jump in the date/time. It is kept until the next one.
+    pub fn get_leap_second_event(&self) -> Option<LeapSecondEntry> {
+        self.leap_second_event
+    }
+
+    /// Return if the internal invariants are checked in release builds too.
+    pub fn get_invariant_checks(&self) -> bool {
+        self.invariant_checks
+    }
+
+    /// Set if the internal invariants should be checked in release builds too, debug builds
+    /// always check them.
+    ///
+    /// A violated invariant is reported as `Diagnostic::InconsistentState` (and panics in debug
+    /// builds), which is meant to catch misuse of the decoder (e.g. calling `increase_second()`
+    /// too often) early in tests.
+    ///
+    /// # Arguments
+    /// * `value` - check the invariants
+    pub fn set_invariant_checks(&mut self, value: bool) {
+        self.invariant_checks = value;
+    }
+
+    /// Set the function which is called for each diagnostic, None to disable it.
+    ///
+    /// The hook is called from within `handle_new_edge()` and `decode_minute()`, so it should
+    /// return quickly.
+    ///
+    /// # Arguments
+    /// * `hook` - the function to call
+    pub fn set_diagnostic_hook(&mut self, hook: Option<fn(Diagnostic)>) {
+        self.diagnostic_hook = hook;
+    }
+
+    /// Pass a diagnostic to the hook and to the log crate.
+    fn diagnose(&mut self, diagnostic: Diagnostic) {
+        #[cfg(feature = "statistics")]
+        match diagnostic {
+            Diagnostic::ActiveRunaway { .. } => {
+                self.reception_quality.add_runaway();
+                self.metrics.add_active_runaway();
+            }
+            Diagnostic::PassiveRunaway { .. } => {
+                self.reception_quality.add_runaway();
+                self.metrics.add_passive_runaway();
+            }
+            Diagnostic::Misalignment { .. } => self.metrics.add_resync(),
+            Diagnostic::ParityMismatch { group } => self.metrics.add_parity_failure(group),
+            _ => {}
+        }
+        #[cfg(feature = "log")]
+        log::log!(
+            diagnostic.severity().into(),
+            "msf60_utils: {:?}",
+            diagnostic
+        );
+        if let Some(hook) = self.diagnostic_hook {
+            hook(diagnostic);
+        }
+        if let Some(observer) = self.observer {
+            if diagnostic.severity() == Severity::Warning {
+                observer.on_reception_error(diagnostic);
+            }
+        }
+    }
+
+    /// Count the received date/time bits which differ from the bits predicted by `add_minute()`.
+    ///
+    /// # Arguments
+    /// * `minute_length` - length of the received minute in seconds
+    #[cfg(feature = "statistics")]
+    fn compare_predicted_bits(&mut self, minute_length: u8) {
+        let options = EncodeOptions {
+            minute_length,
+            ..EncodeOptions::default()
+        };
+        let Some(frame) = MSFFrame::encode(&self.radio_datetime, &options) else {
+            return;
+        };
+        for second in 0..minute_length {
+            if !frame_layout::nominal_second(second, minute_length)
+                .is_some_and(|n| (17..=51).contains(&n))
+            {
+                continue;
+            }
+            if let (Some(received), Some(predicted)) = (
+                self.bit_buffer_a[second as usize],
+                frame.bits_a[second as usize],
+            ) {
+                self.bit_error_rate.add_bit(received, predicted);
+            }
+        }
+    }
+
+    /// Mark the parity groups which failed their parity to be decoded from the bits predicted
+    /// by `add_minute()`, if they differ in at most one position.
+    ///
+    /// Returns the predicted frame if any group is to be decoded from it.
+    ///
+    /// # Arguments
+    /// * `minute_length` - length of the received minute in seconds
+    /// * `offset` - shift of the date/time bits caused by a leap second
+    fn predict_failed_fields(&mut self, minute_length: u8, offset: isize) -> Option<MSFFrame> {
+        let options = EncodeOptions {
+            minute_length,
+            ..EncodeOptions::default()
+        };
+        let frame = MSFFrame::encode(&self.radio_datetime, &options)?;
+        let groups = [
+            (self.parity_1, 17, 24),
+            (self.parity_2, 25, 35),
+            (self.parity_3, 36, 38),
+            (self.parity_4, 39, 51),
+        ];
+        let mut predicted = [false; 4];
+        for (idx, &(parity, start, stop)) in groups.iter().enumerate() {
+            if parity != Some(false) {
+                continue;
+            }
+            let range = (start + offset) as usize..=(stop + offset) as usize;
+            let mismatches = range
+                .filter(|&s| self.decoded_a()[s] != frame.bits_a[s])
+                .count();
+            predicted[idx] = mismatches <= 1;
+        }
+        self.predicted_fields = PredictedFields {
+            year: predicted[0],
+            month_day: predicted[1],
+            weekday: predicted[2],
+            hour_minute: predicted[3],
+        };
+        predicted.contains(&true).then_some(frame)
+    }
+
+    /// Pass the bits of the ending second and the new second to the observer.
+    fn notify_new_second(&self) {
+        if let Some(observer) = self.observer {
+            observer.on_bit(
+                self.second,
+                self.bit_buffer_a[self.second as usize],
+                self.bit_buffer_b[self.second as usize],
+            );
+            observer.on_new_second();
+        }
+    }
+
+    /// Set the observer which is notified of the events of the decoder, None to disable it.
+    ///
+    /// The observer is called from within `handle_new_edge()` and `decode_minute()`, so it
+    /// should return quickly.
+    ///
+    /// # Arguments
+    /// * `observer` - the observer to notify
+    pub fn set_observer(&mut self, observer: Option<&'static dyn MSFObserver>) {
+        self.observer = observer;
+    }
+
+    /// Return if the internal state is consistent:
+    /// * the second counter lies within the bit buffers
+    /// * the minute length is 59, 60, or 61 seconds and the second counter does not exceed it
+    pub fn invariants_hold(&self) -> bool {
+        let minute_length = self.get_minute_length();
+        (self.second as usize) < radio_datetime_utils::BIT_BUFFER_SIZE
+            && (59..=61).contains(&minute_length)
+            && self.second <= minute_length
+    }
+
+    /// Check the invariants in debug builds or if invariant checks are enabled, reporting a
+    /// violation as `Diagnostic::InconsistentState` and panicking on it in debug builds.
+    fn check_invariants(&mut self) {
+        if !cfg!(debug_assertions) && !self.invariant_checks {
+            return;
+        }
+        let hold = self.invariants_hold();
+        if !hold {
+            self.diagnose(Diagnostic::InconsistentState);
+        }
+        debug_assert!(hold, "MSFUtils: inconsistent state");
+    }
+
+    /// Determine the length of this minute in seconds.
+    ///
+    /// If fixed minute lengths are enabled, this is 60 unless the leap second table says
+    /// otherwise, the end-of-minute marker is then not used.
+    ///
+    /// See `minute_length_estimate()` to know if the value is still provisional.
+    pub fn get_minute_length(&self) -> u8 {
+        self.minute_length_estimate().length
+    }
+
+    /// Determine the length of this minute in seconds, and if it is certain.
+    ///
+    /// Without fixed minute lengths, the length is only certain once the end-of-minute marker
+    /// has been received, i.e. at second 58, 59, or 60. Before that it defaults to 60, and at
+    /// second 59 a partially received marker gives a provisional 61.
+    pub fn minute_length_estimate(&self) -> MinuteLengthEstimate {
+        if self.fixed_minute_length {
+            MinuteLengthEstimate {
+                length: leap_table::find_minute_length(self.leap_table, &self.radio_datetime)
+                    .unwrap_or(60),
+                certain: true,
+            }
+        } else if (58..=60).contains(&self.second) && self.search_eom_marker(0) {
+            MinuteLengthEstimate {
+                length: self.second + 1,
+                certain: true,
+            }
+        } else if self.second == 59 && self.search_eom_marker(1) {
+            MinuteLengthEstimate {
+                length: 61,
+                certain: false,
+            }
+        } else {
+            MinuteLengthEstimate {
+                length: 60,
+                certain: false,
+            }
+        }
+    }
+
+    /// Return the seconds after the current one which must still be received in this minute for
+    /// `decode_minute()` to succeed, which is empty if none are left.
+    ///
+    /// Relaxed checks need the date/time, the parities, and the DST bits (17-58), strict checks
+    /// also need DUT1 and the end-of-minute marker (1-59). The indices shift by one in a minute
+    /// with a leap second.
+    ///
+    /// Duty-cycled receivers can power down outside these seconds, but must keep advancing the
+    /// second counter.
+    ///
+    /// # Arguments
+    /// * `strict_checks` - the checks which will be passed to `decode_minute()`
+    pub fn remaining_required_seconds(&self, strict_checks: bool) -> RangeInclusive<u8> {
+        let minute_length = self.get_minute_length();
+        let (first, last) = if strict_checks {
+            (1, minute_length - 1)
+        } else {
+            (minute_length - 43, minute_length - 2)
+        };
+        first.max(self.second + 1)..=last
+    }
+
+    /// Return if the end-of-minute marker (0111_1110) is present at the end of the A bits.
+    ///
+    /// This method must be called _before_ `increase_second()`
+    pub fn end_of_minute_marker_present(&self) -> bool {
+        self.search_eom_marker(0)
+    }
+
+    /// Get the maximum number of seconds `eom_marker_eta()` looks ahead.
+    pub fn get_prediction_horizon(&self) -> u8 {
+        self.prediction_horizon
+    }
+
+    /// Set the maximum number of seconds `eom_marker_eta()` looks ahead, [1..8)
+    ///
+    /// A longer horizon predicts the end of the minute earlier, but less reliably because fewer
+    /// bits of the marker are matched.
+    ///
+    /// # Arguments
+    /// * `value` - the value to set the horizon to.
+    pub fn set_prediction_horizon(&mut self, value: u8) {
+        if (1..EOM_MARKER_LENGTH).contains(&value) {
+            self.prediction_horizon = value;
+        }
+    }
+
+    /// Return the number of seconds until the end-of-minute marker (0111_1110) is complete, if
+    /// the last A bits match its beginning within the prediction horizon.
+    ///
+    /// Only the beginning of a marker which would end the minute at second 58, 59, or 60 is
+    /// matched.
+    ///
+    /// 0 means that the marker is present, so the minute ends with this second.
+    pub fn eom_marker_eta(&self) -> Option<u8> {
+        (0..=self.prediction_horizon).find(|horizon| self.search_eom_marker(*horizon))
+    }
+
+    /// Return how far the current minute has been received, counting only the bits needed for
+    /// decoding it under the current configuration.
+    ///
+    /// These are the date/time bits, the DST bits, and unless `get_a_only()` is set, the parity
+    /// bits and (without the `no-dut1` feature) the DUT1 bits. Bits with a fixed value, like the
+    /// markers, are not counted.
+    pub fn minute_progress(&self) -> MinuteProgress {
+        let minute_length = self.get_minute_length();
+        let needed_b = |field: &frame_layout::FrameField| {
+            field.fixed.is_none()
+                && if self.a_only {
+                    field.kind == frame_layout::FieldKind::Dst
+                } else {
+                    cfg!(not(feature = "no-dut1")) || field.kind != frame_layout::FieldKind::Dut1
+                }
+        };
+        let (mut received, mut required) = (0, 0);
+        for second in 0..minute_length {
+            let fields = [
+                (
+                    frame_layout::field_a(second, minute_length).is_some_and(|f| f.fixed.is_none()),
+                    self.bit_buffer_a[second as usize],
+                ),
+                (
+                    frame_layout::field_b(second, minute_length).is_some_and(needed_b),
+                    self.bit_buffer_b[second as usize],
+                ),
+            ];
+            for (needed, bit) in fields {
+                if needed {
+                    required += 1;
+                    if second <= self.second && bit.is_some() {
+                        received += 1;
+                    }
+                }
+            }
+        }
+        MinuteProgress {
+            second: self.second,
+            received,
+            required,
+            percent: (received as u16 * 100 / required.max(1) as u16) as u8,
+        }
+    }
+
+    /// Return the probability in percent that the end-of-minute marker completes as predicted
+    /// by `eom_marker_eta()`, or 0 if no completion is predicted.
+    ///
+    /// This is the chance that the matched bits are not a coincidence of random A bits, i.e.
+    /// 50% for one matched bit and 99% for the full marker.
+    pub fn marker_completion_probability(&self) -> u8 {
+        self.eom_marker_eta().map_or(0, |eta| {
+            let combinations = 1u16 << (EOM_MARKER_LENGTH - eta);
+            (100 * (combinations - 1) / combinations) as u8
+        })
+    }
+
+    /// Return the time stamp at which the last received begin-of-minute marker started, in the
+    /// same unit and time base as passed to `handle_new_edge()`.
+    ///
+    /// This is the top of the minute as received, e.g. to be passed to `unix_time_at()` or
+    /// `compare_against()` when disciplining a clock.
+    pub fn get_minute_marker_t(&self) -> Option<u32> {
+        self.minute_marker_t
+    }
+
+    /// Return the offset in microseconds of the last received begin-of-minute marker from the
+    /// top of the minute according to the local time base, i.e. the previous marker plus the
+    /// length of its minute.
+    ///
+    /// A positive offset means that the marker arrived late, so the local timer runs fast. The
+    /// offset is updated with every marker which follows a marker one minute earlier, otherwise
+    /// it is None.
+    pub fn get_minute_phase_offset(&self) -> Option<i32> {
+        self.minute_phase_offset
+    }
+
+    /// Return the time stamp at which the begin-of-minute marker of the next minute is expected
+    /// to start, in the same unit and time base as passed to `handle_new_edge()`.
+    ///
+    /// The prediction is based on the start of the current second and the length of the
+    /// current minute, so it is only meaningful once the second counter is synchronized to a
+    /// begin-of-minute marker. Returns None if no second has started yet.
+    ///
+    /// This method must be called _after_ `increase_second()`
+    pub fn predicted_minute_marker_at(&self) -> Option<u32> {
+        let seconds_left = self.get_minute_length().saturating_sub(self.second) as u32;
+        self.second_start
+            .map(|t| t.wrapping_add(seconds_left * 1_000_000))
+    }
+
+    /// Estimate the number of microseconds until the next significant edge is expected, so a
+    /// cooperative scheduler can decide whether to run a long task before it.
+    ///
+    /// During a pulse, this is the end of the shortest (100 ms) pulse. After a pulse, it is the
+    /// start of the second pulse of a possible (0,1) pair, or the start of the next second.
+    /// Returns 0 if the edge is overdue, or None if no second has started yet.
+    ///
+    /// # Arguments
+    /// * `t` - the current time stamp, in the same unit and time base as passed to
+    ///   `handle_new_edge()`
+    pub fn time_budget_until_next_edge(&self, t: u32) -> Option<u32> {
+        let start = self.second_edge.or(self.second_start)?;
+        let (is_low_edge, last_t) = self.last_edge?;
+        if !is_low_edge {
+            let active = radio_datetime_helpers::time_diff(last_t, t);
+            return Some(ACTIVE_0_NOMINAL.saturating_sub(active));
+        }
+        let elapsed = radio_datetime_helpers::time_diff(start, t);
+        let next = if elapsed < 2 * ACTIVE_0_NOMINAL {
+            2 * ACTIVE_0_NOMINAL
+        } else {
+            1_000_000
+        };
+        Some(next.saturating_sub(elapsed))
+    }
+
+    /// Helper for end_of_minute_marker_present(), get_minute_length(), and eom_marker_eta()
+    ///
+    /// A partial marker only matches if it would be complete at second 58, 59, or 60, i.e. if it
+    /// started at second 52 of the minute adjusted for a leap second. A complete marker matches
+    /// at any second, so that it can realign the second counter.
+    ///
+    /// # Arguments
+    /// * `horizon` - number of marker bits still to be received
+    fn search_eom_marker(&self, horizon: u8) -> bool {
+        if self.second + 1 + horizon < EOM_MARKER_LENGTH {
+            return false; // not enough bits to test
+        }
+        if horizon > 0 && !(58..=60).contains(&(self.second + horizon)) {
+            return false;
+        }
+        const MARKER: [bool; EOM_MARKER_LENGTH as usize] =
+            [false, true, true, true, true, true, true, false];
+        for (idx, bit) in self.bit_buffer_a
+            [(self.second + 1 + horizon - EOM_MARKER_LENGTH) as usize..=self.second as usize]
+            .iter()
+            .enumerate()
+        {
+            if bit.is_none() || *bit != Some(MARKER[idx]) {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Increase or reset `second`.
+    ///
+    /// Returns if the second counter was increased/wrapped normally (true)
+    /// or due to an overflow (false).
+    ///
+    /// This method must be called _after_ `decode_minute()`, `handle_new_edge()`,
+    /// `set_current_bit_a()`, `set_current_bit_b()`, `end_of_minute_marker_present()`
+    /// and `force_new_minute()`.
+    pub fn increase_second(&mut self) -> bool {
+        #[cfg(feature = "fault-injection")]
+        if self.take_fault(FaultKind::DelaySecond) {
+            return true;
+        }
+        let minute_length = self.get_minute_length();
+        if ::core::mem::take(&mut self.valid_second) {
+            self.bad_seconds = 0;
+        } else {
+            self.bad_seconds = self.bad_seconds.saturating_add(1);
+        }
+        self.watchdog_kick = self.bad_seconds < self.watchdog_tolerance;
+        // without a new edge, the second is assumed to start one second after the previous one
+        self.second_start = match self.second_edge.take() {
+            Some(t) => Some(t),
+            None => self.second_start.map(|t| t.wrapping_add(1_000_000)),
+        };
+        #[cfg_attr(not(feature = "flywheel"), allow(unused_mut))]
+        let mut res =
+            RadioDateTimeUtils::increase_second(&mut self.second, self.new_minute, minute_length);
+        #[cfg(feature = "flywheel")]
+        for _ in 0..::core::mem::take(&mut self.missed_seconds) {
+            self.clear_current_bits();
+            res =
+                RadioDateTimeUtils::increase_second(&mut self.second, false, minute_length) && res;
+        }
+        self.check_invariants();
+        res
+    }
+
+    /// Return the number of whole seconds between the start of the current second and the given
+    /// time stamp, if the time stamp is within `FLYWHEEL_TOLERANCE` of a second boundary.
+    ///
+    /// # Arguments
+    /// * `t` - time stamp of a rising edge, in microseconds
+    #[cfg(feature = "flywheel")]
+    fn flywheel_seconds(&self, t: u32) -> Option<u32> {
+        let elapsed = radio_datetime_helpers::time_diff(self.second_edge.or(self.second_start)?, t);
+        let seconds = elapsed.saturating_add(500_000) / 1_000_000;
+        if elapsed.abs_diff(seconds * 1_000_000) <= FLYWHEEL_TOLERANCE {
+            Some(seconds)
+        } else {
+            None
+        }
+    }
+
+    /// Let a locked flywheel bridge the seconds missed before a passive runaway, so that its edge
+    /// starts a new second. Returns if the seconds were bridged.
+    ///
+    /// # Arguments
+    /// * `t` - time stamp of the rising edge ending the passive runaway, in microseconds
+    #[cfg(feature = "flywheel")]
+    fn bridge_missed_seconds(&mut self, t: u32) -> bool {
+        match self.flywheel_seconds(t) {
+            Some(seconds)
+                if self.decoder_config.flywheel
+                    && self.flywheel_lock >= FLYWHEEL_LOCK_SECONDS
+                    && (2..=FLYWHEEL_MAX_MISSED + 1).contains(&seconds) =>
+            {
+                self.missed_seconds = (seconds - 1) as u8;
+                true
+            }
+            _ => {
+                self.flywheel_lock = 0;
+                false
+            }
+        }
+    }
+
+    /// Without the flywheel, missed seconds are never bridged.
+    #[cfg(not(feature = "flywheel"))]
+    fn bridge_missed_seconds(&mut self, _t: u32) -> bool {
+        false
+    }
+
+    /// Return if the flywheel is enabled.
+    #[cfg(feature = "flywheel")]
+    pub fn get_flywheel(&self) -> bool {
+        self.decoder_config.flywheel
+    }
+
+    /// Set if the flywheel is enabled.
+    ///
+    /// Once a number of consecutive seconds started on time, the flywheel is locked. When the
+    /// pulses of one or more seconds are then missed, the next second which starts on time is
+    /// still reported as a new second, and `increase_second()` skips the missed seconds with
+    /// their bits set to None. This keeps the second counter aligned instead of shifting every
+    /// later bit of the minute.
+    ///
+    /// # Arguments
+    /// * `value` - enable the flywheel
+    #[cfg(feature = "flywheel")]
+    pub fn set_flywheel(&mut self, value: bool) {
+        self.decoder_config.flywheel = value;
+    }
+
+    /// Return if the flywheel is locked to the second boundaries.
+    #[cfg(feature = "flywheel")]
+    pub fn get_flywheel_locked(&self) -> bool {
+        self.decoder_config.flywheel && self.flywheel_lock >= FLYWHEEL_LOCK_SECONDS
+    }
+
+    /// Call add_minute() on `self.radio_datetime` and passes on that result.
+    ///
+    /// This could be useful for consumers just wanting to advance their current date/time.
+    pub fn add_minute(&mut self) -> bool {
+        self.radio_datetime.clear_jumps();
+        self.radio_datetime.add_minute()
+    }
+
+    /// Decode the time broadcast during the last minute and clear `first_minute` when appropriate.
+    ///
+    /// This method must be called _before_ `increase_second()`
+    ///
+    /// # Arguments
+    /// * `strict_checks` - checks all parities, DUT1 validity, and EOM marker presence when setting
+    ///                     date/time and clearing self.first_minute
+    #[deprecated(since = "0.5.0", note = "use decode_minute() from the api2 module")]
+    pub fn decode_time(&mut self, strict_checks: bool) {
+        let _ = self.decode_minute(strict_checks);
+    }
+
+    /// Helper for decode_minute(), see there.
+    fn decode(&mut self, strict_checks: bool) {
+        self.radio_datetime.clear_jumps();
+        #[cfg(feature = "statistics")]
+        {
+            self.confidence_scores = ConfidenceScores::default();
+        }
+        let minute_length = self.get_minute_length(); // calculation depends on self.second
+        let rdt = &self.radio_datetime;
+        // the date/time of this minute is only known if the previous one was decoded
+        let leap_second = if self.minutes_since_decode == Some(0) {
+            LeapSecondEntry::new(rdt, minute_length)
+        } else {
+            None
+        };
+        #[cfg(feature = "statistics")]
+        let old_date = match (rdt.get_year(), rdt.get_month(), rdt.get_day()) {
+            (Some(year), Some(month), Some(day)) => Some((year, month, day)),
+            _ => None,
+        };
+        let mut added_minute = false;
+        self.minutes_since_decode = self.minutes_since_decode.map(|m| m.saturating_add(1));
+        if !self.first_minute {
+            added_minute = self.radio_datetime.add_minute();
+        }
+        if self.second + 1 == minute_length {
+            #[cfg(feature = "statistics")]
+            if added_minute {
+                self.compare_predicted_bits(minute_length);
+            }
+            let offset: isize = match 60.cmp(&minute_length) {
+                Ordering::Less => 1,
+                Ordering::Equal => 0,
+                Ordering::Greater => -1,
+            };
+            #[cfg(feature = "voting")]
+            {
+                self.voted_bits = 0;
+                if self.voting_depth > 0 {
+                    // vote into a copy, so that the received bits are kept
+                    self.decoded_buffer_a = self.bit_buffer_a;
+                    self.decoded_buffer_b = self.bit_buffer_b;
+                    // the date bits of the previous day are of no use anymore
+                    let rdt = &self.radio_datetime;
+                    if added_minute && rdt.get_hour() == Some(0) && rdt.get_minute() == Some(0) {
+                        self.voter.clear();
+                    }
+                    self.voted_bits = self.voter.vote(
+                        self.voting_depth,
+                        &mut self.decoded_buffer_a,
+                        &mut self.decoded_buffer_b,
+                        minute_length,
+                    );
+                }
+            }
+
+            self.parity_1 = radio_datetime_helpers::get_parity(
+                self.decoded_a(),
+                (17 + offset) as usize,
+                (24 + offset) as usize,
+                self.decoded_b()[(54 + offset) as usize],
+            );
+            self.parity_2 = radio_datetime_helpers::get_parity(
+                self.decoded_a(),
+                (25 + offset) as usize,
+                (35 + offset) as usize,
+                self.decoded_b()[(55 + offset) as usize],
+            );
+            self.parity_3 = radio_datetime_helpers::get_parity(
+                self.decoded_a(),
+                (36 + offset) as usize,
+                (38 + offset) as usize,
+                self.decoded_b()[(56 + offset) as usize],
+            );
+            self.parity_4 = radio_datetime_helpers::get_parity(
+                self.decoded_a(),
+                (39 + offset) as usize,
+                (51 + offset) as usize,
+                self.decoded_b()[(57 + offset) as usize],
+            );
+            self.parity_bits = [
+                self.compute_parity_bits(17 + offset, 24 + offset, 54 + offset),
+                self.compute_parity_bits(25 + offset, 35 + offset, 55 + offset),
+                self.compute_parity_bits(36 + offset, 38 + offset, 56 + offset),
+                self.compute_parity_bits(39 + offset, 51 + offset, 57 + offset),
+            ];
+            if self.a_only {
+                // the parity bits are in the B channel
+                self.parity_1 = None;
+                self.parity_2 = None;
+                self.parity_3 = None;
+                self.parity_4 = None;
+            }
+
+            #[cfg(feature = "statistics")]
+            let old_dut1 = self.get_dut1();
+            #[cfg(not(feature = "no-dut1"))]
+            if !self.a_only {
+                self.dut1 = None;
+                self.dut1_suspicious = None;
+                // bit 16 is dropped in case of a negative leap second
+                let stop = if offset == -1 { 15 } else { 16 };
+                if let Some(dut1p) = msf_helpers::get_unary_value(self.decoded_b(), 1, 8) {
+                    if let Some(dut1n) = msf_helpers::get_unary_value(self.decoded_b(), 9, stop) {
+                        let dut1 = dut1p - dut1n;
+                        if dut1p * dut1n == 0 && dut1.unsigned_abs() <= self.dut1_limit {
+                            self.dut1 = Some(dut1);
+                        } else {
+                            self.dut1_suspicious = Some(dut1);
+                        }
+                    }
+                }
+                #[cfg(feature = "dut1-filter")]
+                self.filter_dut1();
+            }
+
+            self.minute_crc = Some(msf_helpers::get_minute_crc(
+                &self.bit_buffer_a[..minute_length as usize],
+                &self.bit_buffer_b[..minute_length as usize],
+            ));
+
+            for (group, parity) in [self.parity_1, self.parity_2, self.parity_3, self.parity_4]
+                .iter()
+                .enumerate()
+            {
+                if *parity == Some(false) {
+                    self.diagnose(Diagnostic::ParityMismatch {
+                        group: group as u8 + 1,
+                    });
+                }
+            }
+
+            self.predicted_fields = PredictedFields::default();
+            let prediction = if self.predictive_fallback && added_minute && !self.a_only {
+                self.predict_failed_fields(minute_length, offset)
+            } else {
+                None
+            };
+
+            // without the B channel, only the end-of-minute marker can be checked
+            let predicted = self.predicted_fields;
+            let (p1, p2, p3, p4) = (
+                self.a_only || self.parity_1 == Some(true) || predicted.year,
+                self.a_only || self.parity_2 == Some(true) || predicted.month_day,
+                self.a_only || self.parity_3 == Some(true) || predicted.weekday,
+                self.a_only || self.parity_4 == Some(true) || predicted.hour_minute,
+            );
+            // predicted fields do not count as passing their parity for strict checks
+            let parities_ok = [self.parity_1, self.parity_2, self.parity_3, self.parity_4]
+                .iter()
+                .all(|parity| *parity == Some(true));
+            let strict_ok = (self.a_only || (parities_ok && self.dut1_ok()))
+                && self.end_of_minute_marker_present();
+
+            // the predicted fields are decoded from the predicted bits
+            let bits_a = |predicted: bool| match &prediction {
+                Some(frame) if predicted => &frame.bits_a,
+                _ => self.decoded_a(),
+            };
+            let year = radio_datetime_helpers::get_bcd_value(
+                bits_a(predicted.year),
+                (24 + offset) as usize,
+                (17 + offset) as usize,
+            );
+            let month = radio_datetime_helpers::get_bcd_value(
+                bits_a(predicted.month_day),
+                (29 + offset) as usize,
+                (25 + offset) as usize,
+            );
+            let weekday = radio_datetime_helpers::get_bcd_value(
+                bits_a(predicted.weekday),
+                (38 + offset) as usize,
+                (36 + offset) as usize,
+            );
+            let day = radio_datetime_helpers::get_bcd_value(
+                bits_a(predicted.month_day),
+                (35 + offset) as usize,
+                (30 + offset) as usize,
+            );
+            let hour = radio_datetime_helpers::get_bcd_value(
+                bits_a(predicted.hour_minute),
+                (44 + offset) as usize,
+                (39 + offset) as usize,
+            );
+            let minute = radio_datetime_helpers::get_bcd_value(
+                bits_a(predicted.hour_minute),
+                (51 + offset) as usize,
+                (45 + offset) as usize,
+            );
+            let rdt = self.radio_datetime;
+            let policy = self.decoder_config.merge_policy;
+            let merge_year =
+                policy.merge(&mut self.field_confidence.year, p1, rdt.get_year(), year);
+            let merge_month_day = policy.merge(
+                &mut self.field_confidence.month_day,
+                p2,
+                (rdt.get_month(), rdt.get_day()),
+                (month, day),
+            );
+            let merge_weekday = policy.merge(
+                &mut self.field_confidence.weekday,
+                p3,
+                rdt.get_weekday(),
+                weekday,
+            );
+            let merge_hour_minute = policy.merge(
+                &mut self.field_confidence.hour_minute,
+                p4,
+                (rdt.get_hour(), rdt.get_minute()),
+                (hour, minute),
+            );
+
+            #[cfg(feature = "statistics")]
+            {
+                // the held date/time is the one predicted from the previous minute
+                let agrees = |held: Option<u8>, received: Option<u8>| {
+                    added_minute.then_some(held == received)
+                };
+                let dst_bit = self.decoded_b()[(58 + offset) as usize];
+                let dst_complete =
+                    dst_bit.is_some() && self.decoded_b()[(53 + offset) as usize].is_some();
+                let held_summer = rdt
+                    .get_dst()
+                    .map(|dst| dst & radio_datetime_utils::DST_SUMMER != 0);
+                self.confidence_scores = ConfidenceScores {
+                    year: confidence::score(
+                        year.is_some(),
+                        self.parity_1,
+                        agrees(rdt.get_year(), year),
+                    ),
+                    month: confidence::score(
+                        month.is_some(),
+                        self.parity_2,
+                        agrees(rdt.get_month(), month),
+                    ),
+                    day: confidence::score(
+                        day.is_some(),
+                        self.parity_2,
+                        agrees(rdt.get_day(), day),
+                    ),
+                    weekday: confidence::score(
+                        weekday.is_some(),
+                        self.parity_3,
+                        agrees(rdt.get_weekday(), weekday),
+                    ),
+                    hour: confidence::score(
+                        hour.is_some(),
+                        self.parity_4,
+                        agrees(rdt.get_hour(), hour),
+                    ),
+                    minute: confidence::score(
+                        minute.is_some(),
+                        self.parity_4,
+                        agrees(rdt.get_minute(), minute),
+                    ),
+                    dst: confidence::score(
+                        dst_complete,
+                        Some(true),
+                        held_summer.map(|summer| Some(summer) == dst_bit),
+                    ),
+                    dut1: confidence::score(
+                        !self.a_only && self.get_dut1().is_some(),
+                        Some(true),
+                        old_dut1.map(|dut1| Some(dut1) == self.get_dut1()),
+                    ),
+                };
+            }
+
+            self.radio_datetime.set_year(
+                year,
+                if strict_checks { strict_ok } else { merge_year },
+                added_minute && !self.first_minute,
+            );
+            self.radio_datetime.set_month(
+                month,
+                if strict_checks {
+                    strict_ok
+                } else {
+                    merge_month_day
+                },
+                added_minute && !self.first_minute,
+            );
+            self.radio_datetime.set_weekday(
+                weekday,
+                if strict_checks {
+                    strict_ok
+                } else {
+                    merge_weekday
+                },
+                added_minute && !self.first_minute,
+            );
+            self.radio_datetime.set_day(
+                day,
+                if strict_checks {
+                    strict_ok
+                } else {
+                    p1 && p3 && merge_month_day
+                },
+                added_minute && !self.first_minute,
+            );
+
+            self.radio_datetime.set_hour(
+                hour,
+                if strict_checks {
+                    strict_ok
+                } else {
+                    merge_hour_minute
+                },
+                added_minute && !self.first_minute,
+            );
+            self.radio_datetime.set_minute(
+                minute,
+                if strict_checks {
+                    strict_ok
+                } else {
+                    merge_hour_minute
+                },
+                added_minute && !self.first_minute,
+            );
+
+            self.radio_datetime.set_dst(
+                self.decoded_b()[(58 + offset) as usize],
+                self.decoded_b()[(53 + offset) as usize],
+                added_minute && !self.first_minute,
+            );
+
+            if if strict_checks {
+                strict_ok
+            } else {
+                self.a_only || self.dut1_ok()
+            } && self.radio_datetime.is_valid()
+            {
+                // allow displaying of information after the first properly decoded minute
+                self.first_minute = false;
+                #[cfg(feature = "statistics")]
+                if let Some(gap) = self.minutes_since_decode {
+                    self.gap_histogram.add_gap(gap);
+                }
+                self.minutes_since_decode = Some(0);
+            }
+
+            self.radio_datetime.bump_minutes_running();
+        }
+        let rdt = &self.radio_datetime;
+        let step = rdt.get_jump_year()
+            || rdt.get_jump_month()
+            || rdt.get_jump_day()
+            || rdt.get_jump_weekday()
+            || rdt.get_jump_hour()
+            || rdt.get_jump_minute();
+        #[cfg(feature = "statistics")]
+        self.audit.add_minute(
+            self.minutes_since_decode == Some(0),
+            step,
+            old_date,
+            rdt.get_day(),
+        );
+        #[cfg(feature = "statistics")]
+        self.metrics
+            .add_minute(self.minutes_since_decode == Some(0));
+        // only a marker of this minute predicts the next one
+        let minute_us = minute_length as u32 * 1_000_000;
+        self.minute_marker_expected = self
+            .minute_marker_t
+            .filter(|&marker_t| {
+                self.second_start.is_some_and(|start| {
+                    radio_datetime_helpers::time_diff(marker_t, start) < minute_us
+                })
+            })
+            .map(|marker_t| marker_t.wrapping_add(minute_us));
+        if self.auto_spike_limit {
+            let spike_limit = self.spike_tuner.end_minute();
+            self.spike_limit_low = spike_limit;
+            self.spike_limit_high = spike_limit;
+        }
+        #[cfg(feature = "statistics")]
+        {
+            let valid_seconds = (0..minute_length as usize)
+                .filter(|&s| {
+                    self.bit_buffer_a[s].is_some()
+                        && (self.a_only || self.bit_buffer_b[s].is_some())
+                })
+                .count() as u8;
+            let parity_failures = [self.parity_1, self.parity_2, self.parity_3, self.parity_4]
+                .iter()
+                .filter(|&&p| p == Some(false))
+                .count() as u8;
+            self.reception_quality
+                .end_minute(valid_seconds, minute_length, parity_failures);
+        }
+        let confirmed = self.minutes_since_decode == Some(0) && !step;
+        #[cfg(feature = "single-shot")]
+        {
+            self.sync_streak = if confirmed {
+                self.sync_streak.saturating_add(1)
+            } else {
+                0
+            };
+        }
+        if let Some(pending) = self.leap_second_pending.take() {
+            if confirmed {
+                self.leap_second_event = Some(pending);
+            }
+        }
+        if confirmed {
+            self.leap_second_pending = leap_second;
+        }
+        if self.minutes_since_decode == Some(0) {
+            self.last_decoded = Some(self.snapshot());
+            #[cfg(feature = "single-shot")]
+            if self.decoder_config.sync_minutes > 0
+                && self.sync_snapshot.is_none()
+                && self.sync_streak >= self.decoder_config.sync_minutes
+            {
+                self.sync_snapshot = self.last_decoded;
+            }
+            if let Some(observer) = self.observer {
+                observer.on_minute_decoded(&self.radio_datetime);
+            }
+        }
+    }
+
+    /// Inject a fault which is applied at the next opportunity, replacing any pending fault.
+    ///
+    /// This is meant for testing how an application reacts to reception problems.
+    ///
+    /// # Arguments
+    /// * `fault` - the kind of fault to inject
+    #[cfg(feature = "fault-injection")]
+    pub fn inject_fault(&mut self, fault: FaultKind) {
+        self.pending_fault = Some(fault);
+    }
+
+    /// Return the fault which is still waiting to be applied, if any.
+    #[cfg(feature = "fault-injection")]
+    pub fn get_pending_fault(&self) -> Option<FaultKind> {
+        self.pending_fault
+    }
+
+    /// Helper to consume the pending fault if it is of the given kind.
+    #[cfg(feature = "fault-injection")]
+    fn take_fault(&mut self, fault: FaultKind) -> bool {
+        if self.pending_fault == Some(fault) {
+            self.pending_fault = None;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Fast-forward the date/time by a number of minutes as if they were propagated without
+    /// reception, applying announced DST changes on the way.
+    ///
+    /// This is meant for testing calendar logic of an application (alarms, schedules) without
+    /// generating the edges of all these minutes.
+    ///
+    /// Returns false if the date/time could not be advanced because it is not fully known.
+    ///
+    /// # Arguments
+    /// * `minutes` - number of minutes to advance
+    #[cfg(feature = "fault-injection")]
+    pub fn advance_minutes(&mut self, minutes: u32) -> bool {
+        for _ in 0..minutes {
+            if !self.radio_datetime.add_minute() {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Helper for handle_new_edge() to correct the length of an active (low) or passive (high)
+    /// pulse for the estimated pulse bias, if enabled.
+    fn compensate_pulse(&self, length: u32, active: bool) -> u32 {
+        if !self.decoder_config.pulse_bias_compensation || length == 0 {
+            return length;
+        }
+        match self.get_pulse_bias() {
+            Some(bias) => {
+                let corrected = if active {
+                    length as i64 - bias as i64
+                } else {
+                    length as i64 + bias as i64
+                };
+                corrected.clamp(0, u32::MAX as i64) as u32
+            }
+            None => length,
+        }
+    }
+
+    /// Helper for handle_new_edge() to update the moving average of the pulse bias.
+    fn update_pulse_bias(&mut self, deviation: i32) {
+        let deviation = Q16::from_ratio(deviation as i64, 1_000);
+        if self.pulse_bias_count == 0 {
+            self.pulse_bias = deviation;
+        } else {
+            self.pulse_bias = self.pulse_bias.ema(deviation, 4);
+        }
+        self.pulse_bias_count = self.pulse_bias_count.saturating_add(1);
+    }
+
+    /// Return the A bits decode() works on, the voted ones if voting is enabled.
+    fn decoded_a(&self) -> &[Option<bool>; radio_datetime_utils::BIT_BUFFER_SIZE] {
+        #[cfg(feature = "voting")]
+        if self.voting_depth > 0 {
+            return &self.decoded_buffer_a;
+        }
+        &self.bit_buffer_a
+    }
+
+    /// Return the B bits decode() works on, the voted ones if voting is enabled.
+    fn decoded_b(&self) -> &[Option<bool>; radio_datetime_utils::BIT_BUFFER_SIZE] {
+        #[cfg(feature = "voting")]
+        if self.voting_depth > 0 {
+            return &self.decoded_buffer_b;
+        }
+        &self.bit_buffer_b
+    }
+
+    /// Helper for decode() to compute the (odd) parity bit over the given A bits and to
+    /// fetch the received one from the B bits.
+    fn compute_parity_bits(&self, start: isize, stop: isize, parity: isize) -> ParityBits {
+        ParityBits {
+            computed: radio_datetime_helpers::get_parity(
+                self.decoded_a(),
+                start as usize,
+                stop as usize,
+                Some(true),
+            ),
+            received: self.decoded_b()[parity as usize],
+        }
+    }
+}
 
 impl Default for MSFUtils {
     fn default() -> Self {
@@ -491,39 +3017,443 @@ impl Default for MSFUtils {
     }
 }
 
+impl EdgeFraming for MSFUtils {
+    fn edge_state(&mut self) -> (&mut bool, &mut u32, &mut u32) {
+        (
+            &mut self.before_first_edge,
+            &mut self.t0,
+            &mut self.old_t_diff,
+        )
+    }
+}
+
+/// Settings rejected by `MSFUtilsBuilder::build()`, named after the builder method.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConfigError {
+    SpikeLimit,
+    SpikeLimitLow,
+    SpikeLimitHigh,
+    PredictionHorizon,
+    RefractoryWindow,
+    PassiveRunaway,
+    DecoderConfig,
+    #[cfg(feature = "marker-policy")]
+    MarkerPolicy,
+    Escalation,
+    Dut1Limit,
+    #[cfg(feature = "dut1-filter")]
+    Dut1Confirmations,
+    #[cfg(feature = "voting")]
+    VotingDepth,
+    #[cfg(feature = "statistics")]
+    EdgeRingLength,
+    WatchdogTolerance,
+}
+
+/// Builder for `MSFUtils`, obtained via `MSFUtils::builder()`.
+pub struct MSFUtilsBuilder {
+    msf: MSFUtils,
+    error: Option<ConfigError>,
+}
+
+impl MSFUtilsBuilder {
+    fn new() -> Self {
+        Self {
+            msf: MSFUtils::new(),
+            error: None,
+        }
+    }
+
+    /// Remember the first setting which was rejected.
+    fn check(mut self, accepted: bool, error: ConfigError) -> Self {
+        if !accepted && self.error.is_none() {
+            self.error = Some(error);
+        }
+        self
+    }
+
+    /// Set the spike limit for both edge polarities in microseconds, [0(off)..ACTIVE_0_LIMIT)
+    ///
+    /// Out of range values are rejected by `build()`.
+    ///
+    /// # Arguments
+    /// * `value` - the value to set the spike limit to.
+    pub fn spike_limit(mut self, value: u32) -> Self {
+        self.msf.set_spike_limit(value);
+        let accepted = self.msf.get_spike_limit_low() == value;
+        self.check(accepted, ConfigError::SpikeLimit)
+    }
+
+    /// Set the spike limit for high-to-low edges in microseconds, [0(off)..ACTIVE_0_LIMIT)
+    ///
+    /// Out of range values are rejected by `build()`.
+    ///
+    /// # Arguments
+    /// * `value` - the value to set the spike limit to.
+    pub fn spike_limit_low(mut self, value: u32) -> Self {
+        self.msf.set_spike_limit_low(value);
+        let accepted = self.msf.get_spike_limit_low() == value;
+        self.check(accepted, ConfigError::SpikeLimitLow)
+    }
+
+    /// Set the spike limit for low-to-high edges in microseconds, [0(off)..ACTIVE_0_LIMIT)
+    ///
+    /// Out of range values are rejected by `build()`.
+    ///
+    /// # Arguments
+    /// * `value` - the value to set the spike limit to.
+    pub fn spike_limit_high(mut self, value: u32) -> Self {
+        self.msf.set_spike_limit_high(value);
+        let accepted = self.msf.get_spike_limit_high() == value;
+        self.check(accepted, ConfigError::SpikeLimitHigh)
+    }
+
+    /// Set if the spike limit is tuned from the observed short pulses, see
+    /// `MSFUtils::set_auto_spike_limit()`.
+    ///
+    /// # Arguments
+    /// * `value` - tune the spike limit
+    pub fn auto_spike_limit(mut self, value: bool) -> Self {
+        self.msf.set_auto_spike_limit(value);
+        self
+    }
+
+    /// Set the maximum number of seconds `eom_marker_eta()` looks ahead, [1..8)
+    ///
+    /// Out of range values are rejected by `build()`.
+    ///
+    /// # Arguments
+    /// * `value` - the value to set the horizon to.
+    pub fn prediction_horizon(mut self, value: u8) -> Self {
+        self.msf.set_prediction_horizon(value);
+        let accepted = self.msf.get_prediction_horizon() == value;
+        self.check(accepted, ConfigError::PredictionHorizon)
+    }
+
+    /// Set the level of the receiver output during the carrier-off pulses.
+    ///
+    /// # Arguments
+    /// * `value` - the convention of the receiver
+    pub fn signal_convention(mut self, value: SignalConvention) -> Self {
+        self.msf.set_signal_convention(value);
+        self
+    }
+
+    /// Set the window in microseconds within which a second edge of the same direction is
+    /// ignored, [0(off)..ACTIVE_0_LIMIT)
+    ///
+    /// Out of range values are rejected by `build()`.
+    ///
+    /// # Arguments
+    /// * `value` - the value to set the window to.
+    pub fn refractory_window(mut self, value: u32) -> Self {
+        self.msf.set_refractory_window(value);
+        let accepted = self.msf.get_refractory_window() == value;
+        self.check(accepted, ConfigError::RefractoryWindow)
+    }
+
+    /// Set the time in microseconds without a pulse after which the signal is considered lost,
+    /// [1_000_000..TIMER_DISCONTINUITY)
+    ///
+    /// Out of range values are rejected by `build()`.
+    ///
+    /// # Arguments
+    /// * `value` - the value to set the limit to.
+    pub fn passive_runaway(mut self, value: u32) -> Self {
+        self.msf.set_passive_runaway(value);
+        let accepted = self.msf.get_passive_runaway() == value;
+        self.check(accepted, ConfigError::PassiveRunaway)
+    }
+
+    /// Set the configuration of the decoder, e.g. `DecoderConfig::preset()`, see
+    /// `MSFUtils::set_decoder_config()`.
+    ///
+    /// An invalid configuration is rejected by `build()`. Settings made after this one override it.
+    ///
+    /// # Arguments
+    /// * `config` - the configuration to use
+    pub fn decoder_config(mut self, config: DecoderConfig) -> Self {
+        self.msf.set_decoder_config(config);
+        let accepted = self.msf.get_decoder_config() == config;
+        self.check(accepted, ConfigError::DecoderConfig)
+    }
+
+    /// Set if the internal invariants should be checked in release builds too.
+    ///
+    /// # Arguments
+    /// * `value` - check the invariants
+    pub fn invariant_checks(mut self, value: bool) -> Self {
+        self.msf.set_invariant_checks(value);
+        self
+    }
+
+    /// Set the policy deciding which fields of a minute decoded using relaxed checks are merged
+    /// into the date/time.
+    ///
+    /// # Arguments
+    /// * `policy` - the policy to use
+    pub fn merge_policy(mut self, policy: MergePolicy) -> Self {
+        self.msf.set_merge_policy(policy);
+        self
+    }
+
+    /// Set the policy deciding which minute marker aligns the second counter if the
+    /// end-of-minute and the begin-of-minute marker disagree, a majority over 0 minutes is
+    /// rejected by `build()`.
+    ///
+    /// # Arguments
+    /// * `policy` - the policy to use
+    #[cfg(feature = "marker-policy")]
+    pub fn marker_policy(mut self, policy: MarkerPolicy) -> Self {
+        self.msf.set_marker_policy(policy);
+        let accepted = self.msf.get_marker_policy() == policy;
+        self.check(accepted, ConfigError::MarkerPolicy)
+    }
+
+    /// Set when `decode_minute_auto()` switches between relaxed and strict checks, limits of 0
+    /// are rejected by `build()`.
+    ///
+    /// # Arguments
+    /// * `escalation` - the limits to use
+    pub fn escalation(mut self, escalation: Escalation) -> Self {
+        self.msf.set_escalation(escalation);
+        let accepted = self.msf.get_escalation() == escalation;
+        self.check(accepted, ConfigError::Escalation)
+    }
+
+    /// Set the level of the checks `decode_minute_auto()` starts with.
+    ///
+    /// # Arguments
+    /// * `strictness` - the level to start with
+    pub fn strictness(mut self, strictness: Strictness) -> Self {
+        self.msf.set_strictness(strictness);
+        self
+    }
+
+    /// Set the function which is called for each diagnostic.
+    ///
+    /// # Arguments
+    /// * `hook` - the function to call
+    pub fn diagnostic_hook(mut self, hook: fn(Diagnostic)) -> Self {
+        self.msf.set_diagnostic_hook(Some(hook));
+        self
+    }
+
+    /// Set the observer which is notified of the events of the decoder.
+    ///
+    /// # Arguments
+    /// * `observer` - the observer to notify
+    pub fn observer(mut self, observer: &'static dyn MSFObserver) -> Self {
+        self.msf.set_observer(Some(observer));
+        self
+    }
+
+    /// Set if the pulse lengths should be corrected for the estimated pulse bias.
+    ///
+    /// # Arguments
+    /// * `value` - correct the pulse lengths
+    pub fn pulse_bias_compensation(mut self, value: bool) -> Self {
+        self.msf.set_pulse_bias_compensation(value);
+        self
+    }
+
+    /// Set the largest magnitude of DUT1 in deci-seconds which is considered plausible, values
+    /// above 8 are rejected by `build()`.
+    ///
+    /// # Arguments
+    /// * `limit` - largest magnitude in deci-seconds
+    pub fn dut1_limit(mut self, limit: u8) -> Self {
+        self.msf.set_dut1_limit(limit);
+        let accepted = self.msf.get_dut1_limit() == limit;
+        self.check(accepted, ConfigError::Dut1Limit)
+    }
+
+    /// Set the number of minutes in a row in which a new DUT1 value must be received before it
+    /// is returned by `MSFUtils::get_filtered_dut1()`, 0 is rejected by `build()`.
+    ///
+    /// # Arguments
+    /// * `value` - the number of minutes
+    #[cfg(feature = "dut1-filter")]
+    pub fn dut1_confirmations(mut self, value: u8) -> Self {
+        self.msf.set_dut1_confirmations(value);
+        let accepted = self.msf.get_dut1_confirmations() == value;
+        self.check(accepted, ConfigError::Dut1Confirmations)
+    }
+
+    /// Set if the flywheel is enabled, see `MSFUtils::set_flywheel()`.
+    ///
+    /// # Arguments
+    /// * `value` - enable the flywheel
+    #[cfg(feature = "flywheel")]
+    pub fn flywheel(mut self, value: bool) -> Self {
+        self.msf.set_flywheel(value);
+        self
+    }
+
+    /// Set the number of consecutive consistent minutes needed for a single-shot sync, see
+    /// `MSFUtils::set_sync_minutes()`.
+    ///
+    /// # Arguments
+    /// * `minutes` - number of minutes, 0 disables single-shot sync
+    #[cfg(feature = "single-shot")]
+    pub fn sync_minutes(mut self, minutes: u8) -> Self {
+        self.msf.set_sync_minutes(minutes);
+        self
+    }
+
+    /// Set if the limits of the pulse lengths are learned from the observed pulses.
+    ///
+    /// # Arguments
+    /// * `value` - learn the limits
+    pub fn adaptive_limits(mut self, value: bool) -> Self {
+        self.msf.set_adaptive_limits(value);
+        self
+    }
+
+    /// Set if minutes should be assumed to last 60 seconds unless listed in the leap second table.
+    ///
+    /// # Arguments
+    /// * `value` - assume fixed minute lengths
+    pub fn fixed_minute_length(mut self, value: bool) -> Self {
+        self.msf.set_fixed_minute_length(value);
+        self
+    }
+
+    /// Set the table of known leap seconds, used when minute lengths are fixed.
+    ///
+    /// # Arguments
+    /// * `table` - the leap second table
+    pub fn leap_table(mut self, table: &'static [LeapSecondEntry]) -> Self {
+        self.msf.set_leap_table(table);
+        self
+    }
+
+    /// Set if decoded (0,1) bit pairs should be treated as noise.
+    ///
+    /// # Arguments
+    /// * `value` - treat (0,1) bit pairs as noise
+    pub fn suspect_0_1(mut self, value: bool) -> Self {
+        self.msf.set_suspect_0_1(value);
+        self
+    }
+
+    /// Set if minutes are decoded from the A bits alone.
+    ///
+    /// # Arguments
+    /// * `value` - decode from the A bits alone
+    pub fn a_only(mut self, value: bool) -> Self {
+        self.msf.set_a_only(value);
+        self
+    }
+
+    /// Set if fields failing their parity may be taken from the prediction, see
+    /// `MSFUtils::set_predictive_fallback()`.
+    ///
+    /// # Arguments
+    /// * `value` - take failing fields from the prediction
+    pub fn predictive_fallback(mut self, value: bool) -> Self {
+        self.msf.set_predictive_fallback(value);
+        self
+    }
+
+    /// Set the number of minutes over which the date and DUT1 bits are voted, values above
+    /// `VOTING_DEPTH_MAX` are rejected by `build()`.
+    ///
+    /// # Arguments
+    /// * `depth` - number of minutes, 0 to disable voting
+    #[cfg(feature = "voting")]
+    pub fn voting_depth(mut self, depth: u8) -> Self {
+        self.msf.set_voting_depth(depth);
+        let accepted = self.msf.get_voting_depth() == depth;
+        self.check(accepted, ConfigError::VotingDepth)
+    }
+
+    /// Set the number of raw edges to keep in the edge ring, values above `EDGE_RING_SIZE` are
+    /// rejected by `build()`.
+    ///
+    /// # Arguments
+    /// * `length` - number of edges, 0 to disable the ring
+    #[cfg(feature = "statistics")]
+    pub fn edge_ring_length(mut self, length: usize) -> Self {
+        self.msf.set_edge_ring_length(length);
+        let accepted = self.msf.get_edge_ring().get_capacity() == length;
+        self.check(accepted, ConfigError::EdgeRingLength)
+    }
+
+    /// Set the number of consecutive seconds without a valid bit pair after which the decoder is
+    /// no longer considered healthy, 0 is rejected by `build()`.
+    ///
+    /// # Arguments
+    /// * `seconds` - number of seconds
+    pub fn watchdog_tolerance(mut self, seconds: u8) -> Self {
+        self.msf.set_watchdog_tolerance(seconds);
+        let accepted = self.msf.get_watchdog_tolerance() == seconds;
+        self.check(accepted, ConfigError::WatchdogTolerance)
+    }
+
+    /// Return the configured decoder, or the first setting which was rejected.
+    pub fn build(self) -> Result<MSFUtils, ConfigError> {
+        match self.error {
+            Some(error) => Err(error),
+            None => Ok(self.msf),
+        }
+    }
+}
+
 #[cfg(test)]
+#[allow(deprecated)]
 mod tests {
     use super::*;
+    use crate::test_utils::{filled_msf, minute_edges, BIT_BUFFER_A, BIT_BUFFER_B};
 
-    const BIT_BUFFER_A: [bool; 60] = [
-        true, // begin-of-minute marker
-        false, false, false, false, false, false, false, false, // unused 1-8
-        false, false, false, false, false, false, false, false, // unused 9-16
-        false, false, true, false, false, false, true, false, // year 22
-        true, false, false, false, false, // month 10
-        true, false, false, false, true, true, // day 23
-        true, true, false, // Saturday
-        false, true, false, true, false, false, // hour 14
-        true, false, true, true, false, false, false, // minute 58
-        false, true, true, true, true, true, true, false, // end-of-minute marker
-    ];
-    const BIT_BUFFER_B: [bool; 60] = [
-        true, // begin-of-minute marker,
-        false, false, false, false, false, false, false, false, // DUT1 positive
-        true, true, false, false, false, false, false, false, // DUT1 negative (-2)
-        false, false, false, false, false, false, false, false, // unused 17-24
-        false, false, false, false, false, false, false, false, // unused 25-32
-        false, false, false, false, false, false, false, false, // unused 33-40
-        false, false, false, false, false, false, false, false, // unused 41-48
-        false, false, false, false, // unused 49-52
-        false, // summer time warning
-        true,  // year parity
-        true,  // month+day parity
-        true,  // weekday parity
-        false, // hour+minute parity
-        true,  // summer time active
-        false, // unused
-    ];
+    #[test]
+    fn test_capabilities() {
+        let capabilities = MSFUtils::capabilities();
+        assert_eq!(capabilities.frontends, cfg!(feature = "frontends"));
+        assert_eq!(capabilities.hal, cfg!(feature = "hal"));
+        assert_eq!(capabilities.async_receiver, cfg!(feature = "async"));
+        assert_eq!(capabilities.formats, cfg!(feature = "formats"));
+        assert_eq!(capabilities.discipline, cfg!(feature = "discipline"));
+        assert_eq!(capabilities.statistics, cfg!(feature = "statistics"));
+        assert_eq!(capabilities.soft_bits, cfg!(feature = "soft-bits"));
+        assert_eq!(capabilities.flywheel, cfg!(feature = "flywheel"));
+        assert_eq!(capabilities.marker_policy, cfg!(feature = "marker-policy"));
+        assert_eq!(capabilities.dut1_filter, cfg!(feature = "dut1-filter"));
+        assert_eq!(capabilities.single_shot, cfg!(feature = "single-shot"));
+        assert_eq!(capabilities.voting, cfg!(feature = "voting"));
+        assert_eq!(capabilities.protocol, cfg!(feature = "protocol"));
+        assert_eq!(capabilities.serde, cfg!(feature = "serde"));
+        assert_eq!(capabilities.chrono, cfg!(feature = "chrono"));
+        assert_eq!(capabilities.time, cfg!(feature = "time"));
+        assert_eq!(capabilities.net, cfg!(feature = "net"));
+        assert_eq!(
+            capabilities.fault_injection,
+            cfg!(feature = "fault-injection")
+        );
+        assert_eq!(capabilities.log, cfg!(feature = "log"));
+        assert_eq!(capabilities.defmt, cfg!(feature = "defmt"));
+        assert_eq!(capabilities.time_only, cfg!(feature = "time-only"));
+        assert_eq!(capabilities.std, cfg!(feature = "std"));
+        assert_eq!(capabilities.dut1, !cfg!(feature = "no-dut1"));
+    }
+    #[test]
+    #[cfg(all(
+        target_pointer_width = "64",
+        not(any(
+            feature = "discipline",
+            feature = "statistics",
+            feature = "single-shot",
+            feature = "dut1-filter",
+            feature = "marker-policy",
+            feature = "flywheel",
+            feature = "soft-bits",
+            feature = "voting"
+        ))
+    ))]
+    fn test_size_of_core() {
+        // the decoder core without any of the optional state
+        assert_eq!(::core::mem::size_of::<MSFUtils>(), 480);
+    }
 
     #[test]
     fn test_new_edge_bit_0_0() {
@@ -620,6 +3550,111 @@ mod tests {
         assert_eq!(msf.get_current_bit_b(), Some(true)); // keep bit value
     }
     #[test]
+    fn test_new_edge_bit_0_1_counter() {
+        const EDGE_BUFFER: [(bool, u32); 5] = [
+            (!false, 0),         // 0
+            (!true, 920_000),    // 920_000
+            (!false, 1_030_000), // 110_000
+            (!true, 1_128_000),  // 98_000
+            (!false, 1_232_000), // 104_000
+        ];
+        let mut msf = MSFUtils::default();
+        msf.second = 20; // outside of the DUT1 field
+        for edge in EDGE_BUFFER {
+            msf.handle_new_edge(edge.0, edge.1);
+        }
+        assert_eq!(msf.get_count_0_1(), 1);
+        assert_eq!(msf.get_current_bit_a(), Some(false)); // not suspect by default
+        assert_eq!(msf.get_current_bit_b(), Some(true));
+        // DUT1 bits are not counted
+        let mut msf = MSFUtils::default();
+        msf.second = 9;
+        for edge in EDGE_BUFFER {
+            msf.handle_new_edge(edge.0, edge.1);
+        }
+        assert_eq!(msf.get_count_0_1(), 0);
+    }
+    #[test]
+    fn test_new_edge_bit_0_1_suspect() {
+        const EDGE_BUFFER: [(bool, u32); 5] = [
+            (!false, 0),         // 0
+            (!true, 920_000),    // 920_000
+            (!false, 1_030_000), // 110_000
+            (!true, 1_128_000),  // 98_000
+            (!false, 1_232_000), // 104_000
+        ];
+        let mut msf = MSFUtils::builder().suspect_0_1(true).build().unwrap();
+        assert_eq!(msf.get_suspect_0_1(), true);
+        msf.second = 20; // outside of the DUT1 field
+        for edge in EDGE_BUFFER {
+            msf.handle_new_edge(edge.0, edge.1);
+        }
+        assert_eq!(msf.get_count_0_1(), 1);
+        assert_eq!(msf.get_current_bit_a(), None);
+        assert_eq!(msf.get_current_bit_b(), None);
+        // DUT1 bits are kept
+        let mut msf = MSFUtils::builder().suspect_0_1(true).build().unwrap();
+        msf.second = 9;
+        for edge in EDGE_BUFFER {
+            msf.handle_new_edge(edge.0, edge.1);
+        }
+        assert_eq!(msf.get_count_0_1(), 0);
+        assert_eq!(msf.get_current_bit_a(), Some(false));
+        assert_eq!(msf.get_current_bit_b(), Some(true));
+    }
+    #[test]
+    fn test_new_edge_whole_minute_suspect_0_1() {
+        let mut msf = MSFUtils::builder().suspect_0_1(true).build().unwrap();
+        let mut decoded = false;
+        for (is_low_edge, t) in minute_edges(1_000_000) {
+            msf.handle_new_edge(is_low_edge, t);
+            if msf.get_new_second() {
+                if msf.second + 1 == msf.get_minute_length() {
+                    decoded = msf.decode_minute(true).is_ok();
+                }
+                msf.increase_second();
+            }
+        }
+        assert_eq!(decoded, true);
+        #[cfg(not(feature = "no-dut1"))]
+        assert_eq!(msf.get_dut1(), Some(-2));
+        assert_eq!(msf.get_count_0_1(), 0);
+    }
+    #[test]
+    #[cfg(feature = "soft-bits")]
+    fn test_new_edge_bit_likelihoods() {
+        const EDGE_BUFFER: [(bool, u32); 5] = [
+            (!false, 0),         // 0
+            (!true, 920_000),    // 920_000
+            (!false, 1_030_000), // 110_000
+            (!true, 1_128_000),  // 98_000
+            (!false, 1_232_000), // 104_000
+        ];
+        let mut msf = MSFUtils::builder().suspect_0_1(true).build().unwrap();
+        msf.second = 20; // outside of the DUT1 field
+        for edge in EDGE_BUFFER.iter().take(3) {
+            msf.handle_new_edge(edge.0, edge.1);
+        }
+        let likelihoods = msf.get_current_bit_likelihoods();
+        assert_eq!(likelihoods.l00, 229);
+        assert_eq!(likelihoods.l01, 114); // could still become a (0,1) pair
+        assert_eq!(likelihoods.best(), Some((false, false)));
+        for edge in EDGE_BUFFER.iter().skip(3) {
+            msf.handle_new_edge(edge.0, edge.1);
+        }
+        // kept even though the hard bits were cleared
+        assert_eq!(msf.get_current_bit_a(), None);
+        assert_eq!(
+            msf.get_current_bit_likelihoods().best(),
+            Some((false, true))
+        );
+        assert_eq!(msf.get_bit_likelihoods(20).unwrap().l01, 246);
+        assert_eq!(
+            msf.get_bit_likelihoods(radio_datetime_utils::BIT_BUFFER_SIZE as u8),
+            None
+        );
+    }
+    #[test]
     fn test_new_edge_bit_1_0() {
         const EDGE_BUFFER: [(bool, u32); 4] = [
             // Some(true,false) bit value
@@ -694,60 +3729,676 @@ mod tests {
         assert_eq!(msf.get_current_bit_b(), Some(true)); // keep bit value
     }
     #[test]
-    fn test_new_edge_minute() {
-        const EDGE_BUFFER: [(bool, u32); 3] = [
-            // new minute, (true,true) bit value
-            (!false, 420_994_620), // 0
-            (!true, 421_906_680),  // 912_060
-            (!false, 422_389_442), // 482_762
-        ];
+    fn test_new_edge_minute() {
+        const EDGE_BUFFER: [(bool, u32); 3] = [
+            // new minute, (true,true) bit value
+            (!false, 420_994_620), // 0
+            (!true, 421_906_680),  // 912_060
+            (!false, 422_389_442), // 482_762
+        ];
+        let mut msf = MSFUtils::default();
+        assert_eq!(msf.before_first_edge, true);
+        msf.handle_new_edge(EDGE_BUFFER[0].0, EDGE_BUFFER[0].1);
+        assert_eq!(msf.before_first_edge, false);
+        assert_eq!(msf.t0, EDGE_BUFFER[0].1); // very first edge
+
+        msf.handle_new_edge(EDGE_BUFFER[1].0, EDGE_BUFFER[1].1); // first significant edge
+        assert_eq!(msf.t0, EDGE_BUFFER[1].1); // longer than a spike
+        assert_eq!(msf.new_second, true);
+        assert_eq!(msf.past_new_minute, false);
+        assert_eq!(msf.get_current_bit_a(), None); // not yet determined
+        assert_eq!(msf.get_current_bit_b(), None); // not yet determined
+
+        msf.handle_new_edge(EDGE_BUFFER[2].0, EDGE_BUFFER[2].1); // new minute
+        assert_eq!(msf.t0, EDGE_BUFFER[2].1); // longer than a spike
+        assert_eq!(msf.new_second, false);
+        assert_eq!(msf.past_new_minute, true);
+        assert_eq!(msf.get_current_bit_a(), Some(true));
+        assert_eq!(msf.get_current_bit_b(), Some(true));
+    }
+    #[test]
+    fn test_new_edge_active_runaway() {
+        const EDGE_BUFFER: [(bool, u32); 3] = [
+            // active runaway (broken bit)
+            (!false, 417_195_653), // 0
+            (!true, 417_908_323),  // 712_670
+            (!false, 419_193_216), // 1_284_893
+        ];
+        let mut msf = MSFUtils::default();
+        assert_eq!(msf.before_first_edge, true);
+        msf.handle_new_edge(EDGE_BUFFER[0].0, EDGE_BUFFER[0].1);
+        assert_eq!(msf.before_first_edge, false);
+        assert_eq!(msf.t0, EDGE_BUFFER[0].1); // very first edge
+
+        msf.handle_new_edge(EDGE_BUFFER[1].0, EDGE_BUFFER[1].1); // first significant edge
+        assert_eq!(msf.t0, EDGE_BUFFER[1].1); // longer than a spike
+        assert_eq!(msf.new_second, true);
+        assert_eq!(msf.past_new_minute, false);
+        assert_eq!(msf.get_current_bit_a(), None); // not yet determined, passive part
+        assert_eq!(msf.get_current_bit_b(), None); // not yet determined, passive part
+
+        msf.handle_new_edge(EDGE_BUFFER[2].0, EDGE_BUFFER[2].1);
+        assert_eq!(msf.t0, EDGE_BUFFER[2].1); // longer than a spike
+        assert_eq!(msf.new_second, false);
+        assert_eq!(msf.past_new_minute, false);
+        assert_eq!(msf.get_current_bit_a(), None);
+        assert_eq!(msf.get_current_bit_b(), None);
+    }
+    #[test]
+    fn test_new_edge_whole_minute() {
+        let mut msf = MSFUtils::default();
+        for (is_low_edge, t) in minute_edges(1_000_000) {
+            msf.handle_new_edge(is_low_edge, t);
+            if msf.get_new_second() {
+                msf.increase_second();
+            }
+            if !is_low_edge && msf.second > 1 {
+                assert_eq!(
+                    msf.bit_buffer_a[msf.second as usize - 1],
+                    Some(BIT_BUFFER_A[msf.second as usize - 1])
+                );
+                assert_eq!(
+                    msf.bit_buffer_b[msf.second as usize - 1],
+                    Some(BIT_BUFFER_B[msf.second as usize - 1])
+                );
+            }
+        }
+        assert_eq!(msf.second, 0); // wrapped into the next minute
+    }
+    #[test]
+    #[cfg(feature = "statistics")]
+    fn test_new_edge_edge_ring() {
+        let mut msf = MSFUtils::builder().edge_ring_length(3).build().unwrap();
+        for (is_low_edge, t) in minute_edges(1_000_000) {
+            msf.handle_new_edge(is_low_edge, t);
+            if msf.get_new_second() {
+                msf.increase_second();
+            }
+        }
+        msf.handle_new_edge(false, 61_005_000); // spike
+        let classes: Vec<EdgeClass> = msf.get_edge_ring().iter().map(|r| r.class).collect();
+        assert_eq!(
+            classes,
+            [
+                EdgeClass::Bit { a: false, b: false }, // end of the EOM marker
+                EdgeClass::NewSecond,
+                EdgeClass::Spike
+            ]
+        );
+    }
+    #[test]
+    fn test_should_kick_watchdog() {
+        let mut msf = MSFUtils::builder().watchdog_tolerance(2).build().unwrap();
+        assert_eq!(msf.get_watchdog_tolerance(), 2);
+        assert_eq!(msf.should_kick_watchdog(), false); // nothing received yet
+        for (is_low_edge, t) in minute_edges(1_000_000) {
+            msf.handle_new_edge(is_low_edge, t);
+            if msf.get_new_second() {
+                msf.increase_second();
+            }
+        }
+        assert_eq!(msf.should_kick_watchdog(), true);
+        assert_eq!(msf.should_kick_watchdog(), false); // no new second since
+                                                       // too long pulses do not carry a bit pair
+        for s in 0..3 {
+            let start = 61_000_000 + s * 2_000_000;
+            msf.handle_new_edge(true, start + 600_000);
+            msf.handle_new_edge(false, start + 2_000_000);
+            if msf.get_new_second() {
+                msf.increase_second();
+            }
+            assert_eq!(msf.should_kick_watchdog(), s == 0);
+        }
+    }
+    #[test]
+    fn test_force_past_new_minute() {
+        let mut msf = MSFUtils::default();
+        msf.second = 30;
+        msf.force_past_new_minute();
+        assert_eq!(msf.get_past_new_minute(), true);
+        assert_eq!(msf.get_minute_origin(), MinuteOrigin::Forced);
+        assert_eq!(msf.second, 0);
+        #[cfg(feature = "statistics")]
+        assert_eq!(msf.total_resyncs(), 1); // same path as a detected marker
+        for (is_low_edge, t) in minute_edges(1_000_000) {
+            msf.handle_new_edge(is_low_edge, t);
+            if msf.get_new_second() {
+                msf.increase_second();
+            }
+        }
+        assert_eq!(msf.get_minute_origin(), MinuteOrigin::Radio);
+    }
+    #[cfg(not(feature = "no-dut1"))]
+    #[test]
+    fn test_decode_minute_dut1_limit() {
+        let mut msf = MSFUtils::builder().dut1_limit(1).build().unwrap();
+        assert_eq!(msf.get_dut1_limit(), 1);
+        msf.set_dut1_limit(DUT1_LIMIT + 1);
+        assert_eq!(msf.get_dut1_limit(), 1); // ignored
+        for b in 0..60 {
+            msf.bit_buffer_a[b] = Some(BIT_BUFFER_A[b]);
+            msf.bit_buffer_b[b] = Some(BIT_BUFFER_B[b]);
+        }
+        msf.second = 59;
+        assert_eq!(
+            msf.decode_minute(true).err(),
+            Some(api2::DecodeError::Invalid)
+        );
+        assert_eq!(msf.get_dut1(), None);
+        assert_eq!(msf.get_dut1_status(), Dut1Status::Suspicious(-2));
+        msf.set_dut1_limit(2);
+        assert_eq!(msf.decode_minute(true).is_ok(), true);
+        assert_eq!(msf.get_dut1_status(), Dut1Status::Valid(-2));
+    }
+    #[cfg(not(feature = "no-dut1"))]
+    #[test]
+    #[cfg(feature = "dut1-filter")]
+    fn test_decode_minute_filtered_dut1() {
+        let mut msf = MSFUtils::builder().dut1_confirmations(3).build().unwrap();
+        assert_eq!(msf.get_dut1_confirmations(), 3);
+        msf.set_dut1_confirmations(0);
+        assert_eq!(msf.get_dut1_confirmations(), 3); // ignored
+        for b in 0..60 {
+            msf.bit_buffer_a[b] = Some(BIT_BUFFER_A[b]);
+            msf.bit_buffer_b[b] = Some(BIT_BUFFER_B[b]);
+        }
+        msf.second = 59;
+        for _ in 0..2 {
+            let _ = msf.decode_minute(false);
+        }
+        assert_eq!(msf.get_dut1(), Some(-2));
+        assert_eq!(msf.get_filtered_dut1(), None);
+        let _ = msf.decode_minute(false);
+        assert_eq!(msf.get_filtered_dut1(), Some(-2));
+        // a single corrupted bit, DUT1 -3
+        msf.bit_buffer_b[11] = Some(true);
+        let _ = msf.decode_minute(false);
+        assert_eq!(msf.get_dut1(), Some(-3));
+        assert_eq!(msf.get_filtered_dut1(), Some(-2));
+        // an unknown DUT1 does not break the row
+        msf.bit_buffer_b[12] = None;
+        let _ = msf.decode_minute(false);
+        assert_eq!(msf.get_dut1(), None);
+        msf.bit_buffer_b[12] = Some(false);
+        for _ in 0..2 {
+            let _ = msf.decode_minute(false);
+        }
+        assert_eq!(msf.get_filtered_dut1(), Some(-3));
+    }
+    #[cfg(feature = "no-dut1")]
+    #[test]
+    fn test_decode_minute_no_dut1() {
+        let mut msf = filled_msf();
+        msf.bit_buffer_b[1] = Some(true); // invalid DUT1, both signs set
+        assert_eq!(msf.decode_minute(true).is_ok(), true);
+        assert_eq!(msf.get_dut1(), None);
+    }
+    #[test]
+    fn test_get_signal_quality() {
+        let mut msf = filled_msf();
+        msf.bit_buffer_b[20] = None;
+        assert_eq!(
+            msf.get_signal_quality(),
+            SignalQuality {
+                valid_seconds: 58,
+                seconds: 59
+            }
+        );
+        assert_eq!(
+            msf.get_current_bit_pair(),
+            BitPair {
+                a: Some(false),
+                b: Some(false)
+            }
+        );
+    }
+    #[test]
+    fn test_minute_progress() {
+        let mut msf = filled_msf();
+        let progress = msf.minute_progress();
+        assert_eq!(progress.second, 59);
+        assert_eq!(progress.received, progress.required);
+        assert_eq!(progress.percent, 100);
+        msf.second = 35; // up to the day
+        msf.set_a_only(true);
+        assert_eq!(
+            msf.minute_progress(),
+            MinuteProgress {
+                second: 35,
+                received: 19,
+                required: 37,
+                percent: 51
+            }
+        );
+    }
+    #[test]
+    fn test_minute_length_estimate() {
+        let mut msf = filled_msf();
+        msf.second = 30;
+        assert_eq!(
+            msf.minute_length_estimate(),
+            MinuteLengthEstimate {
+                length: 60,
+                certain: false
+            }
+        );
+        msf.second = 59;
+        assert_eq!(
+            msf.minute_length_estimate(),
+            MinuteLengthEstimate {
+                length: 60,
+                certain: true
+            }
+        );
+        // shift the marker one second later, as with a positive leap second
+        msf.bit_buffer_a[53] = Some(false);
+        msf.bit_buffer_a[59] = Some(true);
+        assert_eq!(
+            msf.minute_length_estimate(),
+            MinuteLengthEstimate {
+                length: 61,
+                certain: false
+            }
+        );
+        msf.set_fixed_minute_length(true);
+        assert_eq!(msf.minute_length_estimate().certain, true);
+    }
+    #[test]
+    fn test_state_fingerprint() {
+        let mut msf_a = MSFUtils::default();
+        let mut msf_b = MSFUtils::default();
+        assert_eq!(msf_a.state_fingerprint(), msf_b.state_fingerprint());
+        // same edges with a different time base
+        for (is_low_edge, t) in minute_edges(1_000_000) {
+            msf_a.handle_new_edge(is_low_edge, t);
+            msf_b.handle_new_edge(is_low_edge, t + 123_456);
+        }
+        assert_eq!(msf_a.state_fingerprint(), msf_b.state_fingerprint());
+        msf_b.set_refractory_window(1_000);
+        assert_ne!(msf_a.state_fingerprint(), msf_b.state_fingerprint());
+    }
+    #[test]
+    #[cfg(feature = "statistics")]
+    fn test_audit_counters() {
+        let mut msf = filled_msf();
+        let _ = msf.decode_minute(false);
+        msf.radio_datetime.set_minute(Some(30), true, false);
+        let _ = msf.decode_minute(false); // 14:31 expected, 14:58 received
+        assert_eq!(msf.uptime_minutes(), 2);
+        assert_eq!(msf.total_steps(), 1);
+        assert_eq!(msf.total_resyncs(), 0);
+        assert_eq!(msf.take_daily_summary(), None);
+    }
+    #[test]
+    #[cfg(feature = "statistics")]
+    fn test_bit_error_rate() {
+        let mut msf = filled_msf();
+        let _ = msf.decode_minute(false);
+        assert_eq!(msf.get_bit_error_rate().get_ppm(), None); // nothing to predict from
+        let _ = msf.decode_minute(false); // 14:59 predicted, 14:58 received
+        let ber = msf.get_bit_error_rate();
+        assert_eq!((ber.get_bits(), ber.get_errors()), (35, 1));
+        assert_eq!(ber.get_ppm(), Some(28_571));
+        msf.reset_bit_error_rate();
+        assert_eq!(msf.get_bit_error_rate().get_bits(), 0);
+    }
+    #[test]
+    fn test_predictive_fallback() {
+        let mut msf = MSFUtils::builder()
+            .predictive_fallback(true)
+            .build()
+            .unwrap();
+        assert_eq!(msf.get_predictive_fallback(), true);
+        for b in 0..60 {
+            msf.bit_buffer_a[b] = Some(BIT_BUFFER_A[b]);
+            msf.bit_buffer_b[b] = Some(BIT_BUFFER_B[b]);
+        }
+        msf.second = 59;
+        let _ = msf.decode_minute(true);
+        assert_eq!(msf.get_predicted_fields(), PredictedFields::default());
+        msf.radio_datetime.set_minute(Some(57), true, false); // 14:58 is predicted again
+        msf.bit_buffer_a[32] = msf.bit_buffer_a[32].map(|b| !b); // a single hit in the day
+        assert_eq!(msf.decode_minute(false).is_ok(), true);
+        assert_eq!(msf.get_parity_2(), Some(false));
+        let predicted = msf.get_predicted_fields();
+        assert_eq!((predicted.month_day, predicted.any()), (true, true));
+        assert_eq!(msf.get_radio_datetime().get_day(), Some(23));
+        // the predicted day does not pass strict checks
+        msf.radio_datetime.set_minute(Some(57), true, false);
+        assert_eq!(
+            msf.decode_minute(true).err(),
+            Some(api2::DecodeError::Invalid)
+        );
+        assert_eq!(msf.get_predicted_fields().month_day, true);
+        assert_eq!(msf.bit_buffer_a[32], Some(!BIT_BUFFER_A[32])); // received bit is kept
+        msf.bit_buffer_a[32] = Some(BIT_BUFFER_A[32]);
+        // two hits are not bridged
+        msf.radio_datetime.set_minute(Some(57), true, false);
+        msf.bit_buffer_a[47] = msf.bit_buffer_a[47].map(|b| !b);
+        msf.bit_buffer_a[49] = msf.bit_buffer_a[49].map(|b| !b);
+        msf.bit_buffer_a[50] = msf.bit_buffer_a[50].map(|b| !b);
+        assert_eq!(msf.decode_minute(true).is_ok(), false);
+        assert_eq!(msf.get_predicted_fields().hour_minute, false);
+    }
+    #[test]
+    fn test_bit_buffers() {
+        let msf = filled_msf();
+        assert_eq!(
+            msf.get_bit_buffer_a().len(),
+            radio_datetime_utils::BIT_BUFFER_SIZE
+        );
+        assert_eq!(msf.get_bit_buffer_a()[17], Some(BIT_BUFFER_A[17]));
+        assert_eq!(msf.get_bit_buffer_b()[58], Some(BIT_BUFFER_B[58]));
+        assert_eq!(msf.get_bit_buffer_b()[60], None);
+    }
+    #[test]
+    fn test_auto_spike_limit() {
+        let mut msf = MSFUtils::builder().auto_spike_limit(true).build().unwrap();
+        assert_eq!(msf.get_auto_spike_limit(), true);
+        for (is_low_edge, t) in [
+            (false, 1_000_000),
+            (true, 1_035_000), // spike bursts just above the default limit
+            (false, 1_070_000),
+            (true, 1_105_000),
+        ] {
+            msf.handle_new_edge(is_low_edge, t);
+        }
+        let _ = msf.decode_minute(false);
+        assert_eq!(msf.get_spike_limit_low(), 45_000);
+        assert_eq!(msf.get_spike_limit_high(), 45_000);
+        let _ = msf.decode_minute(false);
+        let _ = msf.decode_minute(false);
+        assert_eq!(msf.get_spike_limit(), SPIKE_LIMIT);
+    }
+    #[test]
+    fn test_minute_phase_offset() {
+        let mut msf = MSFUtils::default();
+        let mut edges = minute_edges(1_000_000);
+        edges.pop();
+        edges.extend(minute_edges(61_000_200));
+        edges.pop();
+        edges.extend(minute_edges(121_000_500));
+        for (is_low_edge, t) in edges {
+            msf.handle_new_edge(is_low_edge, t);
+            if msf.get_new_second() {
+                if msf.second + 1 == msf.get_minute_length() {
+                    let _ = msf.decode_minute(false);
+                }
+                msf.increase_second();
+                if msf.second_start == Some(61_000_200) {
+                    // the first marker of the stream is not recognized
+                    assert_eq!(msf.get_minute_marker_t(), None);
+                }
+                if msf.second_start == Some(62_000_200) {
+                    assert_eq!(msf.get_minute_marker_t(), Some(61_000_200));
+                    assert_eq!(msf.get_minute_phase_offset(), None);
+                }
+            }
+        }
+        assert_eq!(msf.get_minute_marker_t(), Some(121_000_500));
+        assert_eq!(msf.get_minute_phase_offset(), Some(300)); // the local timer runs fast
+    }
+    #[test]
+    #[cfg(feature = "statistics")]
+    fn test_metrics() {
+        let mut msf = MSFUtils::default();
+        for (is_low_edge, t) in minute_edges(1_000_000) {
+            msf.handle_new_edge(is_low_edge, t);
+            if msf.get_new_second() {
+                if msf.second + 1 == msf.get_minute_length() {
+                    let _ = msf.decode_minute(false);
+                }
+                msf.increase_second();
+            }
+        }
+        let metrics = msf.get_metrics();
+        assert_eq!(metrics.get_minutes_attempted(), 1);
+        assert_eq!(metrics.get_minutes_decoded(), 1);
+        assert_eq!(metrics.get_spikes(), 0);
+        assert_eq!(metrics.get_active_runaways(), 0);
+        assert_eq!(metrics.get_resyncs(), 0);
+        msf.reset_metrics();
+        assert_eq!(msf.get_metrics().get_minutes_attempted(), 0);
+        let mut msf = filled_msf();
+        msf.bit_buffer_a[48] = msf.bit_buffer_a[48].map(|b| !b); // break parity 4
+        let _ = msf.decode_minute(true);
+        assert_eq!(msf.get_metrics().get_parity_failures(4), 1);
+        assert_eq!(msf.get_metrics().get_minutes_decoded(), 0);
+    }
+    #[cfg(feature = "discipline")]
+    #[test]
+    fn test_drain_phase_samples() {
+        let mut msf = MSFUtils::default();
+        for (is_low_edge, t) in minute_edges(1_000_000) {
+            msf.handle_new_edge(is_low_edge, t);
+        }
+        // the very first edge only starts the measurement
+        assert_eq!(msf.get_phase_samples().len(), 59);
+        let mut buf = [1; 64];
+        assert_eq!(msf.drain_phase_samples(&mut buf), 59);
+        assert_eq!(buf[..59], [0; 59]);
+        assert_eq!(msf.get_phase_samples().is_empty(), true);
+    }
+    #[test]
+    fn test_predicted_minute_marker_at() {
+        let mut msf = MSFUtils::default();
+        assert_eq!(msf.predicted_minute_marker_at(), None);
+        for (is_low_edge, t) in minute_edges(1_000_000) {
+            msf.handle_new_edge(is_low_edge, t);
+            if msf.get_new_second() {
+                msf.increase_second();
+                if msf.second == 30 {
+                    assert_eq!(msf.predicted_minute_marker_at(), Some(61_000_000));
+                }
+            }
+        }
+        assert_eq!(msf.predicted_minute_marker_at(), Some(121_000_000));
+        msf.increase_second(); // no edge arrived
+        assert_eq!(msf.second, 1);
+        assert_eq!(msf.predicted_minute_marker_at(), Some(121_000_000));
+    }
+    #[test]
+    fn test_new_edge_active_low() {
+        let mut msf = MSFUtils::builder()
+            .signal_convention(SignalConvention::ActiveLow)
+            .build()
+            .unwrap();
+        assert_eq!(msf.get_signal_convention(), SignalConvention::ActiveLow);
+        for (is_low_edge, t) in minute_edges(1_000_000) {
+            msf.handle_new_edge(!is_low_edge, t);
+            if msf.get_new_second() {
+                msf.increase_second();
+            }
+        }
+        assert_eq!(msf.second, 0);
+        assert_eq!(msf.bit_buffer_a[59], Some(false));
+        assert_eq!(
+            msf.bit_buffer_b[54..58],
+            [Some(true), Some(true), Some(true), Some(false)]
+        );
+    }
+    #[test]
+    fn test_new_edge_u64() {
         let mut msf = MSFUtils::default();
-        assert_eq!(msf.before_first_edge, true);
-        msf.handle_new_edge(EDGE_BUFFER[0].0, EDGE_BUFFER[0].1);
-        assert_eq!(msf.before_first_edge, false);
-        assert_eq!(msf.t0, EDGE_BUFFER[0].1); // very first edge
-
-        msf.handle_new_edge(EDGE_BUFFER[1].0, EDGE_BUFFER[1].1); // first significant edge
-        assert_eq!(msf.t0, EDGE_BUFFER[1].1); // longer than a spike
-        assert_eq!(msf.new_second, true);
-        assert_eq!(msf.past_new_minute, false);
-        assert_eq!(msf.get_current_bit_a(), None); // not yet determined
-        assert_eq!(msf.get_current_bit_b(), None); // not yet determined
-
-        msf.handle_new_edge(EDGE_BUFFER[2].0, EDGE_BUFFER[2].1); // new minute
-        assert_eq!(msf.t0, EDGE_BUFFER[2].1); // longer than a spike
-        assert_eq!(msf.new_second, false);
-        assert_eq!(msf.past_new_minute, true);
-        assert_eq!(msf.get_current_bit_a(), Some(true));
-        assert_eq!(msf.get_current_bit_b(), Some(true));
+        let base = 5_000_000_000_000;
+        msf.handle_new_edge_u64(true, base);
+        msf.handle_new_edge_u64(false, base + 900_000);
+        assert_eq!(msf.get_new_second(), true);
+        // would alias to 100 ms after truncating to 32 bits
+        let t = base + 1_000_000 + (1 << 32);
+        msf.handle_new_edge_u64(true, t);
+        assert_eq!(msf.get_timer_discontinuity(), true);
+        msf.handle_new_edge_u64(false, t + 900_000);
+        assert_eq!(msf.get_timer_discontinuity(), false);
+        assert_eq!(msf.get_new_second(), true);
     }
     #[test]
-    fn test_new_edge_active_runaway() {
-        const EDGE_BUFFER: [(bool, u32); 3] = [
-            // active runaway (broken bit)
-            (!false, 417_195_653), // 0
-            (!true, 417_908_323),  // 712_670
-            (!false, 419_193_216), // 1_284_893
-        ];
+    fn test_new_edge_timer_discontinuity() {
         let mut msf = MSFUtils::default();
-        assert_eq!(msf.before_first_edge, true);
-        msf.handle_new_edge(EDGE_BUFFER[0].0, EDGE_BUFFER[0].1);
-        assert_eq!(msf.before_first_edge, false);
-        assert_eq!(msf.t0, EDGE_BUFFER[0].1); // very first edge
-
-        msf.handle_new_edge(EDGE_BUFFER[1].0, EDGE_BUFFER[1].1); // first significant edge
-        assert_eq!(msf.t0, EDGE_BUFFER[1].1); // longer than a spike
-        assert_eq!(msf.new_second, true);
-        assert_eq!(msf.past_new_minute, false);
-        assert_eq!(msf.get_current_bit_a(), None); // not yet determined, passive part
-        assert_eq!(msf.get_current_bit_b(), None); // not yet determined, passive part
-
-        msf.handle_new_edge(EDGE_BUFFER[2].0, EDGE_BUFFER[2].1);
-        assert_eq!(msf.t0, EDGE_BUFFER[2].1); // longer than a spike
-        assert_eq!(msf.new_second, false);
-        assert_eq!(msf.past_new_minute, false);
-        assert_eq!(msf.get_current_bit_a(), None);
-        assert_eq!(msf.get_current_bit_b(), None);
+        msf.handle_new_edge(true, 413_999_083);
+        msf.handle_new_edge(false, 414_935_664);
+        assert_eq!(msf.get_new_second(), true);
+        msf.handle_new_edge(true, 1_915_000_000); // 1500 s later
+        assert_eq!(msf.get_timer_discontinuity(), true);
+        assert_eq!(msf.get_new_second(), false);
+        assert_eq!(msf.t0, 1_915_000_000);
+        msf.handle_new_edge(false, 1_915_936_581);
+        assert_eq!(msf.get_timer_discontinuity(), false);
+        assert_eq!(msf.get_new_second(), true);
+        msf.handle_new_edge(true, 1_916_004_582);
+        assert_eq!(msf.get_current_bit_a(), Some(false));
+        assert_eq!(msf.get_current_bit_b(), Some(false));
+    }
+    #[test]
+    fn test_diagnostic_hook() {
+        static DIAGNOSTICS: std::sync::Mutex<Vec<Diagnostic>> = std::sync::Mutex::new(Vec::new());
+        let mut msf = MSFUtils::builder()
+            .diagnostic_hook(|d| DIAGNOSTICS.lock().unwrap().push(d))
+            .build()
+            .unwrap();
+        msf.handle_new_edge(true, 897_105_780);
+        msf.handle_new_edge(false, 898_042_361);
+        msf.handle_new_edge(true, 898_110_362);
+        msf.handle_new_edge(false, 900_067_737); // passive runaway
+        msf.handle_new_edge(true, 990_000_000); // timer paused
+        assert_eq!(
+            *DIAGNOSTICS.lock().unwrap(),
+            [
+                Diagnostic::PassiveRunaway { second: 0 },
+                Diagnostic::TimerDiscontinuity
+            ]
+        );
+        msf = filled_msf();
+        msf.set_diagnostic_hook(Some(|d| DIAGNOSTICS.lock().unwrap().push(d)));
+        msf.bit_buffer_a[48] = Some(!msf.bit_buffer_a[48].unwrap());
+        msf.decode_time(false);
+        assert_eq!(
+            DIAGNOSTICS.lock().unwrap().last(),
+            Some(&Diagnostic::ParityMismatch { group: 4 })
+        );
+    }
+    #[test]
+    #[cfg(feature = "marker-policy")]
+    fn test_marker_policy() {
+        static DIAGNOSTICS: std::sync::Mutex<Vec<Diagnostic>> = std::sync::Mutex::new(Vec::new());
+        for (policy, second) in [
+            (MarkerPolicy::PreferLast, 0),
+            (MarkerPolicy::PreferEndOfMinute, 30),
+            (MarkerPolicy::PreferBeginOfMinute, 0),
+            (MarkerPolicy::RequireBoth, 30),
+        ] {
+            let mut msf = MSFUtils::builder()
+                .marker_policy(policy)
+                .diagnostic_hook(|d| DIAGNOSTICS.lock().unwrap().push(d))
+                .build()
+                .unwrap();
+            assert_eq!(msf.get_marker_policy(), policy);
+            msf.handle_new_edge(false, 1_000_000);
+            msf.handle_new_edge(true, 1_100_000);
+            msf.second = 30;
+            msf.handle_new_edge(false, 2_000_000);
+            msf.handle_new_edge(true, 2_500_000); // begin-of-minute marker
+            assert_eq!(msf.second, second);
+            assert_eq!(
+                DIAGNOSTICS.lock().unwrap().first(),
+                Some(&Diagnostic::MarkerConflict {
+                    second: 30,
+                    marker: MinuteMarker::BeginOfMinute
+                })
+            );
+            DIAGNOSTICS.lock().unwrap().clear();
+        }
+        let mut msf = MSFUtils::default();
+        msf.set_marker_policy(MarkerPolicy::Majority { minutes: 0 });
+        assert_eq!(msf.get_marker_policy(), MarkerPolicy::PreferLast);
+    }
+    #[test]
+    fn test_observer() {
+        use ::core::sync::atomic::{AtomicU32, Ordering::Relaxed};
+        struct Counter {
+            seconds: AtomicU32,
+            minutes: AtomicU32,
+            bits_a: AtomicU32,
+            decoded: AtomicU32,
+            errors: AtomicU32,
+        }
+        impl MSFObserver for Counter {
+            fn on_new_second(&self) {
+                self.seconds.fetch_add(1, Relaxed);
+            }
+            fn on_new_minute(&self) {
+                self.minutes.fetch_add(1, Relaxed);
+            }
+            fn on_bit(&self, second: u8, a: Option<bool>, _b: Option<bool>) {
+                if let Some(a) = a {
+                    assert_eq!(a, BIT_BUFFER_A[second as usize]);
+                    self.bits_a.fetch_add(1, Relaxed);
+                }
+            }
+            fn on_minute_decoded(&self, radio_datetime: &RadioDateTimeUtils) {
+                assert_eq!(radio_datetime.get_minute(), Some(58));
+                self.decoded.fetch_add(1, Relaxed);
+            }
+            fn on_reception_error(&self, kind: Diagnostic) {
+                assert_eq!(kind, Diagnostic::ActiveRunaway { second: 0 });
+                self.errors.fetch_add(1, Relaxed);
+            }
+        }
+        static COUNTER: Counter = Counter {
+            seconds: AtomicU32::new(0),
+            minutes: AtomicU32::new(0),
+            bits_a: AtomicU32::new(0),
+            decoded: AtomicU32::new(0),
+            errors: AtomicU32::new(0),
+        };
+        let mut msf = MSFUtils::builder().observer(&COUNTER).build().unwrap();
+        for (is_low_edge, t) in minute_edges(1_000_000) {
+            msf.handle_new_edge(is_low_edge, t);
+            if msf.get_new_second() {
+                if msf.get_second() + 1 == msf.get_minute_length() {
+                    msf.decode_minute(false).ok();
+                }
+                msf.increase_second();
+            }
+        }
+        msf.handle_new_edge(true, 64_000_000); // active runaway
+        assert_eq!(COUNTER.seconds.load(Relaxed), 60);
+        assert_eq!(COUNTER.minutes.load(Relaxed), 1);
+        assert_eq!(COUNTER.bits_a.load(Relaxed), 59); // all but the first marker
+        assert_eq!(COUNTER.decoded.load(Relaxed), 1);
+        assert_eq!(COUNTER.errors.load(Relaxed), 1);
+        msf.set_observer(None);
+        msf.handle_new_edge(false, 66_000_000);
+        assert_eq!(COUNTER.seconds.load(Relaxed), 60);
+    }
+    #[test]
+    fn test_handle_new_edge_classified() {
+        let mut msf = MSFUtils::default();
+        assert_eq!(
+            msf.handle_new_edge_classified(true, 897_105_780),
+            Some(EdgeClass::First)
+        );
+        assert_eq!(
+            msf.handle_new_edge_classified(false, 898_042_361),
+            Some(EdgeClass::NewSecond)
+        );
+        assert_eq!(
+            msf.handle_new_edge_classified(false, 898_050_000),
+            Some(EdgeClass::Spike)
+        );
+        assert_eq!(
+            msf.handle_new_edge_classified(true, 898_110_362),
+            Some(EdgeClass::Bit { a: false, b: false })
+        );
+        assert_eq!(
+            msf.handle_new_edge_classified(false, 900_067_737),
+            Some(EdgeClass::PassiveRunaway)
+        );
+        #[cfg(feature = "single-shot")]
+        {
+            msf.disarm();
+            assert_eq!(msf.handle_new_edge_classified(true, 900_167_737), None);
+        }
     }
     #[test]
     fn test_new_edge_passive_runaway() {
@@ -787,6 +4438,48 @@ mod tests {
         assert_eq!(msf.get_current_bit_b(), None);
     }
     #[test]
+    fn test_new_edge_passive_runaway_limit() {
+        let mut msf = MSFUtils::builder()
+            .passive_runaway(2_500_000)
+            .strictness(Strictness::Strict)
+            .build()
+            .unwrap();
+        assert_eq!(msf.get_passive_runaway(), 2_500_000);
+        assert_eq!(msf.get_strictness(), Strictness::Strict);
+        msf.handle_new_edge(true, 897_105_780);
+        msf.handle_new_edge(false, 898_042_361);
+        msf.handle_new_edge(true, 898_110_362);
+        msf.handle_new_edge(false, 900_067_737); // 1_957_375 passive, no runaway now
+        assert_eq!(msf.get_new_second(), true);
+        msf.set_passive_runaway(TIMER_DISCONTINUITY); // ignored
+        assert_eq!(msf.get_passive_runaway(), 2_500_000);
+    }
+    #[test]
+    fn test_new_edge_decoder_config() {
+        // a receiver stretching the pulses by 80 ms
+        let config = DecoderConfig {
+            active_0_limit: 230_000,
+            active_a_limit: 330_000,
+            active_ab_limit: 430_000,
+            minute_limit: 630_000,
+            ..DecoderConfig::DEFAULT
+        };
+        for (decoder_config, bits) in [
+            (DecoderConfig::DEFAULT, (Some(true), Some(false))),
+            (config, (Some(false), Some(false))),
+        ] {
+            let mut msf = MSFUtils::builder()
+                .decoder_config(decoder_config)
+                .build()
+                .unwrap();
+            assert_eq!(msf.get_decoder_config(), decoder_config);
+            msf.handle_new_edge(true, 897_105_780);
+            msf.handle_new_edge(false, 898_042_361);
+            msf.handle_new_edge(true, 898_222_361); // 180 ms
+            assert_eq!((msf.get_current_bit_a(), msf.get_current_bit_b()), bits);
+        }
+    }
+    #[test]
     fn test_new_edge_spikes() {
         const EDGE_BUFFER: [(bool, u32); 8] = [
             // spikes
@@ -844,6 +4537,241 @@ mod tests {
         assert_eq!(msf.get_current_bit_a(), Some(true)); // keep value
         assert_eq!(msf.get_current_bit_b(), Some(false)); // keep value
     }
+    #[test]
+    fn test_new_edge_spike_limit_per_polarity() {
+        let mut msf = MSFUtils::builder()
+            .spike_limit_low(10_000)
+            .spike_limit_high(40_000)
+            .build()
+            .unwrap();
+        assert_eq!(msf.get_spike_limit_low(), 10_000);
+        assert_eq!(msf.get_spike_limit_high(), 40_000);
+        assert_eq!(msf.get_spike_limit(), 40_000);
+        msf.handle_new_edge(true, 1_000_000);
+        msf.handle_new_edge(false, 1_020_000); // 20_000, spike for low-to-high edges
+        assert_eq!(msf.t0, 1_020_000); // shifted by the spike
+        assert_eq!(msf.old_t_diff, 0);
+        msf.handle_new_edge(true, 1_040_000); // 20_000, no spike for high-to-low edges
+        assert_eq!(msf.t0, 1_040_000);
+        assert_eq!(msf.old_t_diff, 20_000);
+    }
+    #[test]
+    fn test_new_edge_refractory_window() {
+        let mut msf = MSFUtils::builder()
+            .refractory_window(5_000)
+            .build()
+            .unwrap();
+        assert_eq!(msf.get_refractory_window(), 5_000);
+        msf.handle_new_edge(true, 1_000_000);
+        msf.handle_new_edge(false, 1_900_000);
+        msf.handle_new_edge(false, 1_903_000); // double edge, ignored
+        assert_eq!(msf.t0, 1_900_000);
+        assert_eq!(msf.new_second, true);
+        msf.handle_new_edge(true, 2_000_000); // (0,0) bit
+        assert_eq!(msf.t0, 2_000_000);
+        assert_eq!(msf.get_current_bit_a(), Some(false));
+        assert_eq!(msf.get_current_bit_b(), Some(false));
+        msf.handle_new_edge(true, 2_010_000); // outside of the window
+        assert_eq!(msf.t0, 2_010_000); // handled by the spike filter instead
+        assert_eq!(msf.old_t_diff, 100_000);
+        msf.set_refractory_window(ACTIVE_0_LIMIT); // out of range
+        assert_eq!(msf.get_refractory_window(), 5_000);
+    }
+    #[test]
+    #[cfg(feature = "flywheel")]
+    fn test_new_edge_flywheel() {
+        let mut msf = MSFUtils::builder().flywheel(true).build().unwrap();
+        assert_eq!(msf.get_flywheel(), true);
+        let mut edges = minute_edges(1_000_000);
+        // the pulse of second 30 is missed
+        edges.retain(|&(_, t)| !(31_000_000..32_000_000).contains(&t));
+        for (is_low_edge, t) in edges {
+            msf.handle_new_edge(is_low_edge, t);
+            if msf.get_new_second() {
+                msf.increase_second();
+            }
+            if t == 30_000_000 {
+                assert_eq!(msf.get_flywheel_locked(), true);
+            }
+        }
+        assert_eq!(msf.second, 0); // still aligned, wrapped into the next minute
+        assert_eq!(msf.bit_buffer_a[30], None);
+        assert_eq!(msf.bit_buffer_a[31], Some(BIT_BUFFER_A[31]));
+        assert_eq!(msf.bit_buffer_b[59], Some(BIT_BUFFER_B[59]));
+    }
+    #[test]
+    fn test_time_budget_until_next_edge() {
+        let mut msf = MSFUtils::default();
+        assert_eq!(msf.time_budget_until_next_edge(0), None);
+        msf.handle_new_edge(true, 0);
+        msf.handle_new_edge(false, 900_000); // start of a second
+        assert_eq!(msf.time_budget_until_next_edge(930_000), Some(70_000));
+        msf.handle_new_edge(true, 1_000_000);
+        assert_eq!(msf.time_budget_until_next_edge(1_050_000), Some(50_000)); // (0,1) pair?
+        assert_eq!(msf.time_budget_until_next_edge(1_200_000), Some(700_000));
+        msf.increase_second();
+        assert_eq!(msf.time_budget_until_next_edge(2_000_000), Some(0)); // overdue
+    }
+    #[test]
+    fn test_new_edge_adaptive_limits() {
+        // all pulses stretched by 40 ms:
+        let mut msf = MSFUtils::builder().adaptive_limits(true).build().unwrap();
+        assert_eq!(msf.get_adaptive_limits(), true);
+        let mut t = 0;
+        msf.handle_new_edge(true, t);
+        for _ in 0..adaptive_limits::CLUSTER_MIN_SAMPLES {
+            for active in [140_000, 240_000, 340_000] {
+                t += 1_000_000 - active;
+                msf.handle_new_edge(false, t);
+                t += active;
+                msf.handle_new_edge(true, t);
+            }
+        }
+        assert_eq!(msf.get_active_limits().limit_a, 290_000);
+        // a 280 ms (1,0) pulse would be classified as (1,1) with the fixed limits:
+        t += 720_000;
+        msf.handle_new_edge(false, t);
+        t += 280_000;
+        msf.handle_new_edge(true, t);
+        assert_eq!(msf.get_current_bit_a(), Some(true));
+        assert_eq!(msf.get_current_bit_b(), Some(false));
+        msf.set_adaptive_limits(false);
+        assert_eq!(msf.get_active_limits(), ActiveLimits::FIXED);
+    }
+    #[test]
+    fn test_new_edge_pulse_bias() {
+        // (1,0) bits stretched by 40 ms:
+        let mut msf = MSFUtils::builder()
+            .pulse_bias_compensation(true)
+            .build()
+            .unwrap();
+        assert_eq!(msf.get_pulse_bias_compensation(), true);
+        let mut t = 0;
+        msf.handle_new_edge(true, t);
+        for _ in 0..PULSE_BIAS_MIN_SAMPLES {
+            t += 760_000;
+            msf.handle_new_edge(false, t);
+            t += 240_000;
+            msf.handle_new_edge(true, t);
+            assert_eq!(msf.get_current_bit_a(), Some(true));
+            assert_eq!(msf.get_current_bit_b(), Some(false));
+        }
+        assert_eq!(msf.get_pulse_bias(), Some(40_000));
+        // a 270 ms (1,0) pulse would be classified as (1,1) without compensation:
+        t += 730_000;
+        msf.handle_new_edge(false, t);
+        t += 270_000;
+        msf.handle_new_edge(true, t);
+        assert_eq!(msf.get_current_bit_a(), Some(true));
+        assert_eq!(msf.get_current_bit_b(), Some(false));
+    }
+    #[test]
+    fn test_builder_out_of_range() {
+        let msf = MSFUtils::builder().spike_limit(20_000).build().unwrap();
+        assert_eq!(msf.get_spike_limit_low(), 20_000);
+        assert_eq!(msf.get_spike_limit_high(), 20_000);
+        assert_eq!(
+            MSFUtils::builder()
+                .spike_limit(20_000)
+                .spike_limit_high(ACTIVE_0_LIMIT)
+                .build()
+                .err(),
+            Some(ConfigError::SpikeLimitHigh)
+        );
+        // the first rejected setting is reported
+        assert_eq!(
+            MSFUtils::builder()
+                .passive_runaway(500_000)
+                .decoder_config(DecoderConfig {
+                    minute_limit: 300_000,
+                    ..DecoderConfig::DEFAULT
+                })
+                .watchdog_tolerance(0)
+                .build()
+                .err(),
+            Some(ConfigError::PassiveRunaway)
+        );
+        #[cfg(feature = "marker-policy")]
+        assert_eq!(
+            MSFUtils::builder()
+                .marker_policy(MarkerPolicy::Majority { minutes: 0 })
+                .build()
+                .err(),
+            Some(ConfigError::MarkerPolicy)
+        );
+        #[cfg(feature = "statistics")]
+        assert_eq!(
+            MSFUtils::builder()
+                .edge_ring_length(edge_ring::EDGE_RING_SIZE + 1)
+                .build()
+                .err(),
+            Some(ConfigError::EdgeRingLength)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "fault-injection")]
+    fn test_advance_minutes() {
+        let mut msf = MSFUtils::default();
+        assert_eq!(msf.advance_minutes(1), false); // nothing decoded yet
+        let mut msf = filled_msf();
+        msf.decode(true);
+        assert_eq!(msf.advance_minutes(9 * 60 + 2), true);
+        let rdt = msf.get_radio_datetime();
+        assert_eq!(rdt.get_day(), Some(24));
+        assert_eq!(rdt.get_hour(), Some(0));
+        assert_eq!(rdt.get_minute(), Some(0));
+    }
+    #[test]
+    #[cfg(feature = "fault-injection")]
+    fn test_inject_fault_drop_edge() {
+        let mut msf = MSFUtils::default();
+        msf.handle_new_edge(true, 1_000_000);
+        msf.inject_fault(FaultKind::DropEdge);
+        assert_eq!(msf.get_pending_fault(), Some(FaultKind::DropEdge));
+        msf.handle_new_edge(false, 1_900_000);
+        assert_eq!(msf.get_pending_fault(), None);
+        assert_eq!(msf.t0, 1_000_000); // edge ignored
+        msf.handle_new_edge(false, 1_910_000);
+        assert_eq!(msf.t0, 1_910_000);
+    }
+    #[test]
+    #[cfg(feature = "fault-injection")]
+    fn test_inject_fault_corrupt_bit() {
+        let mut msf = MSFUtils::default();
+        msf.inject_fault(FaultKind::CorruptBit);
+        msf.set_current_bit_a(None); // nothing to corrupt
+        assert_eq!(msf.get_current_bit_a(), None);
+        assert_eq!(msf.get_pending_fault(), Some(FaultKind::CorruptBit));
+        msf.set_current_bit_a(Some(true));
+        assert_eq!(msf.get_current_bit_a(), Some(false));
+        assert_eq!(msf.get_pending_fault(), None);
+        // a short pulse leaves the bit of the last minute in place, which is not corrupted
+        msf.handle_new_edge(true, 413_999_083);
+        msf.handle_new_edge(false, 414_299_083);
+        msf.bit_buffer_a[0] = Some(true);
+        msf.inject_fault(FaultKind::CorruptBit);
+        msf.handle_new_edge(true, 414_399_083);
+        assert_eq!(msf.get_current_bit_a(), Some(true));
+        assert_eq!(msf.get_pending_fault(), Some(FaultKind::CorruptBit));
+        // via handle_new_edge(), (1,0) bit turns into (0,0):
+        msf.handle_new_edge(false, 415_309_075);
+        msf.handle_new_edge(true, 415_490_038);
+        assert_eq!(msf.get_current_bit_a(), Some(false));
+        assert_eq!(msf.get_current_bit_b(), Some(false));
+        assert_eq!(msf.get_pending_fault(), None);
+    }
+    #[test]
+    #[cfg(feature = "fault-injection")]
+    fn test_inject_fault_delay_second() {
+        let mut msf = MSFUtils::default();
+        msf.second = 10;
+        msf.inject_fault(FaultKind::DelaySecond);
+        assert_eq!(msf.increase_second(), true);
+        assert_eq!(msf.second, 10);
+        assert_eq!(msf.increase_second(), true);
+        assert_eq!(msf.second, 11);
+    }
 
     #[test]
     fn test_eom_marker_too_short() {
@@ -874,6 +4802,69 @@ mod tests {
         assert_eq!(msf.end_of_minute_marker_present(), true);
     }
 
+    #[test]
+    fn test_remaining_required_seconds() {
+        let mut msf = MSFUtils::default();
+        assert_eq!(msf.remaining_required_seconds(false), 17..=58);
+        assert_eq!(msf.remaining_required_seconds(true), 1..=59);
+        msf.second = 40;
+        assert_eq!(msf.remaining_required_seconds(false), 41..=58);
+        msf.second = 58;
+        assert_eq!(msf.remaining_required_seconds(false).count(), 0);
+        assert_eq!(msf.remaining_required_seconds(true), 59..=59);
+        for b in 53..=59 {
+            msf.bit_buffer_a[b] = Some(BIT_BUFFER_A[b - 1]);
+        }
+        msf.second = 59;
+        assert_eq!(msf.get_minute_length(), 61);
+        assert_eq!(msf.remaining_required_seconds(true), 60..=60);
+        msf.second = 3;
+        assert_eq!(msf.remaining_required_seconds(false), 17..=58); // no marker yet
+    }
+    #[test]
+    fn test_eom_marker_eta() {
+        let mut msf = MSFUtils::builder().prediction_horizon(3).build().unwrap();
+        assert_eq!(msf.get_prediction_horizon(), 3);
+        msf.set_prediction_horizon(8); // out of range
+        assert_eq!(msf.get_prediction_horizon(), 3);
+        msf.second = 48;
+        for (bit, value) in msf.bit_buffer_a[41..=48]
+            .iter_mut()
+            .zip(&BIT_BUFFER_A[41..=48])
+        {
+            *bit = Some(*value);
+        }
+        assert_eq!(msf.eom_marker_eta(), None);
+        assert_eq!(msf.marker_completion_probability(), 0);
+        msf.second = 56;
+        for (bit, value) in msf.bit_buffer_a[49..=56]
+            .iter_mut()
+            .zip(&BIT_BUFFER_A[49..=56])
+        {
+            *bit = Some(*value);
+        }
+        assert_eq!(msf.eom_marker_eta(), Some(3));
+        assert_eq!(msf.marker_completion_probability(), 96);
+        msf.second = 59;
+        for (bit, value) in msf.bit_buffer_a[57..=59]
+            .iter_mut()
+            .zip(&BIT_BUFFER_A[57..=59])
+        {
+            *bit = Some(*value);
+        }
+        assert_eq!(msf.eom_marker_eta(), Some(0));
+        assert_eq!(msf.marker_completion_probability(), 99);
+        // the beginning of the marker elsewhere in the minute is not matched
+        msf.second = 24;
+        for (b, bit) in [false, true, true, true, true, true]
+            .into_iter()
+            .enumerate()
+        {
+            msf.bit_buffer_a[19 + b] = Some(bit);
+        }
+        assert_eq!(msf.eom_marker_eta(), None);
+        assert_eq!(msf.marker_completion_probability(), 0);
+    }
     #[test]
     fn test_running_negative_leap_second() {
         let mut msf = MSFUtils::default();
@@ -890,6 +4881,141 @@ mod tests {
         assert_eq!(msf.get_minute_length(), 59); // negative leap second
     }
     #[test]
+    fn test_fixed_minute_length() {
+        static TABLE: [LeapSecondEntry; 1] = [LeapSecondEntry {
+            year: 22,
+            month: 10,
+            day: 23,
+            hour: 14,
+            minute: 58,
+            minute_length: 59,
+        }];
+        let mut msf = MSFUtils::builder()
+            .fixed_minute_length(true)
+            .build()
+            .unwrap();
+        assert_eq!(msf.get_fixed_minute_length(), true);
+        msf.second = 58;
+        for b in 51..=58 {
+            msf.bit_buffer_a[b] = Some(BIT_BUFFER_A[b + 1]);
+        }
+        assert_eq!(msf.end_of_minute_marker_present(), true);
+        assert_eq!(msf.get_minute_length(), 60); // early marker ignored
+        msf.set_leap_table(&TABLE);
+        assert_eq!(msf.get_leap_table().len(), 1);
+        for b in 0..=59 {
+            msf.bit_buffer_a[b] = Some(BIT_BUFFER_A[b]);
+            msf.bit_buffer_b[b] = Some(BIT_BUFFER_B[b]);
+        }
+        msf.second = 59;
+        assert_eq!(msf.get_minute_length(), 60); // nothing decoded yet
+        msf.decode_time(false);
+        assert_eq!(msf.get_minute_length(), 59); // 14:58 is in the table
+    }
+    #[test]
+    fn test_new_edge_fixed_minute_length() {
+        for fixed in [false, true] {
+            let mut msf = MSFUtils::builder()
+                .fixed_minute_length(fixed)
+                .build()
+                .unwrap();
+            msf.handle_new_edge(false, 1_000_000);
+            msf.handle_new_edge(true, 1_100_000);
+            msf.second = 57;
+            for b in 50..57 {
+                msf.bit_buffer_a[b] = Some(BIT_BUFFER_A[b + 2]);
+            }
+            msf.handle_new_edge(false, 2_000_000);
+            msf.handle_new_edge(true, 2_100_000); // completes a marker at second 57
+            assert_eq!(msf.end_of_minute_marker_present(), true);
+            assert_eq!(msf.get_new_minute(), !fixed);
+            assert_eq!(msf.get_minute_length(), 60);
+        }
+    }
+    #[test]
+    #[cfg(feature = "statistics")]
+    fn test_reception_quality() {
+        let mut msf = MSFUtils::default();
+        assert_eq!(msf.get_reception_quality(), 0);
+        for (is_low_edge, t) in minute_edges(1_000_000) {
+            msf.handle_new_edge(is_low_edge, t);
+            if msf.get_new_second() {
+                if msf.get_second() + 1 == msf.get_minute_length() {
+                    let _ = msf.decode_minute(false);
+                }
+                msf.increase_second();
+            }
+        }
+        // the first marker is incomplete, so second 0 has no bits
+        assert_eq!(
+            msf.get_reception_quality(),
+            reception_quality::score_minute(59, 60, 0, 0, 0)
+        );
+    }
+    #[test]
+    fn test_leap_second_event() {
+        // decode the minute ending with the given one, which lasts `minute_length` seconds
+        fn decode(msf: &mut MSFUtils, (day, hour, minute): (u8, u8, u8), minute_length: u8) {
+            let mut rdt = RadioDateTimeUtils::new(0);
+            let (year, month) = if day == 1 { (17, 1) } else { (16, 12) };
+            rdt.set_year(Some(year), true, false);
+            rdt.set_month(Some(month), true, false);
+            rdt.set_day(Some(day), true, false);
+            rdt.set_weekday(Some(if day == 1 { 0 } else { 6 }), true, false);
+            rdt.set_hour(Some(hour), true, false);
+            rdt.set_minute(Some(minute), true, false);
+            rdt.set_dst(Some(false), Some(false), false);
+            let options = frame::EncodeOptions {
+                minute_length,
+                ..frame::EncodeOptions::default()
+            };
+            let frame = frame::MSFFrame::encode(&rdt, &options).unwrap();
+            msf.bit_buffer_a = frame.bits_a;
+            msf.bit_buffer_b = frame.bits_b;
+            msf.second = minute_length - 1;
+            assert_eq!(msf.decode_minute(false).is_ok(), true);
+        }
+        let mut msf = MSFUtils::default();
+        decode(&mut msf, (31, 23, 58), 60);
+        decode(&mut msf, (31, 23, 59), 60);
+        decode(&mut msf, (1, 0, 0), 61); // 23:59 lasted 61 seconds
+        assert_eq!(msf.get_leap_second_event(), None); // not confirmed yet
+        decode(&mut msf, (1, 0, 1), 60);
+        let event = msf.get_leap_second_event().unwrap();
+        assert_eq!((event.day, event.hour, event.minute), (31, 23, 59));
+        assert_eq!(event.direction(), upcoming_events::LeapDirection::Positive);
+        // a jump in the next minute withdraws the leap second
+        let mut msf = MSFUtils::default();
+        decode(&mut msf, (31, 23, 59), 60);
+        decode(&mut msf, (1, 0, 0), 61);
+        decode(&mut msf, (1, 0, 5), 60);
+        assert_eq!(msf.get_leap_second_event(), None);
+    }
+    #[test]
+    fn test_invariants_hold() {
+        let mut msf = MSFUtils::builder().invariant_checks(true).build().unwrap();
+        assert_eq!(msf.get_invariant_checks(), true);
+        assert_eq!(msf.invariants_hold(), true);
+        msf.handle_new_edge(true, 4_294_567_000);
+        msf.handle_new_edge(false, 4_294_867_000);
+        msf.handle_new_edge(true, 100_000); // wraps around
+        assert_eq!(msf.invariants_hold(), true);
+        msf.second = 61;
+        assert_eq!(msf.invariants_hold(), false);
+    }
+    #[test]
+    fn test_invariants_long_pause() {
+        let mut msf = MSFUtils::builder().invariant_checks(true).build().unwrap();
+        msf.handle_new_edge(true, 413_999_083);
+        msf.handle_new_edge(false, 414_935_664);
+        msf.handle_new_edge(true, 2_814_935_664); // 40 minutes later, wraps past 0x8000_0000
+        assert_eq!(msf.get_timer_discontinuity(), true);
+        assert_eq!(msf.t0, 2_814_935_664);
+        msf.handle_new_edge(false, 2_814_000_000); // out of order
+        assert_eq!(msf.get_timer_discontinuity(), true);
+        assert_eq!(msf.invariants_hold(), true);
+    }
+    #[test]
     fn test_running_no_leap_second() {
         let mut msf = MSFUtils::default();
         msf.second = 52;
@@ -917,18 +5043,41 @@ mod tests {
         assert_eq!(msf.second, 59);
         msf.bit_buffer_a[59] = Some(BIT_BUFFER_A[58]);
         assert_eq!(msf.end_of_minute_marker_present(), false);
-        assert_eq!(msf.search_eom_marker(true), true);
+        assert_eq!(msf.search_eom_marker(1), true);
         assert_eq!(msf.get_minute_length(), 61); // positive leap second (without trailing 0 bit)
         assert_eq!(msf.increase_second(), true);
         assert_eq!(msf.second, 60);
         msf.bit_buffer_a[60] = Some(BIT_BUFFER_A[59]);
         assert_eq!(msf.end_of_minute_marker_present(), true);
-        assert_eq!(msf.search_eom_marker(true), false);
+        assert_eq!(msf.search_eom_marker(1), false);
         assert_eq!(msf.get_minute_length(), 61); // positive leap second (without trailing 0 bit)
     }
 
     // relaxed checks
     #[test]
+    fn test_decode_time_merge_policy() {
+        for (policy, hour, minute) in [
+            (MergePolicy::Parity, 14, 59),
+            (MergePolicy::Confidence { threshold: 1 }, 15, 0),
+        ] {
+            let mut msf = filled_msf();
+            msf.set_merge_policy(policy);
+            assert_eq!(msf.get_merge_policy(), policy);
+            msf.decode_time(false);
+            // 14:59
+            msf.bit_buffer_a[51] = Some(true);
+            msf.bit_buffer_b[57] = Some(true);
+            msf.decode_time(false);
+            assert_eq!(msf.get_field_confidence().hour_minute, 1);
+            // 14:59 again, while 15:00 is expected
+            msf.decode_time(false);
+            assert_eq!(msf.get_field_confidence().hour_minute, 0);
+            assert_eq!(msf.radio_datetime.get_hour(), Some(hour));
+            assert_eq!(msf.radio_datetime.get_minute(), Some(minute));
+            assert_eq!(msf.get_field_confidence().year, 2);
+        }
+    }
+    #[test]
     fn test_decode_time_incomplete_minute() {
         let mut msf = MSFUtils::default();
         assert_eq!(msf.first_minute, true);
@@ -967,7 +5116,8 @@ mod tests {
             Some(radio_datetime_utils::DST_SUMMER)
         );
         assert_eq!(msf.radio_datetime.get_leap_second(), None); // not available
-        assert_eq!(msf.dut1, Some(-2));
+        #[cfg(not(feature = "no-dut1"))]
+        assert_eq!(msf.get_dut1(), Some(-2));
     }
     #[test]
     fn test_decode_time_complete_minute_ok_negative_leap_second() {
@@ -1001,7 +5151,8 @@ mod tests {
             Some(radio_datetime_utils::DST_SUMMER)
         );
         assert_eq!(msf.radio_datetime.get_leap_second(), None); // not available
-        assert_eq!(msf.dut1, Some(-2));
+        #[cfg(not(feature = "no-dut1"))]
+        assert_eq!(msf.get_dut1(), Some(-2));
         assert_eq!(msf.first_minute, false);
     }
     #[test]
@@ -1038,7 +5189,8 @@ mod tests {
             Some(radio_datetime_utils::DST_SUMMER)
         );
         assert_eq!(msf.radio_datetime.get_leap_second(), None); // not available
-        assert_eq!(msf.dut1, Some(-2));
+        #[cfg(not(feature = "no-dut1"))]
+        assert_eq!(msf.get_dut1(), Some(-2));
         assert_eq!(msf.first_minute, false);
     }
     #[test]
@@ -1070,7 +5222,42 @@ mod tests {
             Some(radio_datetime_utils::DST_SUMMER)
         );
         assert_eq!(msf.radio_datetime.get_leap_second(), None);
-        assert_eq!(msf.dut1, None);
+        assert_eq!(msf.get_dut1(), None);
+    }
+    #[test]
+    fn test_parity_bits_bad_bits() {
+        let mut msf = MSFUtils::default();
+        msf.second = 59;
+        for b in 0..=59 {
+            msf.bit_buffer_a[b] = Some(BIT_BUFFER_A[b]);
+            msf.bit_buffer_b[b] = Some(BIT_BUFFER_B[b]);
+        }
+        msf.bit_buffer_a[31] = None; // None hour
+        msf.bit_buffer_a[48] = Some(!msf.bit_buffer_a[48].unwrap());
+        msf.decode_time(false);
+        assert_eq!(
+            msf.get_parity_bits_1(),
+            ParityBits {
+                computed: Some(true),
+                received: Some(true)
+            }
+        );
+        assert_eq!(msf.get_parity_bits_2().computed, None); // broken bit
+        assert_eq!(msf.get_parity_bits_2().received, Some(true));
+        assert_eq!(
+            msf.get_parity_bits_3(),
+            ParityBits {
+                computed: Some(true),
+                received: Some(true)
+            }
+        );
+        assert_eq!(
+            msf.get_parity_bits_4(),
+            ParityBits {
+                computed: Some(true),
+                received: Some(false)
+            }
+        ); // data bit flipped
     }
     #[test]
     fn continue_decode_time_complete_minute_jumped_values() {
@@ -1228,7 +5415,8 @@ mod tests {
             Some(radio_datetime_utils::DST_SUMMER)
         );
         assert_eq!(msf.radio_datetime.get_leap_second(), None); // not available
-        assert_eq!(msf.dut1, Some(-2));
+        #[cfg(not(feature = "no-dut1"))]
+        assert_eq!(msf.get_dut1(), Some(-2));
     }
     #[test]
     fn test_decode_time_complete_minute_ok_negative_leap_second_strict() {
@@ -1262,7 +5450,8 @@ mod tests {
             Some(radio_datetime_utils::DST_SUMMER)
         );
         assert_eq!(msf.radio_datetime.get_leap_second(), None); // not available
-        assert_eq!(msf.dut1, Some(-2));
+        #[cfg(not(feature = "no-dut1"))]
+        assert_eq!(msf.get_dut1(), Some(-2));
         assert_eq!(msf.first_minute, false);
     }
     #[test]
@@ -1299,7 +5488,8 @@ mod tests {
             Some(radio_datetime_utils::DST_SUMMER)
         );
         assert_eq!(msf.radio_datetime.get_leap_second(), None); // not available
-        assert_eq!(msf.dut1, Some(-2));
+        #[cfg(not(feature = "no-dut1"))]
+        assert_eq!(msf.get_dut1(), Some(-2));
         assert_eq!(msf.first_minute, false);
     }
     #[test]
@@ -1331,7 +5521,7 @@ mod tests {
             Some(radio_datetime_utils::DST_SUMMER)
         ); // not affected by strict checking
         assert_eq!(msf.radio_datetime.get_leap_second(), None);
-        assert_eq!(msf.dut1, None);
+        assert_eq!(msf.get_dut1(), None);
     }
     #[test]
     fn continue_decode_time_complete_minute_jumped_values_strict() {
@@ -1451,6 +5641,28 @@ mod tests {
         ); // DST flipped on
     }
 
+    #[test]
+    #[cfg(feature = "statistics")]
+    fn test_decode_time_gap_histogram() {
+        let mut msf = MSFUtils::default();
+        msf.second = 59;
+        for b in 0..=59 {
+            msf.bit_buffer_a[b] = Some(BIT_BUFFER_A[b]);
+            msf.bit_buffer_b[b] = Some(BIT_BUFFER_B[b]);
+        }
+        msf.decode_time(false);
+        assert_eq!(msf.get_gap_histogram().get_total(), 0); // first decode, no gap yet
+        msf.second = 30;
+        msf.decode_time(false); // incomplete minute
+        msf.second = 59;
+        msf.decode_time(false);
+        msf.decode_time(false);
+        assert_eq!(msf.get_gap_histogram().get_count(1), 1);
+        assert_eq!(msf.get_gap_histogram().get_count(2), 1);
+        msf.reset_gap_histogram();
+        assert_eq!(msf.get_gap_histogram().get_total(), 0);
+    }
+
     #[test]
     fn test_increase_second_same_minute_ok() {
         let mut msf = MSFUtils::default();
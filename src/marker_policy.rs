@@ -0,0 +1,149 @@
+//! Policies to decide which minute marker aligns the second counter when the end-of-minute
+//! marker (0111_1110) and the 500 ms begin-of-minute marker disagree about where second 0 is.
+
+/// The two markers of the start of a minute.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum MinuteMarker {
+    /// The 0111_1110 pattern of A bits at the end of the minute.
+    EndOfMinute,
+    /// The 500 ms pulse at the start of the minute.
+    BeginOfMinute,
+}
+
+/// How a conflict between the two minute markers is resolved.
+#[cfg(feature = "marker-policy")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MarkerPolicy {
+    /// Follow whichever marker arrived last.
+    #[default]
+    PreferLast,
+    /// Follow the end-of-minute marker, ignore a conflicting begin-of-minute marker.
+    PreferEndOfMinute,
+    /// Follow the begin-of-minute marker, ignore a conflicting end-of-minute marker.
+    PreferBeginOfMinute,
+    /// Only realign when the begin-of-minute marker directly follows a conflicting
+    /// end-of-minute marker, i.e. when both markers agree.
+    RequireBoth,
+    /// Follow the marker which agreed with the second counter more often, counting up to
+    /// `minutes` agreements, or the last one if both did equally often.
+    Majority { minutes: u8 },
+}
+
+/// Number of times each marker agreed with the second counter, see `MarkerPolicy::Majority`.
+#[cfg(feature = "marker-policy")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MarkerVotes {
+    pub end_of_minute: u8,
+    pub begin_of_minute: u8,
+}
+
+#[cfg(feature = "marker-policy")]
+impl MarkerVotes {
+    fn get_mut(&mut self, marker: MinuteMarker) -> &mut u8 {
+        match marker {
+            MinuteMarker::EndOfMinute => &mut self.end_of_minute,
+            MinuteMarker::BeginOfMinute => &mut self.begin_of_minute,
+        }
+    }
+}
+
+#[cfg(feature = "marker-policy")]
+impl MarkerPolicy {
+    /// Update the votes and return if the marker should align the second counter.
+    ///
+    /// # Arguments
+    /// * `votes` - the number of agreements of both markers
+    /// * `marker` - the marker which arrived
+    /// * `conflict` - the marker disagrees with the second counter
+    /// * `confirmed` - the other marker disagreed with the second counter in the same way just
+    ///   before, only used for `RequireBoth`
+    pub(crate) fn arbitrate(
+        &self,
+        votes: &mut MarkerVotes,
+        marker: MinuteMarker,
+        conflict: bool,
+        confirmed: bool,
+    ) -> bool {
+        let cap = match self {
+            Self::Majority { minutes } => *minutes,
+            _ => u8::MAX,
+        };
+        if !conflict {
+            let own = votes.get_mut(marker);
+            *own = own.saturating_add(1).min(cap);
+            return true;
+        }
+        let (own, other) = match marker {
+            MinuteMarker::EndOfMinute => (votes.end_of_minute, votes.begin_of_minute),
+            MinuteMarker::BeginOfMinute => (votes.begin_of_minute, votes.end_of_minute),
+        };
+        let own_votes = votes.get_mut(marker);
+        *own_votes = own_votes.saturating_sub(1);
+        match self {
+            Self::PreferLast => true,
+            Self::PreferEndOfMinute => marker == MinuteMarker::EndOfMinute,
+            Self::PreferBeginOfMinute => marker == MinuteMarker::BeginOfMinute,
+            Self::RequireBoth => marker == MinuteMarker::BeginOfMinute && confirmed,
+            Self::Majority { .. } => own >= other,
+        }
+    }
+}
+
+#[cfg(all(test, feature = "marker-policy"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arbitrate_fixed() {
+        let mut votes = MarkerVotes::default();
+        for (policy, eom, bom) in [
+            (MarkerPolicy::PreferLast, true, true),
+            (MarkerPolicy::PreferEndOfMinute, true, false),
+            (MarkerPolicy::PreferBeginOfMinute, false, true),
+            (MarkerPolicy::RequireBoth, false, false),
+        ] {
+            assert_eq!(
+                policy.arbitrate(&mut votes, MinuteMarker::EndOfMinute, true, false),
+                eom
+            );
+            assert_eq!(
+                policy.arbitrate(&mut votes, MinuteMarker::BeginOfMinute, true, false),
+                bom
+            );
+            assert_eq!(
+                policy.arbitrate(&mut votes, MinuteMarker::EndOfMinute, false, false),
+                true
+            );
+        }
+        assert_eq!(
+            MarkerPolicy::RequireBoth.arbitrate(
+                &mut votes,
+                MinuteMarker::BeginOfMinute,
+                true,
+                true
+            ),
+            true
+        );
+    }
+    #[test]
+    fn test_arbitrate_majority() {
+        let policy = MarkerPolicy::Majority { minutes: 2 };
+        let mut votes = MarkerVotes::default();
+        for _ in 0..3 {
+            policy.arbitrate(&mut votes, MinuteMarker::BeginOfMinute, false, false);
+        }
+        assert_eq!(votes.begin_of_minute, 2); // capped
+        policy.arbitrate(&mut votes, MinuteMarker::EndOfMinute, false, false);
+        assert_eq!(
+            policy.arbitrate(&mut votes, MinuteMarker::EndOfMinute, true, false),
+            false
+        );
+        assert_eq!(votes.end_of_minute, 0);
+        assert_eq!(
+            policy.arbitrate(&mut votes, MinuteMarker::BeginOfMinute, true, false),
+            true
+        );
+        assert_eq!(votes.begin_of_minute, 1);
+    }
+}
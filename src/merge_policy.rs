@@ -0,0 +1,89 @@
+//! Policies to decide which fields of a decoded minute are merged into the held date/time.
+
+/// How the fields of a minute decoded using relaxed checks are merged into the date/time.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MergePolicy {
+    /// Merge each field of which the parity is OK.
+    #[default]
+    Parity,
+    /// Like `Parity`, but reject a field that differs from the held value once if that value
+    /// was confirmed by at least `threshold` consecutive minutes.
+    Confidence { threshold: u8 },
+}
+
+/// Number of consecutive minutes in which the received fields matched the held ones.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FieldConfidence {
+    pub year: u8,
+    pub month_day: u8,
+    pub weekday: u8,
+    pub hour_minute: u8,
+}
+
+impl MergePolicy {
+    /// Update the confidence of a field and return if the received value should be merged.
+    ///
+    /// # Arguments
+    /// * `confidence` - the confidence of the held value
+    /// * `parity_ok` - the parity of the received value is OK
+    /// * `held` - the held (predicted) value
+    /// * `received` - the received value
+    pub(crate) fn merge<T: PartialEq>(
+        &self,
+        confidence: &mut u8,
+        parity_ok: bool,
+        held: T,
+        received: T,
+    ) -> bool {
+        if !parity_ok {
+            return false;
+        }
+        if held == received {
+            *confidence = confidence.saturating_add(1);
+            return true;
+        }
+        let confident = match self {
+            Self::Parity => false,
+            Self::Confidence { threshold } => *confidence >= *threshold,
+        };
+        *confidence = 0;
+        !confident
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_parity() {
+        let mut confidence = 0;
+        assert_eq!(MergePolicy::Parity.merge(&mut confidence, true, 5, 5), true);
+        assert_eq!(confidence, 1);
+        assert_eq!(
+            MergePolicy::Parity.merge(&mut confidence, false, 5, 5),
+            false
+        );
+        assert_eq!(confidence, 1);
+        assert_eq!(MergePolicy::Parity.merge(&mut confidence, true, 5, 6), true);
+        assert_eq!(confidence, 0);
+    }
+    #[test]
+    fn test_merge_confidence() {
+        let policy = MergePolicy::Confidence { threshold: 2 };
+        let mut confidence = 1;
+        assert_eq!(
+            policy.merge(&mut confidence, true, (14, 58), (14, 58)),
+            true
+        );
+        assert_eq!(
+            policy.merge(&mut confidence, true, (14, 59), (15, 59)),
+            false
+        );
+        assert_eq!(confidence, 0);
+        assert_eq!(
+            policy.merge(&mut confidence, true, (14, 59), (15, 59)),
+            true
+        );
+    }
+}
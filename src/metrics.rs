@@ -0,0 +1,138 @@
+//! Counters of the reception problems and decoding results, for monitoring the reception health
+//! of long-running gateways.
+//!
+//! Unlike the audit counters, the metrics can be reset, e.g. after each scrape by a monitoring
+//! system.
+
+/// Running counters of the decoder, see `MSFUtils::get_metrics()`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Metrics {
+    spikes: u32,
+    active_runaways: u32,
+    passive_runaways: u32,
+    minutes_attempted: u32,
+    minutes_decoded: u32,
+    parity_failures: [u32; 4],
+    resyncs: u32,
+}
+
+impl Metrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set all counters to 0.
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    pub(crate) fn add_spike(&mut self) {
+        self.spikes = self.spikes.saturating_add(1);
+    }
+
+    pub(crate) fn add_active_runaway(&mut self) {
+        self.active_runaways = self.active_runaways.saturating_add(1);
+    }
+
+    pub(crate) fn add_passive_runaway(&mut self) {
+        self.passive_runaways = self.passive_runaways.saturating_add(1);
+    }
+
+    /// Account for a minute seen by `decode_minute()`.
+    ///
+    /// # Arguments
+    /// * `decoded` - the minute passed the checks
+    pub(crate) fn add_minute(&mut self, decoded: bool) {
+        self.minutes_attempted = self.minutes_attempted.saturating_add(1);
+        if decoded {
+            self.minutes_decoded = self.minutes_decoded.saturating_add(1);
+        }
+    }
+
+    /// Account for a failed parity check.
+    ///
+    /// # Arguments
+    /// * `group` - the parity group, 1 to 4, other values are ignored
+    pub(crate) fn add_parity_failure(&mut self, group: u8) {
+        if (1..=4).contains(&group) {
+            let idx = group as usize - 1;
+            self.parity_failures[idx] = self.parity_failures[idx].saturating_add(1);
+        }
+    }
+
+    pub(crate) fn add_resync(&mut self) {
+        self.resyncs = self.resyncs.saturating_add(1);
+    }
+
+    /// Get the number of spikes which were ignored.
+    pub fn get_spikes(&self) -> u32 {
+        self.spikes
+    }
+
+    /// Get the number of active parts of a second which were too long.
+    pub fn get_active_runaways(&self) -> u32 {
+        self.active_runaways
+    }
+
+    /// Get the number of passive parts of a second which were too long.
+    pub fn get_passive_runaways(&self) -> u32 {
+        self.passive_runaways
+    }
+
+    /// Get the number of minutes seen by `decode_minute()`.
+    pub fn get_minutes_attempted(&self) -> u32 {
+        self.minutes_attempted
+    }
+
+    /// Get the number of minutes which passed the checks.
+    pub fn get_minutes_decoded(&self) -> u32 {
+        self.minutes_decoded
+    }
+
+    /// Get the number of failed checks of the given parity.
+    ///
+    /// # Arguments
+    /// * `group` - the parity group, 1 to 4, 0 is returned for other values
+    pub fn get_parity_failures(&self, group: u8) -> u32 {
+        if (1..=4).contains(&group) {
+            self.parity_failures[group as usize - 1]
+        } else {
+            0
+        }
+    }
+
+    /// Get the number of times the second counter was realigned by a begin-of-minute marker.
+    pub fn get_resyncs(&self) -> u32 {
+        self.resyncs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metrics() {
+        let mut metrics = Metrics::new();
+        metrics.add_spike();
+        metrics.add_active_runaway();
+        metrics.add_passive_runaway();
+        metrics.add_passive_runaway();
+        metrics.add_minute(true);
+        metrics.add_minute(false);
+        metrics.add_parity_failure(3);
+        metrics.add_parity_failure(5); // ignored
+        metrics.add_resync();
+        assert_eq!(metrics.get_spikes(), 1);
+        assert_eq!(metrics.get_active_runaways(), 1);
+        assert_eq!(metrics.get_passive_runaways(), 2);
+        assert_eq!(metrics.get_minutes_attempted(), 2);
+        assert_eq!(metrics.get_minutes_decoded(), 1);
+        assert_eq!(metrics.get_parity_failures(3), 1);
+        assert_eq!(metrics.get_parity_failures(1), 0);
+        assert_eq!(metrics.get_parity_failures(5), 0);
+        assert_eq!(metrics.get_resyncs(), 1);
+        metrics.reset();
+        assert_eq!(metrics, Metrics::default());
+    }
+}
@@ -0,0 +1,159 @@
+//! Rolling log of the raw bits of the last minutes together with their decode outcome, for
+//! finding out after the fact why a minute failed to decode.
+
+use crate::api2::{DecodeError, Parities, Snapshot};
+use crate::frame::MSFFrame;
+use crate::MSFUtils;
+
+/// Raw bits and decode outcome of one minute.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MinuteRecord {
+    /// Hour of the date/time held after decoding, if known.
+    pub hour: Option<u8>,
+    /// Minute of the date/time held after decoding, if known.
+    pub minute: Option<u8>,
+    /// The received bits, they can be decoded again using `MSFFrame::decode()`.
+    pub frame: MSFFrame,
+    /// Results of the parity checks.
+    pub parities: Parities,
+    /// Outcome of `decode_minute()`.
+    pub outcome: Result<(), DecodeError>,
+}
+
+impl Default for MinuteRecord {
+    fn default() -> Self {
+        Self {
+            hour: None,
+            minute: None,
+            frame: MSFFrame::default(),
+            parities: Parities::default(),
+            outcome: Err(DecodeError::Incomplete),
+        }
+    }
+}
+
+/// Ring buffer holding the raw bits of the last `N` minutes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MinuteHistory<const N: usize> {
+    records: [MinuteRecord; N],
+    next: usize,
+    len: usize,
+}
+
+impl<const N: usize> Default for MinuteHistory<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> MinuteHistory<N> {
+    pub fn new() -> Self {
+        Self {
+            records: [MinuteRecord::default(); N],
+            next: 0,
+            len: 0,
+        }
+    }
+
+    /// Record the minute which was just decoded, replacing the oldest record if the history is
+    /// full.
+    ///
+    /// This method must be called after `decode_minute()` and _before_ `increase_second()`.
+    ///
+    /// # Arguments
+    /// * `msf` - the decoder
+    /// * `result` - the result of `decode_minute()`
+    pub fn record(&mut self, msf: &MSFUtils, result: &Result<Snapshot, DecodeError>) {
+        if N == 0 {
+            return;
+        }
+        let minute_length = msf.get_minute_length();
+        let mut frame = MSFFrame {
+            minute_length,
+            ..MSFFrame::default()
+        };
+        let len = minute_length as usize;
+        frame.bits_a[..len].copy_from_slice(&msf.bit_buffer_a[..len]);
+        frame.bits_b[..len].copy_from_slice(&msf.bit_buffer_b[..len]);
+        self.records[self.next] = MinuteRecord {
+            hour: msf.radio_datetime.get_hour(),
+            minute: msf.radio_datetime.get_minute(),
+            frame,
+            parities: Parities {
+                year: msf.parity_1,
+                month_day: msf.parity_2,
+                weekday: msf.parity_3,
+                hour_minute: msf.parity_4,
+            },
+            outcome: result.map(|_| ()),
+        };
+        self.next = (self.next + 1) % N;
+        self.len = (self.len + 1).min(N);
+    }
+
+    /// Get the number of recorded minutes.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Return if no minutes are recorded.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Iterate over the recorded minutes, oldest first.
+    pub fn iter(&self) -> impl Iterator<Item = &MinuteRecord> {
+        let start = (self.next + N - self.len) % N.max(1);
+        (0..self.len).map(move |idx| &self.records[(start + idx) % N])
+    }
+
+    /// Find the most recent record of the given hour and minute.
+    ///
+    /// # Arguments
+    /// * `hour` - the hour of the minute
+    /// * `minute` - the minute to find
+    pub fn find(&self, hour: u8, minute: u8) -> Option<&MinuteRecord> {
+        self.iter()
+            .filter(|r| r.hour == Some(hour) && r.minute == Some(minute))
+            .last()
+    }
+
+    /// Remove all records.
+    pub fn clear(&mut self) {
+        self.next = 0;
+        self.len = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{filled_msf, BIT_BUFFER_A};
+
+    #[test]
+    fn test_record() {
+        let mut msf = filled_msf();
+        let mut history = MinuteHistory::<2>::new();
+        assert_eq!(history.is_empty(), true);
+        msf.bit_buffer_a[48] = Some(!BIT_BUFFER_A[48]);
+        let result = msf.decode_minute(true);
+        history.record(&msf, &result);
+        msf.bit_buffer_a[48] = Some(BIT_BUFFER_A[48]);
+        let result = msf.decode_minute(true);
+        history.record(&msf, &result);
+        let failed = history.iter().next().unwrap();
+        assert_eq!(failed.outcome, Err(DecodeError::Invalid));
+        assert_eq!(failed.parities.hour_minute, Some(false));
+        history.record(&msf, &result);
+        assert_eq!(history.len(), 2);
+        assert_eq!(history.iter().all(|r| r.outcome.is_ok()), true);
+        let record = history.find(14, 58).unwrap();
+        assert_eq!(record.outcome, Ok(()));
+        assert_eq!(record.parities.all_ok(), true);
+        assert_eq!(record.frame.bits_a[48], Some(BIT_BUFFER_A[48]));
+        assert_eq!(record.frame.decode(true).is_ok(), true);
+        assert_eq!(history.find(14, 59), None);
+        history.clear();
+        assert_eq!(history.iter().count(), 0);
+    }
+}
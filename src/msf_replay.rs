@@ -0,0 +1,119 @@
+//! Replay of a textual bit log, which drives the decoder through `set_current_bit_a()`,
+//! `set_current_bit_b()`, `force_new_minute()`, `decode_minute()`, and `increase_second()` in the
+//! order they must be called in.
+//!
+//! The log holds one character per second and one line per minute:
+//! * `0` to `3` - the bits of the second as a number, A is the high bit, so `2` means (1,0)
+//! * `_` - a second with unknown bits
+//! * a newline - the end of the minute, which is then decoded
+//! * `#` - starts a comment running to the end of the line
+//!
+//! Other characters, like spaces grouping the seconds, are ignored. Edge logs are replayed using
+//! `MSFUtils::decode_edges()` instead.
+//!
+//! Enabled with the `std` feature.
+
+use crate::api2::{DecodeError, Snapshot};
+use crate::MSFUtils;
+use core::str::Chars;
+
+/// Iterator over the decoded minutes of a bit log, see `MSFUtils::replay_log()`.
+pub struct LogReplay<'a> {
+    msf: &'a mut MSFUtils,
+    chars: Chars<'a>,
+    strict_checks: bool,
+    pending: bool, // the bits of the current second are set, increase_second() is still due
+}
+
+impl LogReplay<'_> {
+    /// Set the bits of the next second.
+    fn set_bits(&mut self, a: Option<bool>, b: Option<bool>) {
+        if core::mem::take(&mut self.pending) {
+            self.msf.increase_second();
+        }
+        self.msf.set_current_bit_a(a);
+        self.msf.set_current_bit_b(b);
+        self.pending = true;
+    }
+}
+
+impl Iterator for LogReplay<'_> {
+    type Item = Result<Snapshot, DecodeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(c) = self.chars.next() {
+            match c {
+                '0'..='3' => {
+                    let value = c as u8 - b'0';
+                    self.set_bits(Some(value & 2 != 0), Some(value & 1 != 0));
+                }
+                '_' => self.set_bits(None, None),
+                '#' => {
+                    // keep the newline, it still ends the minute
+                    let rest = self.chars.as_str();
+                    let end = rest.find('\n').unwrap_or(rest.len());
+                    self.chars = rest[end..].chars();
+                }
+                '\n' if self.pending => {
+                    self.pending = false;
+                    self.msf.force_new_minute();
+                    let res = self.msf.decode_minute(self.strict_checks);
+                    self.msf.increase_second();
+                    return Some(res);
+                }
+                _ => {}
+            }
+        }
+        if core::mem::take(&mut self.pending) {
+            self.msf.increase_second();
+        }
+        None
+    }
+}
+
+impl MSFUtils {
+    /// Replay a bit log, yielding the result of each minute, see the `msf_replay` module for the
+    /// format.
+    ///
+    /// The decoder is borrowed while iterating, so it can be queried afterwards.
+    ///
+    /// # Arguments
+    /// * `log` - the contents of the log
+    /// * `strict_checks` - passed to `decode_minute()`
+    pub fn replay_log<'a>(&'a mut self, log: &'a str, strict_checks: bool) -> LogReplay<'a> {
+        LogReplay {
+            msf: self,
+            chars: log.chars(),
+            strict_checks,
+            pending: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{BIT_BUFFER_A, BIT_BUFFER_B};
+
+    #[test]
+    fn test_replay_log() {
+        let mut line: String = (0..60)
+            .map(|s| char::from(b'0' + 2 * BIT_BUFFER_A[s] as u8 + BIT_BUFFER_B[s] as u8))
+            .collect();
+        line.push('\n');
+        let log = format!("# two minutes\n{line}{line}");
+        let mut msf = MSFUtils::default();
+        let minutes: Vec<_> = msf.replay_log(&log, true).collect();
+        assert_eq!(minutes.len(), 2);
+        let snapshot = minutes[1].ok().unwrap();
+        assert_eq!(snapshot.radio_datetime.get_hour(), Some(14));
+        assert_eq!(snapshot.radio_datetime.get_minute(), Some(58));
+        assert_eq!(msf.get_second(), 0);
+        // an unknown hour bit fails the strict checks
+        line.replace_range(40..41, "_");
+        let minutes: Vec<_> = msf.replay_log(&line, true).collect();
+        assert_eq!(minutes.len(), 1);
+        assert_eq!(minutes[0].is_err(), true);
+        assert_eq!(msf.get_second(), 0);
+    }
+}
@@ -0,0 +1,127 @@
+//! Majority voting of the date and DUT1 bits across consecutive minutes.
+//!
+//! On marginal reception most bits are right most of the time, so voting can resolve a minute
+//! which is not clean by itself. Only bits which stay the same during a day are voted: the
+//! year, month, day, and weekday with their parities, and DUT1. The history is cleared when a
+//! new day starts.
+
+use crate::frame_layout;
+
+/// Maximum number of minutes to vote over.
+pub const VOTING_DEPTH_MAX: u8 = 8;
+
+/// Number of nominal seconds in a minute.
+const POSITIONS: usize = 60;
+
+/// Return if the bit at the given nominal second is voted.
+///
+/// # Arguments
+/// * `channel_b` - the bit is a B bit
+/// * `nominal` - the nominal second of the bit
+fn is_voted(channel_b: bool, nominal: u8) -> bool {
+    if channel_b {
+        (1..=16).contains(&nominal) || (54..=56).contains(&nominal)
+    } else {
+        (17..=38).contains(&nominal)
+    }
+}
+
+/// Last received values of the voted bits, as shift registers with the newest value in bit 0.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct BitVoter {
+    known: [[u8; POSITIONS]; 2],
+    value: [[u8; POSITIONS]; 2],
+}
+
+impl Default for BitVoter {
+    fn default() -> Self {
+        Self {
+            known: [[0; POSITIONS]; 2],
+            value: [[0; POSITIONS]; 2],
+        }
+    }
+}
+
+impl BitVoter {
+    /// Forget the received values, e.g. because a new day starts.
+    pub(crate) fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Add the bits of a minute to the history and replace the voted bits with the majority.
+    ///
+    /// A bit is left as received if there is no majority. Returns the number of bits changed.
+    ///
+    /// # Arguments
+    /// * `depth` - number of minutes to vote over, at most `VOTING_DEPTH_MAX`
+    /// * `bit_buffer_a` - the received A bits
+    /// * `bit_buffer_b` - the received B bits
+    /// * `minute_length` - length of the minute in seconds
+    pub(crate) fn vote(
+        &mut self,
+        depth: u8,
+        bit_buffer_a: &mut [Option<bool>],
+        bit_buffer_b: &mut [Option<bool>],
+        minute_length: u8,
+    ) -> u8 {
+        let mask = ((1u16 << depth) - 1) as u8;
+        let mut changed = 0;
+        for second in 0..minute_length {
+            let Some(nominal) = frame_layout::nominal_second(second, minute_length) else {
+                continue;
+            };
+            for (channel, buffer) in [&mut *bit_buffer_a, &mut *bit_buffer_b]
+                .into_iter()
+                .enumerate()
+            {
+                if !is_voted(channel == 1, nominal) {
+                    continue;
+                }
+                let bit = &mut buffer[second as usize];
+                let known = &mut self.known[channel][nominal as usize];
+                let value = &mut self.value[channel][nominal as usize];
+                *known = (*known << 1 | bit.is_some() as u8) & mask;
+                *value = (*value << 1 | (*bit == Some(true)) as u8) & mask;
+                let ones = (*known & *value).count_ones();
+                let zeros = (*known & !*value).count_ones();
+                let voted = match ones.cmp(&zeros) {
+                    core::cmp::Ordering::Greater => Some(true),
+                    core::cmp::Ordering::Less => Some(false),
+                    core::cmp::Ordering::Equal => *bit,
+                };
+                if voted != *bit {
+                    *bit = voted;
+                    changed += 1;
+                }
+            }
+        }
+        changed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{BIT_BUFFER_A, BIT_BUFFER_B};
+
+    #[test]
+    fn test_vote() {
+        let mut voter = BitVoter::default();
+        let mut bits_a = BIT_BUFFER_A.map(Some);
+        let mut bits_b = BIT_BUFFER_B.map(Some);
+        assert_eq!(voter.vote(3, &mut bits_a, &mut bits_b, 60), 0);
+        assert_eq!(voter.vote(3, &mut bits_a, &mut bits_b, 60), 0);
+        bits_a[20] = Some(true); // wrong year bit
+        bits_a[30] = None; // missing day bit
+        bits_a[45] = Some(false); // minute bit, not voted
+        bits_b[9] = Some(false); // wrong DUT1 bit
+        assert_eq!(voter.vote(3, &mut bits_a, &mut bits_b, 60), 3);
+        assert_eq!(bits_a[20], Some(BIT_BUFFER_A[20]));
+        assert_eq!(bits_a[30], Some(BIT_BUFFER_A[30]));
+        assert_eq!(bits_a[45], Some(false));
+        assert_eq!(bits_b[9], Some(true));
+        voter.clear();
+        bits_a[20] = Some(true);
+        assert_eq!(voter.vote(3, &mut bits_a, &mut bits_b, 60), 0);
+    }
+}
@@ -0,0 +1,190 @@
+//! Reference clock output to ntpd or chrony through their shared memory (SHM) driver, to use the
+//! decoder as a time source on Linux boards.
+//!
+//! Each decoded minute is published as the Unix time of its begin-of-minute marker together with
+//! the time at which the system clock saw that marker, so the daemon measures the offset of the
+//! system clock from the difference. The segment is laid out as `struct shmTime` on 64-bit Linux
+//! and written using the mode 1 protocol, in which the daemon discards samples whose count
+//! changed while it was reading them.
+//!
+//! Attaching the System V segment needs unsafe code, which this crate forbids, so it is left to
+//! the application through the `ShmSegment` trait, e.g. using a shared memory crate.
+//!
+//! Enabled with the `std` feature.
+
+use crate::MSFUtils;
+
+/// Key of the segment of SHM unit 0 ("NTP0"), unit n uses this key plus n.
+pub const SHM_KEY_BASE: u32 = 0x4e54_5030;
+
+/// Size of `struct shmTime` in bytes.
+pub const SHM_SIZE: usize = 96;
+
+/// Precision of the decoded time as a power of two in seconds, about 1 ms.
+pub const MSF_PRECISION: i32 = -10;
+
+// offsets of the fields of struct shmTime
+const MODE: usize = 0;
+const COUNT: usize = 4;
+const CLOCK_SEC: usize = 8;
+const CLOCK_USEC: usize = 16;
+const RECEIVE_SEC: usize = 24;
+const RECEIVE_USEC: usize = 32;
+const LEAP: usize = 36;
+const PRECISION: usize = 40;
+const NSAMPLES: usize = 44;
+const VALID: usize = 48;
+const CLOCK_NSEC: usize = 52;
+const RECEIVE_NSEC: usize = 56;
+
+/// Leap indicator telling the daemon that the clock is not synchronized.
+const LEAP_NOTINSYNC: i32 = 3;
+
+/// Memory of an attached SHM segment of at least `SHM_SIZE` bytes.
+pub trait ShmSegment {
+    /// Write the bytes at the given offset, in the order of the calls.
+    ///
+    /// # Arguments
+    /// * `offset` - offset in bytes from the start of the segment
+    /// * `bytes` - the bytes to write
+    fn write_bytes(&mut self, offset: usize, bytes: &[u8]);
+}
+
+impl ShmSegment for [u8; SHM_SIZE] {
+    fn write_bytes(&mut self, offset: usize, bytes: &[u8]) {
+        self[offset..offset + bytes.len()].copy_from_slice(bytes);
+    }
+}
+
+impl ShmSegment for &mut [u8] {
+    fn write_bytes(&mut self, offset: usize, bytes: &[u8]) {
+        self[offset..offset + bytes.len()].copy_from_slice(bytes);
+    }
+}
+
+/// Split a Unix time in microseconds into seconds and microseconds.
+fn split_micros(unix_time: i64) -> (i64, i32) {
+    (
+        unix_time.div_euclid(1_000_000),
+        unix_time.rem_euclid(1_000_000) as i32,
+    )
+}
+
+/// Writer of reference clock samples into an SHM segment.
+pub struct ShmWriter<S: ShmSegment> {
+    segment: S,
+    count: i32,
+}
+
+impl<S: ShmSegment> ShmWriter<S> {
+    /// Create a writer and initialize the segment for the mode 1 protocol, without a valid
+    /// sample.
+    ///
+    /// # Arguments
+    /// * `segment` - the attached segment
+    pub fn new(mut segment: S) -> Self {
+        segment.write_bytes(VALID, &0i32.to_ne_bytes());
+        segment.write_bytes(MODE, &1i32.to_ne_bytes());
+        segment.write_bytes(PRECISION, &MSF_PRECISION.to_ne_bytes());
+        segment.write_bytes(NSAMPLES, &3i32.to_ne_bytes());
+        Self { segment, count: 0 }
+    }
+
+    /// Return the segment, e.g. to detach it.
+    pub fn into_segment(self) -> S {
+        self.segment
+    }
+
+    /// Get the number of samples written so far.
+    pub fn get_count(&self) -> i32 {
+        self.count
+    }
+
+    /// Publish a sample.
+    ///
+    /// # Arguments
+    /// * `clock_time` - the decoded time in microseconds since the Unix epoch
+    /// * `receive_time` - the system time at which `clock_time` was current, in microseconds
+    ///   since the Unix epoch
+    /// * `leap` - the NTP leap indicator, 0 for no warning
+    pub fn publish(&mut self, clock_time: i64, receive_time: i64, leap: i32) {
+        let (clock_sec, clock_usec) = split_micros(clock_time);
+        let (receive_sec, receive_usec) = split_micros(receive_time);
+        let segment = &mut self.segment;
+        segment.write_bytes(VALID, &0i32.to_ne_bytes());
+        self.count = self.count.wrapping_add(1);
+        segment.write_bytes(COUNT, &self.count.to_ne_bytes());
+        segment.write_bytes(CLOCK_SEC, &clock_sec.to_ne_bytes());
+        segment.write_bytes(CLOCK_USEC, &clock_usec.to_ne_bytes());
+        segment.write_bytes(CLOCK_NSEC, &(clock_usec as u32 * 1000).to_ne_bytes());
+        segment.write_bytes(RECEIVE_SEC, &receive_sec.to_ne_bytes());
+        segment.write_bytes(RECEIVE_USEC, &receive_usec.to_ne_bytes());
+        segment.write_bytes(RECEIVE_NSEC, &(receive_usec as u32 * 1000).to_ne_bytes());
+        segment.write_bytes(LEAP, &leap.to_ne_bytes());
+        self.count = self.count.wrapping_add(1);
+        segment.write_bytes(COUNT, &self.count.to_ne_bytes());
+        segment.write_bytes(VALID, &1i32.to_ne_bytes());
+    }
+
+    /// Publish the minute which was just decoded, to be called right after `increase_second()`
+    /// moved the decoder to second 0 of it.
+    ///
+    /// Returns if a sample was published, which requires the minute to be decoded and its
+    /// date/time to be valid. The leap indicator is set to not synchronized while the decoder
+    /// is still in its first minute.
+    ///
+    /// # Arguments
+    /// * `msf` - the decoder
+    /// * `marker_t` - time stamp of the begin-of-minute marker, as passed to `handle_new_edge()`
+    /// * `receive_time` - the system time at `marker_t`, in microseconds since the Unix epoch
+    pub fn publish_minute(&mut self, msf: &MSFUtils, marker_t: u32, receive_time: i64) -> bool {
+        if msf.minutes_since_decode != Some(0) {
+            return false;
+        }
+        let Some(clock_time) = msf.unix_time_at(marker_t) else {
+            return false;
+        };
+        let leap = if msf.first_minute { LEAP_NOTINSYNC } else { 0 };
+        self.publish(clock_time, receive_time, leap);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::filled_msf;
+
+    fn field(segment: &[u8; SHM_SIZE], offset: usize) -> i32 {
+        i32::from_ne_bytes(segment[offset..offset + 4].try_into().unwrap())
+    }
+
+    #[test]
+    fn test_publish_minute() {
+        let mut msf = filled_msf();
+        let mut writer = ShmWriter::new([0; SHM_SIZE]);
+        assert_eq!(writer.publish_minute(&msf, 0, 0), false);
+        let _ = msf.decode_minute(false);
+        msf.second = 0;
+        msf.second_start = Some(5_000_000);
+        // the system clock runs 2.5 ms ahead
+        assert_eq!(
+            writer.publish_minute(&msf, 5_000_000, 1_666_533_480_002_500),
+            true
+        );
+        assert_eq!(writer.get_count(), 2);
+        let segment = writer.into_segment();
+        assert_eq!(field(&segment, MODE), 1);
+        assert_eq!(field(&segment, COUNT), 2);
+        assert_eq!(field(&segment, VALID), 1);
+        assert_eq!(
+            i64::from_ne_bytes(segment[CLOCK_SEC..CLOCK_SEC + 8].try_into().unwrap()),
+            1_666_533_480 // 2022-10-23 14:58 BST
+        );
+        assert_eq!(field(&segment, CLOCK_USEC), 0);
+        assert_eq!(field(&segment, RECEIVE_USEC), 2_500);
+        assert_eq!(field(&segment, RECEIVE_NSEC), 2_500_000);
+        assert_eq!(field(&segment, PRECISION), MSF_PRECISION);
+        assert_eq!(field(&segment, LEAP), 0);
+    }
+}
@@ -0,0 +1,39 @@
+//! Callbacks for the events of the decoder, as an alternative to polling its getters after each
+//! edge.
+//!
+//! The observer is registered using `MSFUtils::set_observer()`. Its methods are called from within
+//! `handle_new_edge()` and `decode_minute()`, possibly in interrupt context, so they should return
+//! quickly. They take `&self`, so an observer which records anything uses interior mutability,
+//! e.g. atomics or a `critical_section::Mutex`.
+
+use crate::diagnostics::Diagnostic;
+use radio_datetime_utils::RadioDateTimeUtils;
+
+/// Receiver of decoder events, every method does nothing by default.
+pub trait MSFObserver {
+    /// A new second has started, `increase_second()` is still to be called.
+    fn on_new_second(&self) {}
+
+    /// The end-of-minute marker has arrived, the minute completes at the next second.
+    fn on_new_minute(&self) {}
+
+    /// The bits of a second are final, called right before `on_new_second()` of the next second.
+    ///
+    /// # Arguments
+    /// * `second` - the second the bits belong to
+    /// * `a` - bit A, None if it was not received
+    /// * `b` - bit B, None if it was not received
+    fn on_bit(&self, _second: u8, _a: Option<bool>, _b: Option<bool>) {}
+
+    /// A minute has been decoded successfully.
+    ///
+    /// # Arguments
+    /// * `radio_datetime` - the decoded date/time
+    fn on_minute_decoded(&self, _radio_datetime: &RadioDateTimeUtils) {}
+
+    /// A reception error has occurred, i.e. a diagnostic of `Severity::Warning`.
+    ///
+    /// # Arguments
+    /// * `kind` - the diagnostic describing the error
+    fn on_reception_error(&self, _kind: Diagnostic) {}
+}
@@ -0,0 +1,82 @@
+//! Station-independent parts of an on-off keyed (OOK) time code decoder.
+//!
+//! Time code stations like MSF and JJY mark each second with a carrier-off (or carrier-on)
+//! pulse whose length carries the data. Two steps do not depend on the station: framing the
+//! edges into pulses and seconds using `EdgeFraming`, and classifying the pulses using a table
+//! of rules with `classify()`. Both are used by `MSFUtils` with the MSF limits and table.
+//!
+//! The bit buffers, the minute markers, and the decoding of the fields are specific to MSF and
+//! are not part of this module, another station needs its own decoder for those.
+
+use core::ops::RangeInclusive;
+
+pub use crate::core::framing::{EdgeFraming, FramedEdge, FramingLimits};
+
+/// A rule of a pulse classification table.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PulseRule<S> {
+    /// The rule applies to pulses shorter than this, in microseconds.
+    pub active_below: u32,
+    /// The rule applies if the passive part before the pulse lasted this long, in microseconds.
+    /// A passive part of 0 means it is unknown.
+    pub passive: RangeInclusive<u32>,
+    /// The meaning of a matching pulse.
+    pub symbol: S,
+}
+
+/// Classify a pulse using the first matching rule of the table.
+///
+/// Returns `otherwise` if no rule matches.
+///
+/// # Arguments
+/// * `table` - the rules, in order of precedence
+/// * `active` - length of the pulse in microseconds
+/// * `passive` - length of the passive part before the pulse in microseconds, 0 if unknown
+/// * `otherwise` - the meaning of a pulse which matches no rule
+pub fn classify<S: Copy>(table: &[PulseRule<S>], active: u32, passive: u32, otherwise: S) -> S {
+    table
+        .iter()
+        .find(|rule| active < rule.active_below && rule.passive.contains(&passive))
+        .map_or(otherwise, |rule| rule.symbol)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    enum JjySymbol {
+        One,
+        Zero,
+        Marker,
+        Invalid,
+    }
+
+    /// JJY sends the carrier at full power for 800 ms (0), 500 ms (1), or 200 ms (marker).
+    const JJY_TABLE: [PulseRule<JjySymbol>; 3] = [
+        PulseRule {
+            active_below: 350_000,
+            passive: 0..=u32::MAX,
+            symbol: JjySymbol::Marker,
+        },
+        PulseRule {
+            active_below: 650_000,
+            passive: 0..=u32::MAX,
+            symbol: JjySymbol::One,
+        },
+        PulseRule {
+            active_below: 950_000,
+            passive: 0..=u32::MAX,
+            symbol: JjySymbol::Zero,
+        },
+    ];
+
+    #[test]
+    fn test_classify() {
+        let jjy = |active| classify(&JJY_TABLE, active, 0, JjySymbol::Invalid);
+        assert_eq!(jjy(210_000), JjySymbol::Marker);
+        assert_eq!(jjy(490_000), JjySymbol::One);
+        assert_eq!(jjy(800_000), JjySymbol::Zero);
+        assert_eq!(jjy(990_000), JjySymbol::Invalid);
+    }
+}
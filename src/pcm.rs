@@ -0,0 +1,261 @@
+//! Demodulation of recorded PCM samples, so recordings of the signal can be decoded offline
+//! without converting them to edge logs first.
+//!
+//! The samples are either the 60 kHz carrier itself, sampled at more than 120 kHz, or an audio
+//! tone mixed down from it by the receiver. The envelope of the signal is detected by full-wave
+//! rectification and a low-pass filter, and is compared against a threshold halfway between its
+//! tracked peak and trough. The resulting edges follow `SignalConvention::ActiveHigh`.
+//!
+//! Enabled with the `std` feature.
+
+use crate::MSFUtils;
+use std::io::{self, Read};
+
+/// Lowest usable sample rate in Hz, so the envelope filter spans a few samples.
+pub const MIN_SAMPLE_RATE: u32 = 4_000;
+
+/// Fractional bits of the envelope, so the filter does not lose small steps.
+const ENVELOPE_FRACTION: u32 = 8;
+
+/// AM envelope detector which turns PCM samples into the edges of the carrier-off pulses.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PcmDemodulator {
+    sample_rate: u32,
+    envelope_shift: u32, // time constant of the envelope filter, about 5 ms
+    tracking_shift: u32, // time constant of the peak and trough tracking, about 1 s
+    samples: u64,
+    envelope: Option<i32>,
+    peak: i32,
+    trough: i32,
+    carrier: Option<bool>,
+}
+
+impl PcmDemodulator {
+    /// Create a demodulator, None if the sample rate is below `MIN_SAMPLE_RATE`.
+    ///
+    /// # Arguments
+    /// * `sample_rate` - sample rate of the recording in Hz
+    pub fn new(sample_rate: u32) -> Option<Self> {
+        if sample_rate < MIN_SAMPLE_RATE {
+            return None;
+        }
+        Some(Self {
+            sample_rate,
+            envelope_shift: (sample_rate / 200).ilog2(),
+            tracking_shift: sample_rate.ilog2(),
+            samples: 0,
+            envelope: None,
+            peak: 0,
+            trough: 0,
+            carrier: None,
+        })
+    }
+
+    /// Get the sample rate in Hz.
+    pub fn get_sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    /// Get if the carrier is currently detected, None before the first sample.
+    pub fn get_carrier(&self) -> Option<bool> {
+        self.carrier
+    }
+
+    /// Process a single sample, the first one is taken at 0 and each next one a sample period
+    /// later.
+    ///
+    /// Returns the edge as an `(is_low_edge, t)` pair if the carrier was switched on or off, see
+    /// `MSFUtils::handle_new_edge()`.
+    ///
+    /// # Arguments
+    /// * `sample` - the sample
+    pub fn process_sample(&mut self, sample: i16) -> Option<(bool, u32)> {
+        let t = (self.samples * 1_000_000 / self.sample_rate as u64) as u32;
+        self.samples += 1;
+        let rectified = (sample.unsigned_abs() as i32) << ENVELOPE_FRACTION;
+        let envelope = match self.envelope {
+            Some(envelope) => envelope + ((rectified - envelope) >> self.envelope_shift),
+            None => {
+                self.peak = rectified;
+                self.trough = rectified;
+                rectified
+            }
+        };
+        self.envelope = Some(envelope);
+        let span = self.peak - self.trough;
+        if envelope > self.peak {
+            self.peak = envelope;
+        } else {
+            self.peak -= span >> self.tracking_shift;
+        }
+        if envelope < self.trough {
+            self.trough = envelope;
+        } else {
+            self.trough += span >> self.tracking_shift;
+        }
+        let middle = self.trough + (self.peak - self.trough) / 2;
+        let hysteresis = (self.peak - self.trough) / 8;
+        let carrier = match self.carrier {
+            Some(true) => envelope > middle - hysteresis,
+            _ => envelope > middle + hysteresis,
+        };
+        let edge = self
+            .carrier
+            .is_some_and(|old| old != carrier)
+            .then_some((carrier, t)); // a pulse starts when the carrier goes off
+        self.carrier = Some(carrier);
+        edge
+    }
+
+    /// Process a block of samples and feed the edges to the decoder.
+    ///
+    /// # Arguments
+    /// * `msf` - the decoder to feed
+    /// * `samples` - the samples, oldest first
+    /// * `on_edge` - called after each edge fed to the decoder, e.g. to check for a new second
+    pub fn process_samples<F: FnMut(&mut MSFUtils)>(
+        &mut self,
+        msf: &mut MSFUtils,
+        samples: &[i16],
+        mut on_edge: F,
+    ) {
+        for &sample in samples {
+            if let Some((is_low_edge, t)) = self.process_sample(sample) {
+                msf.handle_new_edge(is_low_edge, t);
+                on_edge(msf);
+            }
+        }
+    }
+}
+
+/// The first channel of a WAV recording, see `read_wav()`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WavSamples {
+    /// Sample rate in Hz.
+    pub sample_rate: u32,
+    /// The samples, 8-bit samples are scaled to 16 bits.
+    pub samples: Vec<i16>,
+}
+
+fn invalid(reason: &'static str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, reason)
+}
+
+/// Read the first channel of an uncompressed 8-bit or 16-bit WAV recording.
+///
+/// # Arguments
+/// * `reader` - the source of the WAV data
+pub fn read_wav<R: Read>(mut reader: R) -> io::Result<WavSamples> {
+    let mut data = Vec::new();
+    reader.read_to_end(&mut data)?;
+    if data.len() < 12 || &data[0..4] != b"RIFF" || &data[8..12] != b"WAVE" {
+        return Err(invalid("not a WAV file"));
+    }
+    let u16_at = |pos: usize| u16::from_le_bytes([data[pos], data[pos + 1]]);
+    let u32_at =
+        |pos: usize| u32::from_le_bytes([data[pos], data[pos + 1], data[pos + 2], data[pos + 3]]);
+    let mut format = None;
+    let mut pos = 12;
+    while pos + 8 <= data.len() {
+        let size = u32_at(pos + 4) as usize;
+        let body = pos + 8..(pos + 8 + size).min(data.len());
+        match &data[pos..pos + 4] {
+            b"fmt " if body.len() >= 16 => {
+                let start = body.start;
+                format = Some((
+                    u16_at(start),
+                    u16_at(start + 2),
+                    u32_at(start + 4),
+                    u16_at(start + 14),
+                ));
+            }
+            b"data" => {
+                let (tag, channels, sample_rate, bits) = format.ok_or(invalid("missing format"))?;
+                if tag != 1 || channels == 0 {
+                    return Err(invalid("not uncompressed PCM"));
+                }
+                if bits != 8 && bits != 16 {
+                    return Err(invalid("unsupported sample size"));
+                }
+                let frame_size = channels as usize * bits as usize / 8;
+                let frames = data[body].chunks_exact(frame_size);
+                let samples = if bits == 8 {
+                    frames.map(|f| (f[0] as i16 - 128) << 8).collect()
+                } else {
+                    frames.map(|f| i16::from_le_bytes([f[0], f[1]])).collect()
+                };
+                return Ok(WavSamples {
+                    sample_rate,
+                    samples,
+                });
+            }
+            _ => {}
+        }
+        pos += 8 + size + size % 2; // chunks are padded to an even size
+    }
+    Err(invalid("missing data"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::minute_edges;
+
+    /// One period of a 1 kHz tone at 8 kHz.
+    const TONE: [i16; 8] = [0, 7071, 10000, 7071, 0, -7071, -10000, -7071];
+
+    /// Samples of a 1 kHz tone at 8 kHz which is switched off during the pulses of the edges.
+    fn tone_samples(edges: &[(bool, u32)]) -> Vec<i16> {
+        let end = edges.last().unwrap().1 + 500_000;
+        let mut samples = Vec::new();
+        let mut idx = 0;
+        let mut carrier = true;
+        for n in 0..end / 125 {
+            while idx < edges.len() && edges[idx].1 <= n * 125 {
+                carrier = edges[idx].0;
+                idx += 1;
+            }
+            samples.push(if carrier { TONE[n as usize % 8] } else { 0 });
+        }
+        samples
+    }
+
+    #[test]
+    fn test_demodulate_tone() {
+        assert_eq!(PcmDemodulator::new(1_000), None);
+        let mut edges = minute_edges(1_000_000);
+        edges.pop();
+        edges.extend(minute_edges(61_000_000));
+        let samples = tone_samples(&edges);
+        let mut demodulator = PcmDemodulator::new(8_000).unwrap();
+        let mut msf = MSFUtils::default();
+        demodulator.process_samples(&mut msf, &samples, |msf| {
+            if msf.get_new_second() {
+                if msf.get_second() + 1 == msf.get_minute_length() {
+                    let _ = msf.decode_minute(false);
+                }
+                msf.increase_second();
+            }
+        });
+        // in the marker of the next minute
+        assert_eq!(demodulator.get_carrier(), Some(false));
+        let rdt = msf.get_radio_datetime();
+        assert_eq!(rdt.get_hour(), Some(14));
+        assert_eq!(rdt.get_minute(), Some(58));
+    }
+    #[test]
+    fn test_read_wav() {
+        let mut wav = Vec::new();
+        wav.extend(b"RIFF\x34\x00\x00\x00WAVE");
+        wav.extend(b"LIST\x03\x00\x00\x00abc\x00"); // odd-sized chunk with padding
+        wav.extend(b"fmt \x10\x00\x00\x00\x01\x00\x02\x00\x40\x1f\x00\x00");
+        wav.extend(b"\x00\x7d\x00\x00\x04\x00\x10\x00");
+        wav.extend(b"data\x08\x00\x00\x00\x10\x27\xff\xff\xf0\xd8\x01\x00");
+        let recording = read_wav(&wav[..]).unwrap();
+        assert_eq!(recording.sample_rate, 8_000);
+        assert_eq!(recording.samples, [10_000, -10_000]);
+        assert_eq!(read_wav(&b"RIFF\x00\x00\x00\x00AVI "[..]).is_err(), true);
+        wav.truncate(wav.len() - 20);
+        assert_eq!(read_wav(&wav[..]).is_err(), true);
+    }
+}
@@ -0,0 +1,25 @@
+//! Re-exports of the commonly used items.
+//!
+//! `use msf60_utils::prelude::*;` brings in everything needed to drive the decoder.
+
+pub use crate::api2::{
+    CurrentView, DecodeError, MinuteSecondFlags, Parities, PredictedFields, Snapshot,
+};
+pub use crate::civil_time::CivilTime;
+pub use crate::core::EdgeClass;
+pub use crate::decoder_config::DecoderConfig;
+pub use crate::edge_time::EdgeTime;
+pub use crate::frame::MSFFrame;
+#[cfg(feature = "marker-policy")]
+pub use crate::marker_policy::MarkerPolicy;
+pub use crate::marker_policy::MinuteMarker;
+pub use crate::merge_policy::MergePolicy;
+pub use crate::observer::MSFObserver;
+pub use crate::preset::Preset;
+pub use crate::signal_convention::SignalConvention;
+pub use crate::strictness::{Escalation, Strictness};
+pub use crate::{
+    ConfigError, Dut1Status, MSFUtils, MSFUtilsBuilder, MinuteLengthEstimate, MinuteOrigin,
+    MinuteProgress,
+};
+pub use radio_datetime_utils::RadioDateTimeUtils;
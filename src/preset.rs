@@ -0,0 +1,114 @@
+//! Named bundles of settings for typical applications, constructed via
+//! `DecoderConfig::preset()`.
+//!
+//! A preset is a regular `DecoderConfig`, so single settings can be overridden, e.g.
+//! `DecoderConfig { refractory_window: 0, ..DecoderConfig::preset(Preset::LowPower) }`, or by
+//! calling the builder method of the setting after `MSFUtilsBuilder::decoder_config()`.
+
+use crate::decoder_config::DecoderConfig;
+use crate::merge_policy::MergePolicy;
+use crate::strictness::Escalation;
+
+/// Typical applications of the decoder.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Preset {
+    /// Clocks showing the time: acquire quickly, accept every minute with OK parities.
+    WallClock,
+    /// Reference clocks for time servers: only accept minutes which are consistent over a longer
+    /// time, and keep the seconds aligned through short dropouts.
+    NtpRefclock,
+    /// Unattended recorders: keep decoding through poor reception by learning the pulse shape
+    /// of the receiver.
+    DataLogger,
+    /// Duty-cycled receivers: sync once using a few consistent minutes so the receiver can be
+    /// switched off, and filter contact bounce of receivers that are powered up and down.
+    LowPower,
+}
+
+impl DecoderConfig {
+    /// Return the settings bundled in a preset, with the default timing limits.
+    ///
+    /// # Arguments
+    /// * `preset` - the preset to return
+    pub const fn preset(preset: Preset) -> Self {
+        match preset {
+            Preset::WallClock => Self::DEFAULT,
+            Preset::NtpRefclock => Self {
+                merge_policy: MergePolicy::Confidence { threshold: 5 },
+                escalation: Escalation {
+                    escalate_after: 5,
+                    deescalate_after: 30,
+                },
+                flywheel: true,
+                pulse_bias_compensation: true,
+                ..Self::DEFAULT
+            },
+            Preset::DataLogger => Self {
+                merge_policy: MergePolicy::Confidence { threshold: 3 },
+                escalation: Escalation {
+                    escalate_after: 10,
+                    deescalate_after: 3,
+                },
+                flywheel: true,
+                pulse_bias_compensation: true,
+                adaptive_limits: true,
+                ..Self::DEFAULT
+            },
+            Preset::LowPower => Self {
+                merge_policy: MergePolicy::Confidence { threshold: 3 },
+                escalation: Escalation {
+                    escalate_after: 2,
+                    deescalate_after: 60,
+                },
+                refractory_window: 5_000,
+                sync_minutes: 2,
+                ..Self::DEFAULT
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MSFUtils;
+
+    #[test]
+    fn test_preset() {
+        for preset in [
+            Preset::WallClock,
+            Preset::NtpRefclock,
+            Preset::DataLogger,
+            Preset::LowPower,
+        ] {
+            assert_eq!(DecoderConfig::preset(preset).is_valid(), true);
+        }
+        let msf = MSFUtils::builder()
+            .decoder_config(DecoderConfig::preset(Preset::NtpRefclock))
+            .build()
+            .unwrap();
+        assert_eq!(
+            msf.get_merge_policy(),
+            MergePolicy::Confidence { threshold: 5 }
+        );
+        assert_eq!(msf.get_pulse_bias_compensation(), true);
+        #[cfg(feature = "flywheel")]
+        assert_eq!(msf.get_flywheel(), true);
+        assert_eq!(msf.get_suspect_0_1(), false);
+        let msf = MSFUtils::builder()
+            .decoder_config(DecoderConfig::preset(Preset::LowPower))
+            .refractory_window(0) // override
+            .build()
+            .unwrap();
+        #[cfg(feature = "single-shot")]
+        assert_eq!(msf.get_sync_minutes(), 2);
+        assert_eq!(msf.get_refractory_window(), 0);
+        assert_eq!(
+            msf.get_decoder_config(),
+            DecoderConfig {
+                refractory_window: 0,
+                ..DecoderConfig::preset(Preset::LowPower)
+            }
+        );
+    }
+}
@@ -0,0 +1,101 @@
+//! Replay of an edge log at the pace it was recorded, or a multiple thereof, for integration
+//! tests of applications which depend on realistic timing.
+//!
+//! Enabled with the `std` feature.
+
+use radio_datetime_utils::radio_datetime_helpers;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Iterator over recorded edges which returns each edge at the moment it is due.
+///
+/// The first edge is returned immediately, each next edge when the time between it and the
+/// first edge, divided by the speed, has elapsed. The edges are returned unchanged, so they can
+/// be passed to `MSFUtils::handle_new_edge()` as-is.
+pub struct RealtimeReplayer<'a> {
+    edges: &'a [(bool, u32)],
+    idx: usize,
+    speed: u32,
+    start: Option<Instant>,
+}
+
+impl<'a> RealtimeReplayer<'a> {
+    /// Create a replayer running at the recorded pace.
+    ///
+    /// # Arguments
+    /// * `edges` - the edges as `(is_low_edge, t)` pairs, see `MSFUtils::handle_new_edge()`
+    pub fn new(edges: &'a [(bool, u32)]) -> Self {
+        Self {
+            edges,
+            idx: 0,
+            speed: 1,
+            start: None,
+        }
+    }
+
+    /// Get the speed-up factor.
+    pub fn get_speed(&self) -> u32 {
+        self.speed
+    }
+
+    /// Set the speed-up factor, 0 is ignored.
+    ///
+    /// # Arguments
+    /// * `value` - run this many times faster than recorded
+    pub fn set_speed(&mut self, value: u32) {
+        if value > 0 {
+            self.speed = value;
+        }
+    }
+
+    /// Set the speed-up factor, 0 is ignored.
+    ///
+    /// # Arguments
+    /// * `value` - run this many times faster than recorded
+    pub fn speed(mut self, value: u32) -> Self {
+        self.set_speed(value);
+        self
+    }
+}
+
+impl Iterator for RealtimeReplayer<'_> {
+    type Item = (bool, u32);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let edge = *self.edges.get(self.idx)?;
+        self.idx += 1;
+        let start = *self.start.get_or_insert_with(Instant::now);
+        let offset = radio_datetime_helpers::time_diff(self.edges[0].1, edge.1);
+        let due = start + Duration::from_micros((offset / self.speed) as u64);
+        let now = Instant::now();
+        if due > now {
+            thread::sleep(due - now);
+        }
+        Some(edge)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::minute_edges;
+    use crate::MSFUtils;
+
+    #[test]
+    fn test_replay_pace() {
+        let edges = minute_edges(1_000_000);
+        let mut msf = MSFUtils::default();
+        let replayer = RealtimeReplayer::new(&edges).speed(600);
+        assert_eq!(replayer.get_speed(), 600);
+        let start = Instant::now();
+        for (is_low_edge, t) in replayer {
+            msf.handle_new_edge(is_low_edge, t);
+            if msf.get_new_second() {
+                msf.increase_second();
+            }
+        }
+        // 60 seconds at 600x speed
+        assert_eq!(start.elapsed() >= Duration::from_millis(100), true);
+        assert_eq!(msf.get_second(), 0);
+    }
+}
@@ -0,0 +1,124 @@
+//! Rolling reception quality over the last few minutes, as a single number for e.g. the signal
+//! bars of a clock display.
+//!
+//! Each minute is scored by the share of seconds with received bits, minus penalties for the
+//! spikes, runaways, and parity failures seen in it. The quality is the average score of the
+//! last `QUALITY_MINUTES` minutes.
+
+/// Number of minutes the quality is averaged over.
+pub const QUALITY_MINUTES: usize = 5;
+
+/// Penalty in points per ignored spike.
+const SPIKE_PENALTY: u16 = 2;
+/// Penalty in points per active or passive runaway.
+const RUNAWAY_PENALTY: u16 = 5;
+/// Penalty in points per failed parity check.
+const PARITY_PENALTY: u16 = 10;
+
+/// Score one minute, from 0 (nothing usable) to 100 (clean reception).
+///
+/// # Arguments
+/// * `valid_seconds` - number of seconds with received bits
+/// * `minute_length` - length of the minute in seconds
+/// * `spikes` - number of ignored spikes
+/// * `runaways` - number of active and passive runaways
+/// * `parity_failures` - number of failed parity checks
+pub fn score_minute(
+    valid_seconds: u8,
+    minute_length: u8,
+    spikes: u16,
+    runaways: u16,
+    parity_failures: u8,
+) -> u8 {
+    if minute_length == 0 {
+        return 0;
+    }
+    let base = 100 * valid_seconds.min(minute_length) as u16 / minute_length as u16;
+    let penalty = spikes
+        .saturating_mul(SPIKE_PENALTY)
+        .saturating_add(runaways.saturating_mul(RUNAWAY_PENALTY))
+        .saturating_add(parity_failures as u16 * PARITY_PENALTY);
+    base.saturating_sub(penalty) as u8
+}
+
+/// Scores of the last minutes and the tallies of the current one.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ReceptionQuality {
+    spikes: u16,
+    runaways: u16,
+    scores: [u8; QUALITY_MINUTES],
+    scored: usize, // number of valid entries in `scores`, up to QUALITY_MINUTES
+    next: usize,
+}
+
+impl ReceptionQuality {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Count an ignored spike in the current minute.
+    pub fn add_spike(&mut self) {
+        self.spikes = self.spikes.saturating_add(1);
+    }
+
+    /// Count an active or passive runaway in the current minute.
+    pub fn add_runaway(&mut self) {
+        self.runaways = self.runaways.saturating_add(1);
+    }
+
+    /// Score the current minute and start the next one.
+    ///
+    /// # Arguments
+    /// * `valid_seconds` - number of seconds with received bits
+    /// * `minute_length` - length of the minute in seconds
+    /// * `parity_failures` - number of failed parity checks
+    pub fn end_minute(&mut self, valid_seconds: u8, minute_length: u8, parity_failures: u8) {
+        self.scores[self.next] = score_minute(
+            valid_seconds,
+            minute_length,
+            self.spikes,
+            self.runaways,
+            parity_failures,
+        );
+        self.next = (self.next + 1) % QUALITY_MINUTES;
+        self.scored = (self.scored + 1).min(QUALITY_MINUTES);
+        self.spikes = 0;
+        self.runaways = 0;
+    }
+
+    /// Get the average score of the last minutes from 0 to 100, 0 if no minute ended yet.
+    pub fn get_quality(&self) -> u8 {
+        if self.scored == 0 {
+            return 0;
+        }
+        let sum: u16 = self.scores[..self.scored].iter().map(|&s| s as u16).sum();
+        (sum / self.scored as u16) as u8
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_score_minute() {
+        assert_eq!(score_minute(60, 60, 0, 0, 0), 100);
+        assert_eq!(score_minute(45, 60, 0, 0, 0), 75);
+        assert_eq!(score_minute(45, 60, 5, 1, 2), 40);
+        assert_eq!(score_minute(10, 60, 100, 0, 0), 0);
+    }
+    #[test]
+    fn test_reception_quality() {
+        let mut quality = ReceptionQuality::new();
+        assert_eq!(quality.get_quality(), 0);
+        quality.end_minute(60, 60, 0);
+        quality.add_spike();
+        quality.add_runaway();
+        quality.end_minute(59, 60, 1); // 98 - 2 - 5 - 10
+        assert_eq!(quality.get_quality(), 90);
+        for _ in 0..QUALITY_MINUTES {
+            quality.end_minute(30, 60, 0);
+        }
+        assert_eq!(quality.get_quality(), 50); // older minutes are forgotten
+    }
+}
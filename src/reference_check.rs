@@ -0,0 +1,208 @@
+//! Cross-validation of the decoded date/time against a reference clock, e.g. NTP.
+
+use crate::MSFUtils;
+use radio_datetime_utils::{radio_datetime_helpers, DST_SUMMER};
+
+/// Maximum offset in microseconds at which both sources are considered to agree.
+pub const AGREE_LIMIT: i64 = 200_000;
+
+/// Conclusion of comparing the decoded date/time against a reference clock.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    /// Both sources agree within `AGREE_LIMIT`.
+    Agree,
+    /// The sources disagree and the decoder is not sure of its date/time, because the last
+    /// minute was not decoded or a field of it jumped.
+    StepSuspected,
+    /// The sources disagree while the decoder just decoded a consistent minute.
+    ReferenceSuspect,
+}
+
+/// Outcome of `MSFUtils::compare_against()`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ReferenceComparison {
+    /// Decoded time minus the reference time, in microseconds.
+    pub offset: i64,
+    pub verdict: Verdict,
+}
+
+/// Number of days since 1970-01-01 of the given date in the proleptic Gregorian calendar.
+///
+/// # Arguments
+/// * `year` - full year
+/// * `month` - month, 1-12
+/// * `day` - day of the month
+pub(crate) fn days_from_civil(year: i64, month: u8, day: u8) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year - era * 400;
+    let month = month as i64;
+    let doy = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// Return the full year of a date of which only the year within the century was broadcast.
+///
+/// The century is the one in [1970, 2370) in which the date falls on the given weekday, these
+/// four centuries all differ in weekday. Without a weekday, or if no century matches it, the
+/// year lies in [1970, 2070). Returns None if the year is out of range.
+///
+/// # Arguments
+/// * `year` - year within the century, 0-99
+/// * `month` - month, 1-12
+/// * `day` - day of the month
+/// * `weekday` - day of the week, 0 is Sunday
+pub fn full_year(year: u8, month: u8, day: u8, weekday: Option<u8>) -> Option<i64> {
+    if year > 99 {
+        return None;
+    }
+    let first = if year >= 70 { 1900 } else { 2000 } + year as i64;
+    let Some(weekday) = weekday else {
+        return Some(first);
+    };
+    (0..4)
+        .map(|c| first + 100 * c)
+        .find(|&full| (days_from_civil(full, month, day) + 4).rem_euclid(7) == weekday as i64)
+        .or(Some(first))
+}
+
+impl MSFUtils {
+    /// Return the decoded date/time as a Unix time in microseconds at the given time stamp.
+    ///
+    /// Returns None if the date/time is not valid or no second has started yet.
+    ///
+    /// # Arguments
+    /// * `at_local_t` - time stamp in the time base of `handle_new_edge()`, within a few
+    ///   seconds of the current second
+    pub fn unix_time_at(&self, at_local_t: u32) -> Option<i64> {
+        let rdt = &self.radio_datetime;
+        if !rdt.is_valid() {
+            return None;
+        }
+        let (month, day) = (rdt.get_month()?, rdt.get_day()?);
+        let year = full_year(rdt.get_year()?, month, day, rdt.get_weekday())?;
+        let days = days_from_civil(year, month, day);
+        // MSF broadcasts UK civil time, which is UTC+1 during summer time
+        let utc_offset = if rdt.get_dst()? & DST_SUMMER != 0 {
+            3600
+        } else {
+            0
+        };
+        let seconds = days * 86_400 + rdt.get_hour()? as i64 * 3600 + rdt.get_minute()? as i64 * 60
+            - utc_offset
+            + self.second as i64;
+        Some(seconds * 1_000_000 + self.elapsed_in_second(at_local_t)?)
+    }
+
+    /// Return the time in microseconds from the start of the current second to the given time
+    /// stamp, negative if it lies before, None if no second has started yet.
+    ///
+    /// # Arguments
+    /// * `at_local_t` - time stamp in the time base of `handle_new_edge()`
+    pub(crate) fn elapsed_in_second(&self, at_local_t: u32) -> Option<i64> {
+        let second_start = self.second_start?;
+        let elapsed = radio_datetime_helpers::time_diff(second_start, at_local_t);
+        Some(if elapsed < 0x8000_0000 {
+            elapsed as i64
+        } else {
+            -(radio_datetime_helpers::time_diff(at_local_t, second_start) as i64)
+        })
+    }
+
+    /// Compare the decoded date/time against a reference clock.
+    ///
+    /// Returns None if the decoded date/time is not available, see `unix_time_at()`.
+    ///
+    /// # Arguments
+    /// * `reference_unix_time` - time of the reference clock in microseconds since the Unix
+    ///   epoch, taken at `at_local_t`
+    /// * `at_local_t` - time stamp in the time base of `handle_new_edge()`
+    pub fn compare_against(
+        &self,
+        reference_unix_time: i64,
+        at_local_t: u32,
+    ) -> Option<ReferenceComparison> {
+        let offset = self.unix_time_at(at_local_t)? - reference_unix_time;
+        let rdt = &self.radio_datetime;
+        let jumped = rdt.get_jump_year()
+            || rdt.get_jump_month()
+            || rdt.get_jump_day()
+            || rdt.get_jump_weekday()
+            || rdt.get_jump_hour()
+            || rdt.get_jump_minute();
+        let verdict = if offset.abs() <= AGREE_LIMIT {
+            Verdict::Agree
+        } else if jumped || self.minutes_since_decode != Some(0) {
+            Verdict::StepSuspected
+        } else {
+            Verdict::ReferenceSuspect
+        };
+        Some(ReferenceComparison { offset, verdict })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::filled_msf;
+
+    // 2022-10-23 14:58 BST
+    const UNIX_TIME: i64 = 1_666_533_480_000_000;
+
+    #[test]
+    fn test_days_from_civil() {
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(days_from_civil(2000, 3, 1), 11_017);
+        assert_eq!(days_from_civil(2022, 10, 23), 19_288);
+    }
+    #[test]
+    fn test_full_year() {
+        assert_eq!(full_year(22, 10, 23, None), Some(2022));
+        assert_eq!(full_year(99, 12, 31, None), Some(1999));
+        assert_eq!(full_year(22, 10, 23, Some(0)), Some(2022));
+        assert_eq!(full_year(99, 12, 31, Some(5)), Some(1999));
+        assert_eq!(full_year(22, 10, 23, Some(5)), Some(2122));
+        assert_eq!(full_year(22, 10, 23, Some(6)), Some(2022)); // no match
+        assert_eq!(full_year(100, 1, 1, None), None);
+    }
+    #[test]
+    fn test_compare_against() {
+        let mut msf = filled_msf();
+        assert_eq!(msf.compare_against(UNIX_TIME, 0), None);
+        let _ = msf.decode_minute(false);
+        msf.second = 0;
+        msf.second_start = Some(5_000_000);
+        assert_eq!(msf.unix_time_at(5_250_000), Some(UNIX_TIME + 250_000));
+        assert_eq!(msf.unix_time_at(4_750_000), Some(UNIX_TIME - 250_000));
+        let weekday = msf.radio_datetime.get_weekday();
+        msf.radio_datetime.set_weekday(Some(5), true, false); // 2122-10-23
+        assert_eq!(
+            msf.unix_time_at(5_000_000),
+            Some(
+                UNIX_TIME
+                    + (days_from_civil(2122, 10, 23) - days_from_civil(2022, 10, 23))
+                        * 86_400_000_000
+            )
+        );
+        msf.radio_datetime.set_weekday(weekday, true, false);
+        assert_eq!(
+            msf.compare_against(UNIX_TIME + 100_000, 5_000_000),
+            Some(ReferenceComparison {
+                offset: -100_000,
+                verdict: Verdict::Agree
+            })
+        );
+        assert_eq!(
+            msf.compare_against(UNIX_TIME - 60_000_000, 5_000_000)
+                .map(|c| c.verdict),
+            Some(Verdict::ReferenceSuspect)
+        );
+        msf.minutes_since_decode = Some(3); // holdover
+        assert_eq!(
+            msf.compare_against(UNIX_TIME - 60_000_000, 5_000_000)
+                .map(|c| c.verdict),
+            Some(Verdict::StepSuspected)
+        );
+    }
+}
@@ -0,0 +1,86 @@
+//! Polarity of the demodulated signal fed to `MSFUtils::handle_new_edge()`.
+
+use radio_datetime_utils::radio_datetime_helpers;
+
+/// Minimum number of edges needed by `SignalConvention::detect()`, about three seconds.
+pub const DETECT_MIN_EDGES: usize = 6;
+
+/// Level of the receiver output during the carrier-off pulses which carry the bits.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SignalConvention {
+    /// The output is high while the carrier is off, so a high-to-low edge ends a pulse.
+    #[default]
+    ActiveHigh,
+    /// The output is low while the carrier is off, like open-drain outputs which idle high.
+    ActiveLow,
+}
+
+impl SignalConvention {
+    /// Determine the convention from a few seconds of edges, by comparing the time spent at
+    /// either level: the carrier is off for at most 500 ms per second.
+    ///
+    /// Returns None if there are fewer than `DETECT_MIN_EDGES` edges, the edges do not alternate,
+    /// or both levels last equally long.
+    ///
+    /// # Arguments
+    /// * `edges` - the edges as `(is_low_edge, t)` pairs, see `MSFUtils::handle_new_edge()`
+    pub fn detect(edges: &[(bool, u32)]) -> Option<Self> {
+        if edges.len() < DETECT_MIN_EDGES {
+            return None;
+        }
+        let mut high: u64 = 0;
+        let mut low: u64 = 0;
+        for pair in edges.windows(2) {
+            let ((was_low_edge, t0), (is_low_edge, t1)) = (pair[0], pair[1]);
+            if was_low_edge == is_low_edge {
+                return None;
+            }
+            let t_diff = radio_datetime_helpers::time_diff(t0, t1) as u64;
+            if is_low_edge {
+                high += t_diff;
+            } else {
+                low += t_diff;
+            }
+        }
+        match high.cmp(&low) {
+            core::cmp::Ordering::Less => Some(Self::ActiveHigh),
+            core::cmp::Ordering::Greater => Some(Self::ActiveLow),
+            core::cmp::Ordering::Equal => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::minute_edges;
+
+    #[test]
+    fn test_detect() {
+        let edges = minute_edges(1_000_000);
+        assert_eq!(
+            SignalConvention::detect(&edges[..DETECT_MIN_EDGES]),
+            Some(SignalConvention::ActiveHigh)
+        );
+        let inverted: Vec<(bool, u32)> = edges.iter().map(|&(e, t)| (!e, t)).collect();
+        assert_eq!(
+            SignalConvention::detect(&inverted),
+            Some(SignalConvention::ActiveLow)
+        );
+        assert_eq!(
+            SignalConvention::detect(&edges[..DETECT_MIN_EDGES - 1]),
+            None
+        );
+        assert_eq!(
+            SignalConvention::detect(&[
+                (true, 0),
+                (false, 1),
+                (false, 2),
+                (true, 3),
+                (false, 4),
+                (true, 5)
+            ]),
+            None
+        );
+    }
+}
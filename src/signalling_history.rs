@@ -0,0 +1,169 @@
+//! Rolling log of the raw signalling bits on the B channel (DUT1 and 53-58), for studying the
+//! behaviour of the broadcast like DUT1 steps and the timing of announcements.
+
+use crate::MSFUtils;
+
+/// Raw B signalling bits of one minute.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SignallingRecord {
+    /// Hour of the decoded date/time, if known.
+    pub hour: Option<u8>,
+    /// Minute of the decoded date/time, if known.
+    pub minute: Option<u8>,
+    /// B bits 1-16 (DUT1), bit 0 holds second 1.
+    pub dut1_bits: u16,
+    /// Mask of the received DUT1 bits.
+    pub dut1_known: u16,
+    /// B bits 53-58 (summer time warning, parities, summer time), bit 0 holds second 53.
+    pub flag_bits: u8,
+    /// Mask of the received flag bits.
+    pub flags_known: u8,
+}
+
+impl SignallingRecord {
+    /// Get the B bit of the given DUT1 second, None if unknown or out of range.
+    ///
+    /// # Arguments
+    /// * `second` - second of the DUT1 bit, 1-16
+    pub fn get_dut1_bit(&self, second: u8) -> Option<bool> {
+        if !(1..=16).contains(&second) || self.dut1_known & (1 << (second - 1)) == 0 {
+            return None;
+        }
+        Some(self.dut1_bits & (1 << (second - 1)) != 0)
+    }
+
+    /// Get the B bit of the given flag second, None if unknown or out of range.
+    ///
+    /// # Arguments
+    /// * `second` - second of the flag bit, 53-58
+    pub fn get_flag_bit(&self, second: u8) -> Option<bool> {
+        if !(53..=58).contains(&second) || self.flags_known & (1 << (second - 53)) == 0 {
+            return None;
+        }
+        Some(self.flag_bits & (1 << (second - 53)) != 0)
+    }
+}
+
+/// Ring buffer holding the signalling bits of the last `N` minutes, e.g. 180 for three hours.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SignallingHistory<const N: usize> {
+    records: [SignallingRecord; N],
+    next: usize,
+    len: usize,
+}
+
+impl<const N: usize> Default for SignallingHistory<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> SignallingHistory<N> {
+    pub fn new() -> Self {
+        Self {
+            records: [SignallingRecord::default(); N],
+            next: 0,
+            len: 0,
+        }
+    }
+
+    /// Record the signalling bits of the minute which was just decoded, replacing the oldest
+    /// record if the history is full.
+    ///
+    /// This method must be called after `decode_minute()` and _before_ `increase_second()`.
+    ///
+    /// # Arguments
+    /// * `msf` - the decoder
+    pub fn record(&mut self, msf: &MSFUtils) {
+        if N == 0 {
+            return;
+        }
+        // a negative leap second drops second 16, a positive one is inserted at 17
+        let minute_length = msf.get_minute_length();
+        let dut1_stop = if minute_length == 59 { 15 } else { 16 };
+        let flag_start = 53 + minute_length as usize - 60;
+        let mut record = SignallingRecord {
+            hour: msf.radio_datetime.get_hour(),
+            minute: msf.radio_datetime.get_minute(),
+            ..SignallingRecord::default()
+        };
+        for second in 1..=dut1_stop {
+            if let Some(bit) = msf.bit_buffer_b[second] {
+                record.dut1_known |= 1 << (second - 1);
+                record.dut1_bits |= (bit as u16) << (second - 1);
+            }
+        }
+        for idx in 0..6 {
+            if let Some(bit) = msf.bit_buffer_b[flag_start + idx] {
+                record.flags_known |= 1 << idx;
+                record.flag_bits |= (bit as u8) << idx;
+            }
+        }
+        self.records[self.next] = record;
+        self.next = (self.next + 1) % N;
+        self.len = (self.len + 1).min(N);
+    }
+
+    /// Get the number of recorded minutes.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Return if no minutes are recorded.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Iterate over the recorded minutes, oldest first.
+    pub fn iter(&self) -> impl Iterator<Item = &SignallingRecord> {
+        let start = (self.next + N - self.len) % N.max(1);
+        (0..self.len).map(move |idx| &self.records[(start + idx) % N])
+    }
+
+    /// Remove all records.
+    pub fn clear(&mut self) {
+        self.next = 0;
+        self.len = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::filled_msf;
+
+    #[test]
+    fn test_record() {
+        let mut msf = filled_msf();
+        let _ = msf.decode_minute(false);
+        let mut history = SignallingHistory::<2>::new();
+        assert_eq!(history.is_empty(), true);
+        history.record(&msf);
+        let record = history.iter().next().unwrap();
+        assert_eq!(record.hour, Some(14));
+        assert_eq!(record.minute, Some(58));
+        assert_eq!(record.get_dut1_bit(8), Some(false));
+        assert_eq!(record.get_dut1_bit(9), Some(true));
+        assert_eq!(record.get_dut1_bit(17), None);
+        assert_eq!(record.get_flag_bit(53), Some(false));
+        assert_eq!(record.get_flag_bit(57), Some(false));
+        assert_eq!(record.get_flag_bit(58), Some(true));
+    }
+    #[test]
+    fn test_rolling() {
+        let mut msf = filled_msf();
+        let mut history = SignallingHistory::<2>::new();
+        history.record(&msf); // not decoded yet
+        msf.bit_buffer_b[9] = None;
+        let _ = msf.decode_minute(false);
+        history.record(&msf);
+        history.record(&msf);
+        assert_eq!(history.len(), 2);
+        for record in history.iter() {
+            assert_eq!(record.hour, Some(14));
+            assert_eq!(record.get_dut1_bit(9), None);
+        }
+        history.clear();
+        assert_eq!(history.iter().count(), 0);
+    }
+}
@@ -0,0 +1,409 @@
+//! Synthetic MSF signal with injected noise, to run robustness tests on the host as well as on
+//! the target.
+//!
+//! A minute is given either as plain bits or as an `MSFFrame`, whose missing bits become seconds
+//! without a pulse. The length of a second and the pulse widths can be changed to mimic the
+//! timing of a particular receiver.
+//!
+//! The noise comes from a `NoiseSource`, `XorShift32` is a small built-in one. No allocation is
+//! needed, so this works with no_std.
+//!
+//! Enabled with the `fault-injection` feature.
+
+use crate::frame::MSFFrame;
+
+/// Source of random numbers for the noise.
+pub trait NoiseSource {
+    /// Return the next random number.
+    fn next_u32(&mut self) -> u32;
+}
+
+/// 32-bit xorshift generator, good enough for noise and deterministic for a given seed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct XorShift32 {
+    state: u32,
+}
+
+impl XorShift32 {
+    /// Create a generator, a seed of 0 is replaced by 1.
+    ///
+    /// # Arguments
+    /// * `seed` - the initial state
+    pub fn new(seed: u32) -> Self {
+        Self {
+            state: if seed == 0 { 1 } else { seed },
+        }
+    }
+}
+
+impl Default for XorShift32 {
+    fn default() -> Self {
+        Self::new(0x2545_f491)
+    }
+}
+
+impl NoiseSource for XorShift32 {
+    fn next_u32(&mut self) -> u32 {
+        let mut x = self.state;
+        x ^= x << 13;
+        x ^= x >> 17;
+        x ^= x << 5;
+        self.state = x;
+        x
+    }
+}
+
+/// Kinds and amounts of noise added to the signal.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct NoiseConfig {
+    /// Maximum deviation of each edge in microseconds, in either direction, at most
+    /// `JITTER_MAX`.
+    pub jitter: u32,
+    /// Chance in percent that a second contains a spike in its passive part.
+    pub spike_percent: u8,
+    /// Length of a spike in microseconds.
+    pub spike_length: u32,
+}
+
+/// Timing of the simulated signal in microseconds, the pulse widths are counted from the start
+/// of the second.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PulseTiming {
+    /// Length of a second.
+    pub second: u32,
+    /// Width of the begin-of-minute marker.
+    pub marker: u32,
+    /// Width of a (0,0) pulse, also the end of the first pulse of a (0,1) pair.
+    pub pulse_0: u32,
+    /// Width of a (1,0) pulse, also the start of the second pulse of a (0,1) pair.
+    pub pulse_a: u32,
+    /// Width of a (1,1) pulse, also the end of the second pulse of a (0,1) pair.
+    pub pulse_ab: u32,
+}
+
+impl PulseTiming {
+    /// The nominal timing of the signal.
+    pub const NOMINAL: Self = Self {
+        second: 1_000_000,
+        marker: 500_000,
+        pulse_0: 100_000,
+        pulse_a: 200_000,
+        pulse_ab: 300_000,
+    };
+
+    /// Return if the pulse widths increase in the order of the fields and fit in a second.
+    pub fn is_valid(&self) -> bool {
+        0 < self.pulse_0
+            && self.pulse_0 < self.pulse_a
+            && self.pulse_a < self.pulse_ab
+            && self.pulse_ab < self.second
+            && 0 < self.marker
+            && self.marker < self.second
+    }
+}
+
+impl Default for PulseTiming {
+    fn default() -> Self {
+        Self::NOMINAL
+    }
+}
+
+/// The bits to simulate.
+enum Bits<'a> {
+    Slices(&'a [bool], &'a [bool]),
+    Frame(&'a MSFFrame),
+}
+
+impl Bits<'_> {
+    fn len(&self) -> usize {
+        match self {
+            Bits::Slices(bits_a, _) => bits_a.len(),
+            Bits::Frame(frame) => frame.minute_length as usize,
+        }
+    }
+
+    fn get(&self, second: usize) -> (Option<bool>, Option<bool>) {
+        match self {
+            Bits::Slices(bits_a, bits_b) => (Some(bits_a[second]), Some(bits_b[second])),
+            Bits::Frame(frame) => (frame.bits_a[second], frame.bits_b[second]),
+        }
+    }
+}
+
+/// Maximum jitter in microseconds, larger values are clamped to it.
+pub const JITTER_MAX: u32 = u32::MAX / 2;
+
+/// Maximum number of edges in one second: two pulses of a (0,1) bit and a spike.
+const MAX_EDGES_PER_SECOND: usize = 6;
+
+/// Iterator over the `(is_low_edge, t)` edges of a minute with noise, see
+/// `MSFUtils::handle_new_edge()`.
+///
+/// A minute is decoded once the marker of the next one arrives, so consecutive minutes are
+/// simulated by starting the next simulator where this one ends.
+pub struct MinuteSimulator<'a, R: NoiseSource = XorShift32> {
+    bits: Bits<'a>,
+    t: u32,
+    timing: PulseTiming,
+    noise: NoiseConfig,
+    rng: R,
+    second: usize,
+    pending: [(bool, u32); MAX_EDGES_PER_SECOND],
+    pending_len: usize,
+    pending_idx: usize,
+}
+
+impl<'a, R: NoiseSource> MinuteSimulator<'a, R> {
+    /// Create a simulator for a minute of 59, 60, or 61 seconds.
+    ///
+    /// Only the seconds present in both `bits_a` and `bits_b` are simulated, and the jitter is
+    /// clamped to `JITTER_MAX`.
+    ///
+    /// # Arguments
+    /// * `bits_a` - the A bits of the minute, bit 0 is the begin-of-minute marker
+    /// * `bits_b` - the B bits of the minute, as long as `bits_a`
+    /// * `t` - time stamp of the start of the minute in microseconds
+    /// * `noise` - the noise to add
+    /// * `rng` - the source of the noise
+    pub fn new(bits_a: &'a [bool], bits_b: &'a [bool], t: u32, noise: NoiseConfig, rng: R) -> Self {
+        let length = bits_a.len().min(bits_b.len());
+        Self {
+            bits: Bits::Slices(&bits_a[..length], &bits_b[..length]),
+            t,
+            timing: PulseTiming::NOMINAL,
+            noise: NoiseConfig {
+                jitter: noise.jitter.min(JITTER_MAX),
+                ..noise
+            },
+            rng,
+            second: 0,
+            pending: [(false, 0); MAX_EDGES_PER_SECOND],
+            pending_len: 0,
+            pending_idx: 0,
+        }
+    }
+
+    /// Create a simulator for a frame, a second of which has no pulse if one of its bits is None.
+    ///
+    /// The nominal timing is used if `timing` is invalid.
+    ///
+    /// # Arguments
+    /// * `frame` - the bits of the minute, bit 0 is the begin-of-minute marker
+    /// * `t` - time stamp of the start of the minute in microseconds
+    /// * `timing` - the length of a second and the pulse widths
+    /// * `noise` - the noise to add
+    /// * `rng` - the source of the noise
+    pub fn from_frame(
+        frame: &'a MSFFrame,
+        t: u32,
+        timing: PulseTiming,
+        noise: NoiseConfig,
+        rng: R,
+    ) -> Self {
+        Self {
+            bits: Bits::Frame(frame),
+            timing: if timing.is_valid() {
+                timing
+            } else {
+                PulseTiming::NOMINAL
+            },
+            ..Self::new(&[], &[], t, noise, rng)
+        }
+    }
+
+    /// Return the noise source, e.g. to continue with it in the next minute.
+    pub fn into_rng(self) -> R {
+        self.rng
+    }
+
+    fn jitter(&mut self, t: u32) -> u32 {
+        if self.noise.jitter == 0 {
+            return t;
+        }
+        let span = 2 * self.noise.jitter + 1;
+        t.wrapping_add(self.rng.next_u32() % span)
+            .wrapping_sub(self.noise.jitter)
+    }
+
+    fn push(&mut self, is_low_edge: bool, t: u32) {
+        let t = self.jitter(t);
+        self.pending[self.pending_len] = (is_low_edge, t);
+        self.pending_len += 1;
+    }
+
+    /// Generate the edges of the current second.
+    fn fill(&mut self) {
+        self.pending_len = 0;
+        self.pending_idx = 0;
+        let timing = self.timing;
+        let start = self.t.wrapping_add(self.second as u32 * timing.second);
+        let active = match (self.second == 0, self.bits.get(self.second)) {
+            (_, (None, _) | (_, None)) => None,
+            (true, _) => Some(timing.marker),
+            (false, (Some(false), Some(true))) => {
+                self.push(false, start);
+                self.push(true, start.wrapping_add(timing.pulse_0));
+                self.push(false, start.wrapping_add(timing.pulse_a));
+                Some(timing.pulse_ab)
+            }
+            (false, (Some(false), Some(false))) => Some(timing.pulse_0),
+            (false, (Some(true), Some(false))) => Some(timing.pulse_a),
+            (false, (Some(true), Some(true))) => Some(timing.pulse_ab),
+        };
+        if let Some(active) = active {
+            if self.pending_len == 0 {
+                self.push(false, start);
+            }
+            self.push(true, start.wrapping_add(active));
+        }
+        if self.rng.next_u32() % 100 < self.noise.spike_percent as u32 {
+            let spike = start.wrapping_add(timing.second / 10 * 7);
+            self.push(false, spike);
+            self.push(true, spike.wrapping_add(self.noise.spike_length));
+        }
+    }
+}
+
+impl<R: NoiseSource> Iterator for MinuteSimulator<'_, R> {
+    type Item = (bool, u32);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.pending_idx == self.pending_len {
+            if self.second == self.bits.len() {
+                return None;
+            }
+            self.fill();
+            self.second += 1;
+        }
+        let edge = self.pending[self.pending_idx];
+        self.pending_idx += 1;
+        Some(edge)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{BIT_BUFFER_A, BIT_BUFFER_B};
+    use crate::MSFUtils;
+
+    #[test]
+    fn test_xorshift32() {
+        let mut rng = XorShift32::new(0);
+        assert_eq!(rng.next_u32(), 270_369);
+        assert_eq!(XorShift32::new(0), XorShift32::new(1));
+    }
+    #[test]
+    fn test_minute_simulator_spikes() {
+        let noise = NoiseConfig {
+            spike_percent: 100,
+            spike_length: 5_000,
+            ..NoiseConfig::default()
+        };
+        let sim = MinuteSimulator::new(&BIT_BUFFER_A, &BIT_BUFFER_B, 0, noise, XorShift32::new(7));
+        // two edges per second plus one (0,1) pair per such bit, and a spike per second
+        let pairs_0_1 = (0..60)
+            .filter(|&s| !BIT_BUFFER_A[s] && BIT_BUFFER_B[s])
+            .count();
+        assert_eq!(sim.count(), 60 * 4 + pairs_0_1 * 2);
+    }
+    #[test]
+    fn test_minute_simulator_invalid_input() {
+        let noise = NoiseConfig {
+            jitter: u32::MAX,
+            ..NoiseConfig::default()
+        };
+        let sim = MinuteSimulator::new(
+            &BIT_BUFFER_A,
+            &BIT_BUFFER_B[..30],
+            0,
+            noise,
+            XorShift32::new(7),
+        );
+        assert_eq!(sim.noise.jitter, JITTER_MAX);
+        assert_eq!(sim.bits.len(), 30);
+        let pairs_0_1 = (0..30)
+            .filter(|&s| !BIT_BUFFER_A[s] && BIT_BUFFER_B[s])
+            .count();
+        assert_eq!(sim.count(), 30 * 2 + pairs_0_1 * 2);
+    }
+    #[test]
+    fn test_minute_simulator_jitter() {
+        let noise = NoiseConfig {
+            jitter: 20_000,
+            ..NoiseConfig::default()
+        };
+        let mut msf = MSFUtils::default();
+        let mut rng = XorShift32::default();
+        for minute in 0..2 {
+            let mut sim = MinuteSimulator::new(
+                &BIT_BUFFER_A,
+                &BIT_BUFFER_B,
+                1_000_000 + minute * 60_000_000,
+                noise,
+                rng,
+            );
+            for (is_low_edge, t) in sim.by_ref() {
+                msf.handle_new_edge(is_low_edge, t);
+                if msf.get_new_second() {
+                    if msf.get_second() + 1 == msf.get_minute_length() {
+                        let _ = msf.decode_minute(false);
+                    }
+                    msf.increase_second();
+                }
+            }
+            rng = sim.into_rng();
+        }
+        let rdt = msf.get_radio_datetime();
+        assert_eq!(rdt.get_hour(), Some(14));
+        assert_eq!(rdt.get_minute(), Some(58));
+    }
+    #[test]
+    fn test_minute_simulator_from_frame() {
+        let mut frame = MSFFrame::from_bits(&BIT_BUFFER_A, &BIT_BUFFER_B).unwrap();
+        // a receiver which stretches its pulses by 40 ms, on a slightly fast clock
+        let timing = PulseTiming {
+            second: 999_000,
+            marker: 540_000,
+            pulse_0: 140_000,
+            pulse_a: 240_000,
+            pulse_ab: 340_000,
+        };
+        let mut msf = MSFUtils::default();
+        let mut edges: Vec<_> = MinuteSimulator::from_frame(
+            &frame,
+            0,
+            timing,
+            NoiseConfig::default(),
+            XorShift32::default(),
+        )
+        .collect();
+        edges.extend(MinuteSimulator::from_frame(
+            &frame,
+            60 * 999_000,
+            timing,
+            NoiseConfig::default(),
+            XorShift32::default(),
+        ));
+        edges.push((false, 120 * 999_000)); // the marker of the next minute
+        let minutes: Vec<_> = msf.decode_edges(edges).collect();
+        assert_eq!(minutes.len(), 2);
+        let rdt = minutes[1].ok().unwrap().radio_datetime;
+        assert_eq!(rdt.get_hour(), Some(14));
+        assert_eq!(rdt.get_minute(), Some(58));
+        // a missing bit is a second without a pulse
+        frame.bits_b[20] = None;
+        let sim = MinuteSimulator::from_frame(
+            &frame,
+            0,
+            PulseTiming::NOMINAL,
+            NoiseConfig::default(),
+            XorShift32::default(),
+        );
+        assert_eq!(
+            sim.filter(|&(_, t)| (20_000_000..21_000_000).contains(&t))
+                .count(),
+            0
+        );
+    }
+}
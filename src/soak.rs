@@ -0,0 +1,273 @@
+//! Long-run test of the decoder on a synthetic signal, to be used as a pre-release gate.
+//!
+//! The signal covers any stretch of days, including DST changes, leap seconds, and outages,
+//! and is generated using the simulator. The decoder is run as an application would, and global
+//! invariants are checked along the way. Panics of the decoder, including failed invariant
+//! checks of its internal state, are not caught so they fail the calling test.
+//!
+//! Enabled with the `soak` feature.
+
+use crate::frame::{EncodeOptions, MSFFrame};
+use crate::reference_check::{days_from_civil, full_year};
+use crate::simulator::{MinuteSimulator, NoiseConfig, XorShift32};
+use crate::MSFUtils;
+use radio_datetime_utils::{RadioDateTimeUtils, DST_SUMMER};
+
+/// Configuration of a soak run.
+#[derive(Clone, Debug)]
+pub struct SoakConfig {
+    /// Unix time in seconds of the first simulated minute, a multiple of 60.
+    pub start: i64,
+    /// Number of minutes to simulate.
+    pub minutes: u32,
+    /// Unix times in seconds of the minutes which contain a positive leap second.
+    pub leap_seconds: Vec<i64>,
+    /// Outages as `(first minute, number of minutes)`, counted from the start of the run.
+    pub outages: Vec<(u32, u32)>,
+    /// DUT1 in deci-seconds, -8 to 8.
+    pub dut1: i8,
+    /// Noise added to the signal.
+    pub noise: NoiseConfig,
+    /// Seed of the noise source.
+    pub seed: u32,
+    /// Passed to `MSFUtils::decode_minute()`.
+    pub strict_checks: bool,
+}
+
+impl Default for SoakConfig {
+    fn default() -> Self {
+        Self {
+            start: 1_666_483_200, // 2022-10-23 00:00 UTC
+            minutes: 24 * 60,
+            leap_seconds: Vec::new(),
+            outages: Vec::new(),
+            dut1: 0,
+            noise: NoiseConfig::default(),
+            seed: 1,
+            strict_checks: false,
+        }
+    }
+}
+
+/// A violated invariant.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SoakViolation {
+    /// The decoded time went backwards without any field being flagged as jumped.
+    Backwards { minute: u32 },
+    /// The decoder reported a minute length other than 59, 60, or 61 seconds.
+    MinuteLength { minute: u32, length: u8 },
+}
+
+/// Outcome of a soak run.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SoakReport {
+    /// Number of simulated minutes.
+    pub minutes: u32,
+    /// Number of minutes which passed `decode_minute()`.
+    pub decoded: u32,
+    /// Number of decoded minutes matching the simulated time.
+    pub correct: u32,
+    pub violations: Vec<SoakViolation>,
+}
+
+/// Return the year, month, and day of the given number of days since 1970-01-01.
+fn civil_from_days(days: i64) -> (i64, u8, u8) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let doe = days - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u8;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u8;
+    let year = yoe + era * 400 + (month <= 2) as i64;
+    (year, month, day)
+}
+
+/// Unix time in seconds at which UK summer time starts (`month` 3) or ends (`month` 10), which
+/// is 01:00 UTC on the last Sunday of the month.
+fn dst_change(year: i64, month: u8) -> i64 {
+    let last = days_from_civil(year, month, 31);
+    // 1970-01-01 was a Thursday
+    let sunday = last - (last + 4).rem_euclid(7);
+    sunday * 86_400 + 3600
+}
+
+/// Return if UK summer time is in effect at the given Unix time in seconds.
+fn is_summer(unix_time: i64) -> bool {
+    let (year, _, _) = civil_from_days(unix_time.div_euclid(86_400));
+    (dst_change(year, 3)..dst_change(year, 10)).contains(&unix_time)
+}
+
+/// Encode the A and B bits broadcast in the minute before the given Unix time in seconds.
+///
+/// # Arguments
+/// * `unix_time` - time of the minute to encode
+/// * `dut1` - DUT1 in deci-seconds
+/// * `minute_length` - length of the minute in seconds, 61 for a positive leap second
+fn encode_minute(unix_time: i64, dut1: i8, minute_length: u8) -> ([bool; 61], [bool; 61]) {
+    let summer = is_summer(unix_time);
+    let local = unix_time + if summer { 3600 } else { 0 };
+    let days = local.div_euclid(86_400);
+    let (year, month, day) = civil_from_days(days);
+    let seconds = local.rem_euclid(86_400);
+    let mut rdt = RadioDateTimeUtils::new(0);
+    rdt.set_year(Some((year % 100) as u8), true, false);
+    rdt.set_month(Some(month), true, false);
+    rdt.set_day(Some(day), true, false);
+    rdt.set_weekday(Some((days + 4).rem_euclid(7) as u8), true, false);
+    rdt.set_hour(Some((seconds / 3600) as u8), true, false);
+    rdt.set_minute(Some((seconds / 60 % 60) as u8), true, false);
+    let options = EncodeOptions {
+        dut1,
+        summer_time: Some(summer),
+        // announce a change during the hour before it
+        dst_warning: is_summer(unix_time + 3660) != summer,
+        minute_length,
+    };
+    let frame = MSFFrame::encode(&rdt, &options).expect("all fields are set");
+    (
+        frame.bits_a.map(|bit| bit == Some(true)),
+        frame.bits_b.map(|bit| bit == Some(true)),
+    )
+}
+
+/// Return the decoded date/time in minutes since the Unix epoch, in UTC.
+fn decoded_utc_minutes(msf: &MSFUtils) -> Option<i64> {
+    let rdt = msf.get_radio_datetime();
+    let (month, day) = (rdt.get_month()?, rdt.get_day()?);
+    let year = full_year(rdt.get_year()?, month, day, rdt.get_weekday())?;
+    let days = days_from_civil(year, month, day);
+    let summer = rdt.get_dst()? & DST_SUMMER != 0;
+    Some(
+        days * 1440 + rdt.get_hour()? as i64 * 60 + rdt.get_minute()? as i64
+            - if summer { 60 } else { 0 },
+    )
+}
+
+/// Run the decoder on the simulated signal and check the invariants.
+///
+/// # Arguments
+/// * `msf` - the decoder, configured as in the application
+/// * `config` - the signal to simulate
+pub fn run_soak(msf: &mut MSFUtils, config: &SoakConfig) -> SoakReport {
+    let mut report = SoakReport {
+        minutes: config.minutes,
+        ..SoakReport::default()
+    };
+    let mut rng = XorShift32::new(config.seed);
+    let mut t = 1_000_000u32;
+    let mut last_decoded = None;
+    for minute in 0..config.minutes {
+        let unix_time = config.start + minute as i64 * 60;
+        let length = if config.leap_seconds.contains(&unix_time) {
+            61
+        } else {
+            60
+        };
+        // the bits broadcast during a minute describe the next one
+        let (a, b) = encode_minute(unix_time + 60, config.dut1, length as u8);
+        let outage = config
+            .outages
+            .iter()
+            .any(|&(first, count)| (first..first + count).contains(&minute));
+        if !outage {
+            let mut sim = MinuteSimulator::new(&a[..length], &b[..length], t, config.noise, rng);
+            for (is_low_edge, edge_t) in sim.by_ref() {
+                msf.handle_new_edge(is_low_edge, edge_t);
+                if !msf.get_new_second() {
+                    continue;
+                }
+                let minute_length = msf.get_minute_length();
+                if !(59..=61).contains(&minute_length) {
+                    report.violations.push(SoakViolation::MinuteLength {
+                        minute,
+                        length: minute_length,
+                    });
+                }
+                if msf.get_second() + 1 == minute_length
+                    && msf.decode_minute(config.strict_checks).is_ok()
+                {
+                    // the marker of this minute completed the previous one
+                    report.decoded += 1;
+                    let decoded = decoded_utc_minutes(msf);
+                    if decoded == Some(unix_time / 60) {
+                        report.correct += 1;
+                    }
+                    let rdt = msf.get_radio_datetime();
+                    let jumped = rdt.get_jump_year()
+                        || rdt.get_jump_month()
+                        || rdt.get_jump_day()
+                        || rdt.get_jump_weekday()
+                        || rdt.get_jump_hour()
+                        || rdt.get_jump_minute();
+                    if decoded.is_some() && decoded < last_decoded && !jumped {
+                        report.violations.push(SoakViolation::Backwards { minute });
+                    }
+                    last_decoded = decoded;
+                }
+                msf.increase_second();
+            }
+            rng = sim.into_rng();
+        }
+        t = t.wrapping_add(length as u32 * 1_000_000);
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{BIT_BUFFER_A, BIT_BUFFER_B};
+
+    #[test]
+    fn test_civil_from_days() {
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(civil_from_days(19_288), (2022, 10, 23));
+        assert_eq!(civil_from_days(days_from_civil(2024, 2, 29)), (2024, 2, 29));
+    }
+    #[test]
+    fn test_encode_minute() {
+        // 2022-10-23 14:58 BST, the weekday of the test data is one off
+        let (a, b) = encode_minute(1_666_533_480, -2, 60);
+        assert_eq!(a[..36], BIT_BUFFER_A[..36]);
+        assert_eq!(a[39..60], BIT_BUFFER_A[39..]);
+        assert_eq!(b[..56], BIT_BUFFER_B[..56]);
+        assert_eq!(b[57..60], BIT_BUFFER_B[57..]);
+        assert_eq!(dst_change(2022, 10), 1_667_091_600); // 2022-10-30 01:00 UTC
+    }
+    #[test]
+    fn test_run_soak() {
+        // 2016-12-31 22:00 UTC to 2017-01-01 02:00 UTC, with a leap second and an outage
+        let config = SoakConfig {
+            start: 1_483_221_600,
+            minutes: 240,
+            leap_seconds: vec![1_483_228_740],
+            outages: vec![(150, 20)],
+            dut1: -4,
+            noise: NoiseConfig {
+                jitter: 10_000,
+                ..NoiseConfig::default()
+            },
+            ..SoakConfig::default()
+        };
+        let report = run_soak(&mut MSFUtils::default(), &config);
+        assert_eq!(report.violations, []);
+        assert_eq!(report.decoded >= 218, true);
+        // the first marker after the outage completes the minute received before it
+        assert_eq!(report.correct, report.decoded - 1);
+    }
+    #[test]
+    fn test_run_soak_dst_change() {
+        // 2022-10-29 23:00 UTC to 2022-10-30 03:00 UTC
+        let config = SoakConfig {
+            start: 1_667_084_400,
+            minutes: 240,
+            ..SoakConfig::default()
+        };
+        let report = run_soak(&mut MSFUtils::default(), &config);
+        assert_eq!(report.violations, []);
+        assert_eq!(report.correct, report.decoded);
+        assert_eq!(report.decoded, 239);
+    }
+}
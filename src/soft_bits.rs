@@ -0,0 +1,106 @@
+//! Soft-decision classification of the bit pairs, giving the likelihood of each of the four
+//! possible (A,B) pairs instead of a single hard decision.
+//!
+//! The likelihoods fall linearly from 255 for a pulse of exactly the nominal length to 0 for
+//! a pulse which is `LIKELIHOOD_SPAN` or more off, so marginal pulses still carry information
+//! for voting or parity correction.
+
+/// Deviation from the nominal pulse length in microseconds at which the likelihood drops to 0.
+pub const LIKELIHOOD_SPAN: u32 = 100_000;
+
+/// Likelihoods (0-255) of the four possible bit pairs of a second.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BitLikelihoods {
+    /// Likelihood of (0,0), a 100 ms pulse.
+    pub l00: u8,
+    /// Likelihood of (0,1), two 100 ms pulses 100 ms apart.
+    pub l01: u8,
+    /// Likelihood of (1,0), a 200 ms pulse.
+    pub l10: u8,
+    /// Likelihood of (1,1), a 300 ms pulse.
+    pub l11: u8,
+}
+
+/// Likelihood of a measured length given the nominal one.
+fn kernel(measured: u32, nominal: u32) -> u8 {
+    let deviation = measured.abs_diff(nominal).min(LIKELIHOOD_SPAN);
+    ((LIKELIHOOD_SPAN - deviation) * 255 / LIKELIHOOD_SPAN) as u8
+}
+
+impl BitLikelihoods {
+    /// Get the likelihood of the given bit pair.
+    ///
+    /// # Arguments
+    /// * `a` - the A bit
+    /// * `b` - the B bit
+    pub fn get(&self, a: bool, b: bool) -> u8 {
+        match (a, b) {
+            (false, false) => self.l00,
+            (false, true) => self.l01,
+            (true, false) => self.l10,
+            (true, true) => self.l11,
+        }
+    }
+
+    /// Return the most likely bit pair, None if there is no single most likely one.
+    pub fn best(&self) -> Option<(bool, bool)> {
+        let pairs = [(false, false), (false, true), (true, false), (true, true)];
+        let max = pairs.iter().map(|&(a, b)| self.get(a, b)).max()?;
+        let mut best = pairs.iter().filter(|&&(a, b)| self.get(a, b) == max);
+        match (best.next(), best.next()) {
+            (Some(&pair), None) if max > 0 => Some(pair),
+            _ => None,
+        }
+    }
+
+    /// Likelihoods after the first pulse of a second.
+    ///
+    /// A (0,1) pair starts like a (0,0) pair, so it gets half of that likelihood until the
+    /// second pulse arrives.
+    ///
+    /// # Arguments
+    /// * `active` - length of the pulse in microseconds
+    pub(crate) fn first_pulse(active: u32) -> Self {
+        let l00 = kernel(active, 100_000);
+        Self {
+            l00,
+            l01: l00 / 2,
+            l10: kernel(active, 200_000),
+            l11: kernel(active, 300_000),
+        }
+    }
+
+    /// Likelihoods after the second pulse of a second, which only a (0,1) pair has.
+    ///
+    /// # Arguments
+    /// * `active` - length of the second pulse in microseconds
+    /// * `passive` - length of the passive part between both pulses in microseconds
+    pub(crate) fn second_pulse(active: u32, passive: u32) -> Self {
+        Self {
+            l01: ((kernel(active, 100_000) as u16 + kernel(passive, 100_000) as u16) / 2) as u8,
+            ..Self::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_first_pulse() {
+        let likelihoods = BitLikelihoods::first_pulse(240_000);
+        assert_eq!(likelihoods.l00, 0);
+        assert_eq!(likelihoods.l10, 153);
+        assert_eq!(likelihoods.l11, 102);
+        assert_eq!(likelihoods.best(), Some((true, false)));
+        assert_eq!(BitLikelihoods::first_pulse(250_000).best(), None); // tie
+        assert_eq!(BitLikelihoods::default().best(), None);
+    }
+    #[test]
+    fn test_second_pulse() {
+        let likelihoods = BitLikelihoods::second_pulse(100_000, 120_000);
+        assert_eq!(likelihoods.get(false, true), 229);
+        assert_eq!(likelihoods.best(), Some((false, true)));
+    }
+}
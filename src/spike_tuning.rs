@@ -0,0 +1,83 @@
+//! Spike limit tuned at runtime from the observed short pulses.
+//!
+//! The shortest pulse or gap of the signal lasts 100 ms, so anything much shorter is noise. The
+//! tuner keeps a decaying histogram of these short pulses and, once per minute, puts the spike
+//! limit one bucket above the longest bucket which is hit repeatedly. Without short pulses, the
+//! default limit is used.
+
+use crate::SPIKE_LIMIT;
+
+/// Lowest spike limit the tuner chooses, in microseconds.
+pub const AUTO_SPIKE_LIMIT_MIN: u32 = 10_000;
+/// Highest spike limit the tuner chooses, in microseconds, well below the shortest pulse.
+pub const AUTO_SPIKE_LIMIT_MAX: u32 = 60_000;
+
+/// Width of a histogram bucket in microseconds.
+const BUCKET_WIDTH: u32 = 5_000;
+/// Number of buckets, covering pulses up to `AUTO_SPIKE_LIMIT_MAX`.
+const BUCKETS: usize = (AUTO_SPIKE_LIMIT_MAX / BUCKET_WIDTH) as usize;
+/// Number of hits for a bucket to count as a burst.
+const MIN_HITS: u16 = 2;
+
+/// Decaying histogram of the lengths of the short pulses.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) struct SpikeTuner {
+    buckets: [u16; BUCKETS],
+}
+
+impl SpikeTuner {
+    /// Add the time since the previous edge, pulses of `AUTO_SPIKE_LIMIT_MAX` and longer are
+    /// ignored.
+    ///
+    /// # Arguments
+    /// * `t_diff` - time since the previous edge in microseconds
+    pub(crate) fn add(&mut self, t_diff: u32) {
+        if t_diff < AUTO_SPIKE_LIMIT_MAX {
+            let idx = (t_diff / BUCKET_WIDTH) as usize;
+            self.buckets[idx] = self.buckets[idx].saturating_add(1);
+        }
+    }
+
+    /// Return the spike limit for the next minute and let the histogram decay.
+    pub(crate) fn end_minute(&mut self) -> u32 {
+        let limit = self
+            .buckets
+            .iter()
+            .rposition(|&hits| hits >= MIN_HITS)
+            .map_or(SPIKE_LIMIT, |idx| {
+                ((idx as u32 + 2) * BUCKET_WIDTH).clamp(AUTO_SPIKE_LIMIT_MIN, AUTO_SPIKE_LIMIT_MAX)
+            });
+        for hits in self.buckets.iter_mut() {
+            *hits /= 2;
+        }
+        limit
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spike_tuner() {
+        let mut tuner = SpikeTuner::default();
+        assert_eq!(tuner.end_minute(), SPIKE_LIMIT);
+        for _ in 0..4 {
+            tuner.add(35_000);
+        }
+        tuner.add(50_000); // a single hit is not a burst
+        tuner.add(150_000); // a regular pulse
+        assert_eq!(tuner.end_minute(), 45_000);
+        assert_eq!(tuner.end_minute(), 45_000); // still remembered
+        assert_eq!(tuner.end_minute(), SPIKE_LIMIT);
+        for _ in 0..2 {
+            tuner.add(2_000);
+            tuner.add(59_999);
+        }
+        assert_eq!(tuner.end_minute(), AUTO_SPIKE_LIMIT_MAX);
+        tuner = SpikeTuner::default();
+        tuner.add(2_000);
+        tuner.add(3_000);
+        assert_eq!(tuner.end_minute(), AUTO_SPIKE_LIMIT_MIN);
+    }
+}
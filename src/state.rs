@@ -0,0 +1,137 @@
+//! Serializable state of the decoder, to persist it across a restart of the application.
+//!
+//! Enabled with the `serde` feature.
+//!
+//! The state holds what was received and decoded: the bits of the current minute, the date/time,
+//! DUT1, and the parities. The configuration is not part of it, so a state is restored into a
+//! decoder which was configured as before. Edge tracking restarts with the next edge, and
+//! announcements of DST changes and leap seconds are picked up again from the signal.
+
+use crate::MSFUtils;
+use radio_datetime_utils::{RadioDateTimeUtils, BIT_BUFFER_SIZE, DST_SUMMER};
+use serde::{Deserialize, Serialize};
+
+/// The state of a decoder, see `MSFUtils::save_state()`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DecoderState {
+    pub first_minute: bool,
+    pub second: u8,
+    /// Bits of the A channel which were received, bit n is second n.
+    pub known_a: u64,
+    /// Values of the received bits of the A channel.
+    pub bits_a: u64,
+    /// Bits of the B channel which were received, bit n is second n.
+    pub known_b: u64,
+    /// Values of the received bits of the B channel.
+    pub bits_b: u64,
+    pub year: Option<u8>,
+    pub month: Option<u8>,
+    pub day: Option<u8>,
+    pub weekday: Option<u8>,
+    pub hour: Option<u8>,
+    pub minute: Option<u8>,
+    pub summer_time: Option<bool>,
+    /// DUT1 in deci-seconds, always None with the `no-dut1` feature.
+    pub dut1: Option<i8>,
+    pub parities: [Option<bool>; 4],
+    pub minutes_since_decode: Option<u16>,
+}
+
+/// Pack a bit buffer into a mask of the received bits and their values.
+fn pack(buffer: &[Option<bool>; BIT_BUFFER_SIZE]) -> (u64, u64) {
+    buffer
+        .iter()
+        .enumerate()
+        .fold((0, 0), |(known, bits), (idx, bit)| match bit {
+            Some(value) => (known | 1 << idx, bits | (*value as u64) << idx),
+            None => (known, bits),
+        })
+}
+
+/// Unpack a mask of the received bits and their values into a bit buffer.
+fn unpack(known: u64, bits: u64) -> [Option<bool>; BIT_BUFFER_SIZE] {
+    core::array::from_fn(|idx| (known & 1 << idx != 0).then_some(bits & 1 << idx != 0))
+}
+
+impl MSFUtils {
+    /// Return the state of the decoder, to be restored later using `restore_state()`.
+    pub fn save_state(&self) -> DecoderState {
+        let (known_a, bits_a) = pack(&self.bit_buffer_a);
+        let (known_b, bits_b) = pack(&self.bit_buffer_b);
+        let rdt = &self.radio_datetime;
+        DecoderState {
+            first_minute: self.first_minute,
+            second: self.second,
+            known_a,
+            bits_a,
+            known_b,
+            bits_b,
+            year: rdt.get_year(),
+            month: rdt.get_month(),
+            day: rdt.get_day(),
+            weekday: rdt.get_weekday(),
+            hour: rdt.get_hour(),
+            minute: rdt.get_minute(),
+            summer_time: rdt.get_dst().map(|dst| dst & DST_SUMMER != 0),
+            dut1: self.get_dut1(),
+            parities: [self.parity_1, self.parity_2, self.parity_3, self.parity_4],
+            minutes_since_decode: self.minutes_since_decode,
+        }
+    }
+
+    /// Restore a state returned by `save_state()`, keeping the configuration of this decoder.
+    ///
+    /// Out of range values of the state are ignored.
+    ///
+    /// # Arguments
+    /// * `state` - the state to restore
+    pub fn restore_state(&mut self, state: &DecoderState) {
+        self.first_minute = state.first_minute;
+        self.bit_buffer_a = unpack(state.known_a, state.bits_a);
+        self.bit_buffer_b = unpack(state.known_b, state.bits_b);
+        self.second = state.second.min(BIT_BUFFER_SIZE as u8 - 1);
+        let mut rdt = RadioDateTimeUtils::new(0);
+        rdt.set_year(state.year, true, false);
+        rdt.set_month(state.month, true, false);
+        rdt.set_day(state.day, true, false);
+        rdt.set_weekday(state.weekday, true, false);
+        rdt.set_hour(state.hour, true, false);
+        rdt.set_minute(state.minute, true, false);
+        rdt.set_dst(state.summer_time, Some(false), false);
+        self.radio_datetime = rdt;
+        #[cfg(not(feature = "no-dut1"))]
+        {
+            self.dut1 = state.dut1;
+        }
+        [self.parity_1, self.parity_2, self.parity_3, self.parity_4] = state.parities;
+        self.minutes_since_decode = state.minutes_since_decode;
+        self.before_first_edge = true;
+        self.new_second = false;
+        self.new_minute = false;
+        self.past_new_minute = false;
+        self.check_invariants();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::filled_msf;
+
+    #[test]
+    fn test_save_restore_state() {
+        let mut msf = filled_msf();
+        msf.bit_buffer_b[20] = None;
+        assert_eq!(msf.decode_minute(false).is_ok(), true);
+        let state = msf.save_state();
+        assert_eq!(state.known_a, (1 << 60) - 1);
+        assert_eq!(state.known_b, (1 << 60) - 1 - (1 << 20));
+        let mut restored = MSFUtils::default();
+        restored.restore_state(&state);
+        assert_eq!(restored.save_state(), state);
+        assert_eq!(restored.bit_buffer_a, msf.bit_buffer_a);
+        assert_eq!(restored.bit_buffer_b, msf.bit_buffer_b);
+        assert_eq!(restored.get_radio_datetime().get_minute(), Some(58));
+        assert_eq!(restored.snapshot().first_minute, false);
+    }
+}
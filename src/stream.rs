@@ -0,0 +1,88 @@
+//! Streaming wrapper which turns an iterator of edges into an iterator of decoded minutes, e.g.
+//! to replay a recorded edge log in a few lines.
+//!
+//! The wrapper feeds `handle_new_edge()`, decodes each complete minute with the checks chosen by
+//! `decode_minute_auto()`, and calls `increase_second()` in the right order.
+
+use crate::api2::{DecodeError, Snapshot};
+use crate::MSFUtils;
+
+/// Iterator over the decoded minutes of a stream of edges, see `MSFUtils::decode_edges()`.
+pub struct MinuteStream<'a, I> {
+    msf: &'a mut MSFUtils,
+    edges: I,
+    last_t: Option<u32>,
+}
+
+impl<I> MinuteStream<'_, I> {
+    /// Get the time stamp of the edge after which the last minute was yielded.
+    pub fn get_last_t(&self) -> Option<u32> {
+        self.last_t
+    }
+}
+
+impl<I: Iterator<Item = (bool, u32)>> Iterator for MinuteStream<'_, I> {
+    type Item = Result<Snapshot, DecodeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for (is_low_edge, t) in self.edges.by_ref() {
+            self.msf.handle_new_edge(is_low_edge, t);
+            if !self.msf.get_new_second() {
+                continue;
+            }
+            let res = (self.msf.get_second() + 1 == self.msf.get_minute_length())
+                .then(|| self.msf.decode_minute_auto());
+            self.msf.increase_second();
+            if res.is_some() {
+                self.last_t = Some(t);
+                return res;
+            }
+        }
+        None
+    }
+}
+
+impl MSFUtils {
+    /// Feed a stream of edges to the decoder, yielding the result of each complete minute.
+    ///
+    /// The decoder is borrowed while iterating, so it can be queried afterwards.
+    ///
+    /// # Arguments
+    /// * `edges` - the edges as `(is_low_edge, t)` pairs, see `handle_new_edge()`
+    pub fn decode_edges<I: IntoIterator<Item = (bool, u32)>>(
+        &mut self,
+        edges: I,
+    ) -> MinuteStream<'_, I::IntoIter> {
+        MinuteStream {
+            msf: self,
+            edges: edges.into_iter(),
+            last_t: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::minute_edges;
+
+    #[test]
+    fn test_decode_edges() {
+        let mut msf = MSFUtils::default();
+        // two minutes, so the first one starts with a complete marker
+        let mut edges = minute_edges(1_000_000);
+        edges.pop();
+        edges.extend(minute_edges(61_000_000));
+        let mut stream = msf.decode_edges(edges);
+        assert_eq!(stream.get_last_t(), None);
+        let first = stream.next();
+        assert_eq!(stream.get_last_t(), Some(61_000_000));
+        let minutes: Vec<_> = first.into_iter().chain(stream).collect();
+        assert_eq!(minutes.len(), 2);
+        assert_eq!(
+            minutes[1].ok().unwrap().radio_datetime.get_minute(),
+            Some(58)
+        );
+        assert_eq!(msf.get_second(), 0);
+    }
+}
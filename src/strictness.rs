@@ -0,0 +1,100 @@
+//! Automatic escalation from relaxed to strict checks, to acquire the time quickly and then keep
+//! it reliably.
+
+/// Level of the checks applied by `decode_minute_auto()`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Strictness {
+    /// Relaxed checks, see `decode_minute()`.
+    #[default]
+    Relaxed,
+    /// Strict checks, see `decode_minute()`.
+    Strict,
+}
+
+/// When `decode_minute_auto()` switches between relaxed and strict checks.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Escalation {
+    /// Number of consecutive consistent minutes after which strict checks are used.
+    pub escalate_after: u8,
+    /// Number of consecutive failed minutes after which relaxed checks are used again.
+    pub deescalate_after: u8,
+}
+
+impl Default for Escalation {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl Escalation {
+    /// The default limits.
+    pub const DEFAULT: Self = Self {
+        escalate_after: 3,
+        deescalate_after: 10,
+    };
+
+    /// Return if the limits are usable, i.e. both are at least 1.
+    pub fn is_valid(&self) -> bool {
+        self.escalate_after > 0 && self.deescalate_after > 0
+    }
+
+    /// Update the streak with the result of a minute and return the new level.
+    ///
+    /// While relaxed, the streak counts consistent minutes, while strict it counts failed ones.
+    ///
+    /// # Arguments
+    /// * `level` - the current level
+    /// * `streak` - the current streak, reset when it is broken or the level changes
+    /// * `consistent` - the minute was decoded and did not jump
+    pub(crate) fn update(
+        &self,
+        level: Strictness,
+        streak: &mut u8,
+        consistent: bool,
+    ) -> Strictness {
+        let (counts, limit, next) = match level {
+            Strictness::Relaxed => (consistent, self.escalate_after, Strictness::Strict),
+            Strictness::Strict => (!consistent, self.deescalate_after, Strictness::Relaxed),
+        };
+        if !counts {
+            *streak = 0;
+            return level;
+        }
+        *streak = streak.saturating_add(1);
+        if *streak >= limit {
+            *streak = 0;
+            next
+        } else {
+            level
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_update() {
+        let escalation = Escalation {
+            escalate_after: 2,
+            deescalate_after: 2,
+        };
+        let mut streak = 0;
+        let mut level = Strictness::Relaxed;
+        for (consistent, expected) in [
+            (true, Strictness::Relaxed),
+            (false, Strictness::Relaxed), // streak broken
+            (true, Strictness::Relaxed),
+            (true, Strictness::Strict),
+            (false, Strictness::Strict),
+            (true, Strictness::Strict), // streak broken
+            (false, Strictness::Strict),
+            (false, Strictness::Relaxed),
+        ] {
+            level = escalation.update(level, &mut streak, consistent);
+            assert_eq!(level, expected);
+        }
+        assert_eq!(streak, 0);
+    }
+}
@@ -0,0 +1,69 @@
+//! Data shared by the unit tests.
+
+use crate::MSFUtils;
+
+/// A bits of 2022-10-23 (Saturday) 14:58 BST, DUT1 = -0.2 s
+pub const BIT_BUFFER_A: [bool; 60] = [
+    true, // begin-of-minute marker
+    false, false, false, false, false, false, false, false, // unused 1-8
+    false, false, false, false, false, false, false, false, // unused 9-16
+    false, false, true, false, false, false, true, false, // year 22
+    true, false, false, false, false, // month 10
+    true, false, false, false, true, true, // day 23
+    true, true, false, // Saturday
+    false, true, false, true, false, false, // hour 14
+    true, false, true, true, false, false, false, // minute 58
+    false, true, true, true, true, true, true, false, // end-of-minute marker
+];
+/// B bits of 2022-10-23 (Saturday) 14:58 BST, DUT1 = -0.2 s
+pub const BIT_BUFFER_B: [bool; 60] = [
+    true, // begin-of-minute marker,
+    false, false, false, false, false, false, false, false, // DUT1 positive
+    true, true, false, false, false, false, false, false, // DUT1 negative (-2)
+    false, false, false, false, false, false, false, false, // unused 17-24
+    false, false, false, false, false, false, false, false, // unused 25-32
+    false, false, false, false, false, false, false, false, // unused 33-40
+    false, false, false, false, false, false, false, false, // unused 41-48
+    false, false, false, false, // unused 49-52
+    false, // summer time warning
+    true,  // year parity
+    true,  // month+day parity
+    true,  // weekday parity
+    false, // hour+minute parity
+    true,  // summer time active
+    false, // unused
+];
+
+/// Return a decoder holding the bits of `BIT_BUFFER_A` and `BIT_BUFFER_B` at the last second.
+pub fn filled_msf() -> MSFUtils {
+    let mut msf = MSFUtils::default();
+    for b in 0..60 {
+        msf.bit_buffer_a[b] = Some(BIT_BUFFER_A[b]);
+        msf.bit_buffer_b[b] = Some(BIT_BUFFER_B[b]);
+    }
+    msf.second = 59;
+    msf
+}
+
+/// Return the edges of a minute carrying `BIT_BUFFER_A` and `BIT_BUFFER_B`, starting at `t`,
+/// followed by the edge starting the next minute.
+pub fn minute_edges(t: u32) -> Vec<(bool, u32)> {
+    let mut edges = Vec::new();
+    for s in 0..60 {
+        let start = t + s as u32 * 1_000_000;
+        edges.push((false, start));
+        match (s, BIT_BUFFER_A[s], BIT_BUFFER_B[s]) {
+            (0, _, _) => edges.push((true, start + 500_000)),
+            (_, false, false) => edges.push((true, start + 100_000)),
+            (_, true, false) => edges.push((true, start + 200_000)),
+            (_, true, true) => edges.push((true, start + 300_000)),
+            (_, false, true) => {
+                edges.push((true, start + 100_000));
+                edges.push((false, start + 200_000));
+                edges.push((true, start + 300_000));
+            }
+        }
+    }
+    edges.push((false, t + 60_000_000));
+    edges
+}
@@ -0,0 +1,244 @@
+//! Encoded example minutes, to be used as golden data by the tests of this crate and of
+//! downstream projects.
+//!
+//! Each vector holds the A and B bits of one minute as strings of `0` and `1`, together with the
+//! date/time they encode, and can be turned into the edges a receiver would produce.
+//!
+//! The bits follow the encoding rules of the MSF time code as published by NPL: BCD date/time in
+//! A17-A51, the end-of-minute marker in A52-A59, DUT1 in B1-B16, and the odd parities, summer time
+//! warning, and summer time flag in B53-B58.
+
+/// One encoded minute and its expected decoding.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TestVector {
+    /// Description of the minute.
+    pub name: &'static str,
+    /// A bits of seconds 0-59.
+    pub bits_a: &'static str,
+    /// B bits of seconds 0-59.
+    pub bits_b: &'static str,
+    /// Year within the century.
+    pub year: u8,
+    pub month: u8,
+    pub day: u8,
+    /// Day of the week, 0 is Sunday.
+    pub weekday: u8,
+    pub hour: u8,
+    pub minute: u8,
+    /// Summer time (BST) is active.
+    pub summer_time: bool,
+    /// A change to or from summer time is announced.
+    pub dst_warning: bool,
+    /// DUT1 (UT1 - UTC) in deci-seconds.
+    pub dut1: i8,
+}
+
+impl TestVector {
+    /// Get the A bit of a second, None if the second is out of range.
+    ///
+    /// # Arguments
+    /// * `second` - the second to get the bit of, 0-59
+    pub fn bit_a(&self, second: usize) -> Option<bool> {
+        self.bits_a.as_bytes().get(second).map(|&b| b == b'1')
+    }
+
+    /// Get the B bit of a second, None if the second is out of range.
+    ///
+    /// # Arguments
+    /// * `second` - the second to get the bit of, 0-59
+    pub fn bit_b(&self, second: usize) -> Option<bool> {
+        self.bits_b.as_bytes().get(second).map(|&b| b == b'1')
+    }
+
+    /// Return the edges of the minute as `(is_low_edge, t)` pairs for `handle_new_edge()`,
+    /// followed by the rising edge of the next minute.
+    ///
+    /// The pulses have their nominal lengths: 500 ms for the begin-of-minute marker, 100 ms for
+    /// (0,0), 200 ms for (1,0), 300 ms for (1,1), and twice 100 ms for (0,1).
+    ///
+    /// # Arguments
+    /// * `t` - time stamp of the start of the minute, in microseconds
+    pub fn edges(&self, t: u32) -> impl Iterator<Item = (bool, u32)> + '_ {
+        (0..60)
+            .flat_map(move |s| {
+                let start = t.wrapping_add(s as u32 * 1_000_000);
+                let at = |offset: u32| Some((true, start.wrapping_add(offset)));
+                let rest = match (s, self.bit_a(s), self.bit_b(s)) {
+                    (0, _, _) => [at(500_000), None, None],
+                    (_, Some(false), Some(true)) => [
+                        at(100_000),
+                        Some((false, start.wrapping_add(200_000))),
+                        at(300_000),
+                    ],
+                    (_, Some(true), Some(true)) => [at(300_000), None, None],
+                    (_, Some(true), _) => [at(200_000), None, None],
+                    _ => [at(100_000), None, None],
+                };
+                core::iter::once(Some((false, start))).chain(rest)
+            })
+            .flatten()
+            .chain(core::iter::once((false, t.wrapping_add(60_000_000))))
+    }
+}
+
+/// The example minutes.
+pub const TEST_VECTORS: [TestVector; 4] = [
+    TestVector {
+        name: "2022-10-23 14:58 BST, DUT1 -0.2 s",
+        bits_a: "100000000000000000010001010000100011000010100101100001111110",
+        bits_b: "100000000110000000000000000000000000000000000000000000111010",
+        year: 22,
+        month: 10,
+        day: 23,
+        weekday: 0,
+        hour: 14,
+        minute: 58,
+        summer_time: true,
+        dst_warning: false,
+        dut1: -2,
+    },
+    TestVector {
+        name: "2024-03-31 00:58 GMT, change to BST announced, DUT1 +0.5 s",
+        bits_a: "100000000000000000010010000011110001000000000101100001111110",
+        bits_b: "111111000000000000000000000000000000000000000000000001101000",
+        year: 24,
+        month: 3,
+        day: 31,
+        weekday: 0,
+        hour: 0,
+        minute: 58,
+        summer_time: false,
+        dst_warning: true,
+        dut1: 5,
+    },
+    TestVector {
+        name: "2000-01-01 00:00 GMT, DUT1 0.0 s",
+        bits_a: "100000000000000000000000000001000001110000000000000001111110",
+        bits_b: "100000000000000000000000000000000000000000000000000000111100",
+        year: 0,
+        month: 1,
+        day: 1,
+        weekday: 6,
+        hour: 0,
+        minute: 0,
+        summer_time: false,
+        dst_warning: false,
+        dut1: 0,
+    },
+    TestVector {
+        name: "1999-12-31 23:59 GMT, DUT1 +0.8 s",
+        bits_a: "100000000000000001001100110010110001101100011101100101111110",
+        bits_b: "111111111000000000000000000000000000000000000000000000101000",
+        year: 99,
+        month: 12,
+        day: 31,
+        weekday: 5,
+        hour: 23,
+        minute: 59,
+        summer_time: false,
+        dst_warning: false,
+        dut1: 8,
+    },
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MSFUtils;
+    use radio_datetime_utils::DST_SUMMER;
+
+    #[test]
+    fn test_vectors_well_formed() {
+        for vector in TEST_VECTORS {
+            assert_eq!(vector.bits_a.len(), 60, "{}", vector.name);
+            assert_eq!(vector.bits_b.len(), 60, "{}", vector.name);
+            assert_eq!(vector.bit_a(60), None);
+            assert_eq!(
+                vector.bit_b(53),
+                Some(vector.dst_warning),
+                "{}",
+                vector.name
+            );
+            assert_eq!(
+                vector.bit_b(58),
+                Some(vector.summer_time),
+                "{}",
+                vector.name
+            );
+            // one rising edge per second and for the next minute, plus one for each (0,1) pair
+            let pairs_01 = (0..60)
+                .filter(|&s| (vector.bit_a(s), vector.bit_b(s)) == (Some(false), Some(true)))
+                .count();
+            assert_eq!(
+                vector
+                    .edges(0)
+                    .filter(|&(is_low_edge, _)| !is_low_edge)
+                    .count(),
+                61 + pairs_01,
+                "{}",
+                vector.name
+            );
+        }
+    }
+
+    #[test]
+    fn test_vectors_edges_to_minute() {
+        for vector in TEST_VECTORS {
+            let mut msf = MSFUtils::default();
+            // end a pulse first, so the passive part of the begin-of-minute marker is known
+            msf.handle_new_edge(true, 0);
+            let mut decoded = false;
+            for (is_low_edge, t) in vector.edges(1_000_000) {
+                msf.handle_new_edge(is_low_edge, t);
+                if msf.get_new_second() {
+                    if msf.get_second() + 1 == msf.get_minute_length() {
+                        assert_eq!(msf.decode_minute(true).is_ok(), true, "{}", vector.name);
+                        decoded = true;
+                    } else if msf.get_second() > 0 {
+                        assert_eq!(
+                            (msf.get_current_bit_a(), msf.get_current_bit_b()),
+                            (
+                                vector.bit_a(msf.get_second() as usize),
+                                vector.bit_b(msf.get_second() as usize)
+                            ),
+                            "{} second {}",
+                            vector.name,
+                            msf.get_second()
+                        );
+                    }
+                    msf.increase_second();
+                }
+            }
+            assert_eq!(decoded, true, "{}", vector.name);
+            let rdt = msf.get_radio_datetime();
+            assert_eq!(
+                (
+                    rdt.get_year(),
+                    rdt.get_month(),
+                    rdt.get_day(),
+                    rdt.get_weekday(),
+                    rdt.get_hour(),
+                    rdt.get_minute()
+                ),
+                (
+                    Some(vector.year),
+                    Some(vector.month),
+                    Some(vector.day),
+                    Some(vector.weekday),
+                    Some(vector.hour),
+                    Some(vector.minute)
+                ),
+                "{}",
+                vector.name
+            );
+            assert_eq!(
+                rdt.get_dst().map(|dst| dst & DST_SUMMER != 0),
+                Some(vector.summer_time),
+                "{}",
+                vector.name
+            );
+            #[cfg(not(feature = "no-dut1"))]
+            assert_eq!(msf.get_dut1(), Some(vector.dut1), "{}", vector.name);
+        }
+    }
+}
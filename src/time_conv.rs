@@ -0,0 +1,61 @@
+//! Conversions of decoded minutes to the date/time types of the `time` crate, like the `chrono`
+//! feature does for `chrono`.
+//!
+//! MSF broadcasts the year within the century only, the full year is chosen using the weekday,
+//! see `reference_check::full_year()`.
+//!
+//! Enabled with the `time` feature.
+
+use crate::api2::Snapshot;
+use crate::reference_check::full_year;
+use radio_datetime_utils::DST_SUMMER;
+use time::{Date, Month, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset};
+
+impl Snapshot {
+    /// Return the broadcast UK civil time, None if a field of it is unknown.
+    pub fn to_time_primitive(&self) -> Option<PrimitiveDateTime> {
+        let rdt = &self.radio_datetime;
+        let (month, day) = (rdt.get_month()?, rdt.get_day()?);
+        let year = full_year(rdt.get_year()?, month, day, rdt.get_weekday())?;
+        let date = Date::from_calendar_date(year as i32, Month::try_from(month).ok()?, day).ok()?;
+        let time = Time::from_hms(rdt.get_hour()?, rdt.get_minute()?, 0).ok()?;
+        Some(PrimitiveDateTime::new(date, time))
+    }
+
+    /// Return the broadcast time as UK civil time with its offset from UTC, which is one hour
+    /// during summer time (BST), None if a field of it is unknown.
+    pub fn to_time_local(&self) -> Option<OffsetDateTime> {
+        let summer = self.radio_datetime.get_dst()? & DST_SUMMER != 0;
+        let offset = UtcOffset::from_hms(if summer { 1 } else { 0 }, 0, 0).ok()?;
+        Some(self.to_time_primitive()?.assume_offset(offset))
+    }
+
+    /// Return the broadcast time in UTC, None if a field of it is unknown.
+    pub fn to_time_utc(&self) -> Option<OffsetDateTime> {
+        Some(self.to_time_local()?.to_offset(UtcOffset::UTC))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::filled_msf;
+    use time::Month;
+
+    #[test]
+    fn test_to_time() {
+        let mut msf = filled_msf();
+        assert_eq!(msf.snapshot().to_time_primitive(), None);
+        let snapshot = msf.decode_minute(false).ok().unwrap();
+        let primitive = snapshot.to_time_primitive().unwrap();
+        assert_eq!(
+            (primitive.year(), primitive.month(), primitive.day()),
+            (2022, Month::October, 23)
+        );
+        assert_eq!((primitive.hour(), primitive.minute()), (14, 58));
+        let local = snapshot.to_time_local().unwrap();
+        assert_eq!(local.offset().whole_hours(), 1);
+        let utc = snapshot.to_time_utc().unwrap();
+        assert_eq!(utc.unix_timestamp(), 1_666_533_480);
+        assert_eq!(utc.hour(), 13);
+    }
+}
@@ -0,0 +1,265 @@
+//! Stripped decoder for devices which only need the hour and minute.
+//!
+//! It shares the edge framing and the pulse classification with `MSFUtils`, but only buffers the
+//! bits from second 38 onwards and does not handle the date or DUT1. Enabled with the `time-only`
+//! feature, the full decoder is left out of the binary by the linker if it is not used.
+
+use crate::core::framing::{EdgeFraming, FramedEdge, FramingLimits};
+use crate::core::pulse::Pulse;
+use crate::{MINUTE_LIMIT, PASSIVE_RUNAWAY, SPIKE_LIMIT, TIMER_DISCONTINUITY};
+use radio_datetime_utils::{radio_datetime_helpers, RadioDateTimeUtils, BIT_BUFFER_SIZE};
+
+/// First buffered second, the hour starts here in a minute with a negative leap second.
+const FIRST_SECOND: usize = 38;
+/// Number of buffered seconds.
+const BUFFER_SIZE: usize = BIT_BUFFER_SIZE - FIRST_SECOND;
+/// Limits to frame the edges with.
+const LIMITS: FramingLimits = FramingLimits {
+    spike: SPIKE_LIMIT,
+    passive_runaway: PASSIVE_RUNAWAY,
+    minute: MINUTE_LIMIT,
+    discontinuity: TIMER_DISCONTINUITY,
+};
+
+/// Hour/minute-only MSF decoder
+#[derive(Clone, Copy, Debug, Default)]
+pub struct MsfTimeOnly {
+    first_minute: bool,
+    new_minute: bool,
+    past_new_minute: bool,
+    new_second: bool,
+    second: u8,
+    bit_buffer_a: [Option<bool>; BUFFER_SIZE],
+    bit_buffer_b: [Option<bool>; BUFFER_SIZE],
+    hour: Option<u8>,
+    minute: Option<u8>,
+    summer_time: Option<bool>,
+    before_first_edge: bool,
+    t0: u32,
+    old_t_diff: u32,
+}
+
+impl MsfTimeOnly {
+    pub fn new() -> Self {
+        Self {
+            first_minute: true,
+            before_first_edge: true,
+            ..Self::default()
+        }
+    }
+
+    /// Return if this is the first minute that is decoded.
+    pub fn get_first_minute(&self) -> bool {
+        self.first_minute
+    }
+
+    /// Return if a new minute (0111_1110 marker) has arrived.
+    pub fn get_new_minute(&self) -> bool {
+        self.new_minute
+    }
+
+    /// Return if the 500 ms long begin-of-minute marker has arrived.
+    pub fn get_past_new_minute(&self) -> bool {
+        self.past_new_minute
+    }
+
+    /// Return if a new second has arrived.
+    pub fn get_new_second(&self) -> bool {
+        self.new_second
+    }
+
+    /// Get the second counter.
+    pub fn get_second(&self) -> u8 {
+        self.second
+    }
+
+    /// Get the decoded hour.
+    pub fn get_hour(&self) -> Option<u8> {
+        self.hour
+    }
+
+    /// Get the decoded minute.
+    pub fn get_minute(&self) -> Option<u8> {
+        self.minute
+    }
+
+    /// Get if summer time (BST) is active.
+    pub fn get_summer_time(&self) -> Option<bool> {
+        self.summer_time
+    }
+
+    /// Get the buffered A bit of the given second, None if unknown or not buffered.
+    ///
+    /// # Arguments
+    /// * `second` - the second of the bit
+    pub fn get_bit_a(&self, second: u8) -> Option<bool> {
+        (second as usize)
+            .checked_sub(FIRST_SECOND)
+            .and_then(|idx| *self.bit_buffer_a.get(idx)?)
+    }
+
+    /// Set the bits of the current second, if it is buffered.
+    fn set_current_bits(&mut self, a: Option<bool>, b: Option<bool>) {
+        if let Some(idx) = (self.second as usize).checked_sub(FIRST_SECOND) {
+            self.bit_buffer_a[idx] = a;
+            self.bit_buffer_b[idx] = b;
+        }
+    }
+
+    /// Determine the bit value if a new edge is received, see `MSFUtils::handle_new_edge()`.
+    ///
+    /// This method must be called _before_ `increase_second()`.
+    ///
+    /// # Arguments
+    /// * `is_low_edge` - indicates that the edge has gone from high to low (as opposed to
+    ///   low-to-high).
+    /// * `t` - time stamp of the received edge, in microseconds
+    pub fn handle_new_edge(&mut self, is_low_edge: bool, t: u32) {
+        let framed = self.frame_edge(is_low_edge, t, &LIMITS);
+        if matches!(framed, FramedEdge::First | FramedEdge::Spike { .. }) {
+            return; // random positive or negative spike, ignore
+        }
+        self.new_minute = false;
+        self.past_new_minute = false;
+        match framed {
+            FramedEdge::First | FramedEdge::Spike { .. } => {} // handled above
+            FramedEdge::Discontinuity => {
+                self.new_second = false;
+                self.set_current_bits(None, None);
+            }
+            FramedEdge::PulseEnd { active, passive } => {
+                self.new_second = false;
+                match Pulse::classify(active, passive) {
+                    Pulse::Bit { a, b } => self.set_current_bits(Some(a), Some(b)),
+                    Pulse::MinuteMarker => {
+                        self.past_new_minute = true;
+                        self.second = 0;
+                    }
+                    Pulse::Runaway => self.set_current_bits(None, None),
+                    Pulse::Unchanged => {}
+                }
+                self.new_minute = self.end_of_minute_marker_present();
+            }
+            FramedEdge::PulseStart { new_second, .. } => self.new_second = new_second,
+            FramedEdge::PassiveRunaway { .. } => self.set_current_bits(None, None),
+        }
+    }
+
+    /// Return if the end-of-minute marker (0111_1110) is present at the end of the A bits.
+    pub fn end_of_minute_marker_present(&self) -> bool {
+        const MARKER: [bool; 8] = [false, true, true, true, true, true, true, false];
+        (self.second as usize) >= FIRST_SECOND + 7
+            && (0..8).all(|idx| self.get_bit_a(self.second - 7 + idx as u8) == Some(MARKER[idx]))
+    }
+
+    /// Determine the length of this minute in seconds.
+    pub fn get_minute_length(&self) -> u8 {
+        if (58..=60).contains(&self.second) && self.end_of_minute_marker_present() {
+            self.second + 1
+        } else {
+            60
+        }
+    }
+
+    /// Increase or reset `second`, see `MSFUtils::increase_second()`.
+    pub fn increase_second(&mut self) -> bool {
+        let minute_length = self.get_minute_length();
+        RadioDateTimeUtils::increase_second(&mut self.second, self.new_minute, minute_length)
+    }
+
+    /// Decode the hour and minute broadcast during the last minute, or advance them by one
+    /// minute if that fails.
+    ///
+    /// This method must be called _before_ `increase_second()`
+    pub fn decode_time(&mut self) {
+        let minute_length = self.get_minute_length();
+        let mut decoded = false;
+        if self.second + 1 == minute_length {
+            // index of second 39, shifted by a leap second
+            let start = 39 + minute_length as usize - 60 - FIRST_SECOND;
+            let parity = radio_datetime_helpers::get_parity(
+                &self.bit_buffer_a,
+                start,
+                start + 12,
+                self.bit_buffer_b[start + 18],
+            );
+            let hour = radio_datetime_helpers::get_bcd_value(&self.bit_buffer_a, start + 5, start);
+            let minute =
+                radio_datetime_helpers::get_bcd_value(&self.bit_buffer_a, start + 12, start + 6);
+            if parity == Some(true)
+                && self.end_of_minute_marker_present()
+                && hour.is_some_and(|h| h < 24)
+                && minute.is_some_and(|m| m < 60)
+            {
+                self.hour = hour;
+                self.minute = minute;
+                self.summer_time = self.bit_buffer_b[start + 19];
+                self.first_minute = false;
+                decoded = true;
+            }
+        }
+        if !decoded && !self.first_minute {
+            if let (Some(hour), Some(minute)) = (self.hour, self.minute) {
+                self.minute = Some((minute + 1) % 60);
+                if minute == 59 {
+                    self.hour = Some((hour + 1) % 24);
+                }
+            }
+        }
+    }
+}
+
+impl EdgeFraming for MsfTimeOnly {
+    fn edge_state(&mut self) -> (&mut bool, &mut u32, &mut u32) {
+        (
+            &mut self.before_first_edge,
+            &mut self.t0,
+            &mut self.old_t_diff,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::minute_edges;
+
+    fn run(msf: &mut MsfTimeOnly, edges: &[(bool, u32)]) {
+        for &(is_low_edge, t) in edges {
+            msf.handle_new_edge(is_low_edge, t);
+            if msf.get_new_second() {
+                if msf.get_second() + 1 == msf.get_minute_length() {
+                    msf.decode_time();
+                }
+                msf.increase_second();
+            }
+        }
+    }
+
+    #[test]
+    fn test_decode_time() {
+        let mut msf = MsfTimeOnly::new();
+        assert_eq!(msf.get_first_minute(), true);
+        run(&mut msf, &minute_edges(1_000_000));
+        assert_eq!(msf.get_first_minute(), false);
+        assert_eq!(msf.get_hour(), Some(14));
+        assert_eq!(msf.get_minute(), Some(58));
+        assert_eq!(msf.get_summer_time(), Some(true));
+        assert_eq!(msf.get_second(), 0);
+        assert_eq!(msf.get_bit_a(37), None); // not buffered
+    }
+    #[test]
+    fn test_decode_time_propagate() {
+        let mut msf = MsfTimeOnly::new();
+        run(&mut msf, &minute_edges(1_000_000));
+        let mut edges = minute_edges(61_000_000);
+        for edge in edges.iter_mut() {
+            if *edge == (true, 105_100_000) {
+                *edge = (true, 105_200_000); // corrupt second 44, the hour parity fails
+            }
+        }
+        run(&mut msf, &edges[1..]);
+        assert_eq!(msf.get_hour(), Some(14));
+        assert_eq!(msf.get_minute(), Some(59));
+    }
+}
@@ -0,0 +1,100 @@
+//! Phase of a change between GMT and BST, which takes place at 01:00 UTC.
+//!
+//! In spring the local hour 01:00-01:59 GMT is skipped, the clock jumps from 00:59 GMT to
+//! 02:00 BST. In autumn the local hour 01:00-01:59 is repeated, the clock jumps back from
+//! 01:59 BST to 01:00 GMT. `MSFUtils::unix_time_at()` follows the DST state of the decoded
+//! date/time, so it stays continuous in both cases.
+
+use crate::upcoming_events::DstDirection;
+use crate::MSFUtils;
+use radio_datetime_utils::{DST_ANNOUNCED, DST_PROCESSED, DST_SUMMER};
+
+/// Where the decoded date/time is relative to a change between GMT and BST.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TransitionPhase {
+    /// No change is announced or just took place.
+    Steady,
+    /// A change is announced for the top of the hour.
+    Announced(DstDirection),
+    /// The change took place at the start of this minute.
+    Processed(DstDirection),
+    /// Later in the hour after a change to GMT, the local times repeat those of the hour
+    /// before.
+    RepeatedHour,
+}
+
+impl MSFUtils {
+    /// Return where the decoded date/time is relative to a change between GMT and BST.
+    ///
+    /// Returns None if the DST state is not known.
+    pub fn transition_phase(&self) -> Option<TransitionPhase> {
+        let rdt = &self.radio_datetime;
+        let dst = rdt.get_dst()?;
+        let summer = dst & DST_SUMMER != 0;
+        if dst & DST_PROCESSED != 0 {
+            return Some(TransitionPhase::Processed(if summer {
+                DstDirection::ToSummer
+            } else {
+                DstDirection::ToWinter
+            }));
+        }
+        if dst & DST_ANNOUNCED != 0 {
+            return Some(TransitionPhase::Announced(if summer {
+                DstDirection::ToWinter
+            } else {
+                DstDirection::ToSummer
+            }));
+        }
+        // the change to GMT takes place on the last Sunday of October
+        let repeated = !summer
+            && rdt.get_month() == Some(10)
+            && rdt.get_weekday() == Some(0)
+            && rdt.get_day().is_some_and(|d| d >= 25)
+            && rdt.get_hour() == Some(1);
+        Some(if repeated {
+            TransitionPhase::RepeatedHour
+        } else {
+            TransitionPhase::Steady
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transition_to_winter() {
+        let mut msf = MSFUtils::default();
+        assert_eq!(msf.transition_phase(), None);
+        let rdt = &mut msf.radio_datetime;
+        rdt.set_year(Some(22), true, false);
+        rdt.set_month(Some(10), true, false);
+        rdt.set_day(Some(30), true, false);
+        rdt.set_weekday(Some(0), true, false);
+        rdt.set_hour(Some(1), true, false);
+        rdt.set_minute(Some(59), true, false);
+        rdt.set_dst(Some(true), Some(true), false);
+        msf.second_start = Some(0);
+        assert_eq!(
+            msf.transition_phase(),
+            Some(TransitionPhase::Announced(DstDirection::ToWinter))
+        );
+        let before = msf.unix_time_at(0).unwrap(); // 01:59 BST
+
+        msf.radio_datetime.set_minute(Some(0), true, false);
+        msf.radio_datetime.set_dst(Some(false), Some(false), false);
+        assert_eq!(
+            msf.transition_phase(),
+            Some(TransitionPhase::Processed(DstDirection::ToWinter))
+        );
+        // 01:00 GMT is one minute after 01:59 BST
+        assert_eq!(msf.unix_time_at(0), Some(before + 60_000_000));
+
+        msf.radio_datetime.set_minute(Some(1), true, false);
+        msf.radio_datetime.set_dst(Some(false), Some(false), false);
+        assert_eq!(msf.transition_phase(), Some(TransitionPhase::RepeatedHour));
+        msf.radio_datetime.set_hour(Some(2), true, false);
+        assert_eq!(msf.transition_phase(), Some(TransitionPhase::Steady));
+    }
+}
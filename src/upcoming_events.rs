@@ -0,0 +1,149 @@
+//! Announced time anomalies (DST changes and leap seconds) as a list of typed events.
+//!
+//! MSF announces DST changes, but not leap seconds. These are taken from the leap second table
+//! set using `MSFUtils::set_leap_table()`.
+
+use crate::MSFUtils;
+use radio_datetime_utils::{DST_ANNOUNCED, DST_SUMMER};
+
+/// Direction of a DST change.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DstDirection {
+    /// From winter time (GMT) to summer time (BST).
+    ToSummer,
+    /// From summer time (BST) to winter time (GMT).
+    ToWinter,
+}
+
+/// Direction of a leap second.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LeapDirection {
+    /// A second is inserted, the minute lasts 61 seconds.
+    Positive,
+    /// A second is removed, the minute lasts 59 seconds.
+    Negative,
+}
+
+/// Broadcast (local) time at which an event takes effect.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EventTime {
+    pub hour: u8,
+    pub minute: u8,
+}
+
+/// An announced anomaly of the broadcast time.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UpcomingEvent {
+    /// The DST state changes at the given time.
+    DstChange {
+        direction: DstDirection,
+        at: EventTime,
+    },
+    /// A leap second occurs in the minute before the given time, according to the leap second
+    /// table.
+    LeapSecond {
+        direction: LeapDirection,
+        at: EventTime,
+    },
+}
+
+impl MSFUtils {
+    /// Return the announced DST changes, and the leap seconds of the leap second table which
+    /// take place later in the current hour.
+    pub fn upcoming_events(&self) -> impl Iterator<Item = UpcomingEvent> {
+        let mut events = [None; 2];
+        let rdt = &self.radio_datetime;
+        // both kinds of events take place at the top of the hour
+        if let (Some(hour), Some(minute)) = (rdt.get_hour(), rdt.get_minute()) {
+            let at = EventTime {
+                hour: (hour + 1) % 24,
+                minute: 0,
+            };
+            if let Some(dst) = self.radio_datetime.get_dst() {
+                if dst & DST_ANNOUNCED != 0 {
+                    events[0] = Some(UpcomingEvent::DstChange {
+                        direction: if dst & DST_SUMMER != 0 {
+                            DstDirection::ToWinter
+                        } else {
+                            DstDirection::ToSummer
+                        },
+                        at,
+                    });
+                }
+            }
+            events[1] = self
+                .leap_table
+                .iter()
+                .find(|e| {
+                    (Some(e.year), Some(e.month), Some(e.day), e.hour)
+                        == (rdt.get_year(), rdt.get_month(), rdt.get_day(), hour)
+                        && e.minute >= minute
+                })
+                .map(|e| UpcomingEvent::LeapSecond {
+                    direction: e.direction(),
+                    at,
+                });
+        }
+        events.into_iter().flatten()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::leap_table::LeapSecondEntry;
+    use crate::test_utils::filled_msf;
+
+    #[test]
+    fn test_upcoming_events_none() {
+        let mut msf = MSFUtils::default();
+        assert_eq!(msf.upcoming_events().count(), 0);
+        msf = filled_msf();
+        let _ = msf.decode_minute(false);
+        assert_eq!(msf.upcoming_events().count(), 0);
+    }
+    #[test]
+    fn test_upcoming_events_dst_change() {
+        let mut msf = filled_msf();
+        msf.bit_buffer_b[53] = Some(true); // announce a DST change
+        let _ = msf.decode_minute(false);
+        let mut events = msf.upcoming_events();
+        assert_eq!(
+            events.next(),
+            Some(UpcomingEvent::DstChange {
+                direction: DstDirection::ToWinter,
+                at: EventTime {
+                    hour: 15,
+                    minute: 0
+                }
+            })
+        );
+        assert_eq!(events.next(), None);
+    }
+    #[test]
+    fn test_upcoming_events_leap_second() {
+        static TABLE: [LeapSecondEntry; 1] = [LeapSecondEntry {
+            year: 22,
+            month: 10,
+            day: 23,
+            hour: 14,
+            minute: 59,
+            minute_length: 61,
+        }];
+        let mut msf = filled_msf();
+        msf.set_leap_table(&TABLE);
+        let _ = msf.decode_minute(false);
+        assert_eq!(
+            msf.upcoming_events().collect::<Vec<_>>(),
+            [UpcomingEvent::LeapSecond {
+                direction: LeapDirection::Positive,
+                at: EventTime {
+                    hour: 15,
+                    minute: 0
+                }
+            }]
+        );
+        msf.radio_datetime.set_hour(Some(15), true, false);
+        assert_eq!(msf.upcoming_events().count(), 0); // passed
+    }
+}